@@ -0,0 +1,39 @@
+/*! Compares a trivially-foldable pipeline with and without an optimization
+barrier inserted by `.pipe_black_box()`.
+
+Run with `cargo bench`. Without the barrier, the sum is computed at compile time
+and the benchmark measures nothing; with it, the loop actually executes.
+!*/
+
+#![feature(test)]
+
+extern crate tap;
+extern crate test;
+
+use tap::prelude::*;
+use test::Bencher;
+
+const DATA: [u64; 32] = {
+	let mut out = [0; 32];
+	let mut idx = 0;
+	while idx < out.len() {
+		out[idx] = idx as u64;
+		idx += 1;
+	}
+	out
+};
+
+#[bench]
+fn fold_plain(b: &mut Bencher) {
+	b.iter(|| DATA.iter().sum::<u64>());
+}
+
+#[bench]
+fn fold_black_box(b: &mut Bencher) {
+	b.iter(|| DATA.pipe_black_box().iter().sum::<u64>());
+}
+
+#[bench]
+fn fold_tap_black_box(b: &mut Bencher) {
+	b.iter(|| DATA.tap_black_box().iter().sum::<u64>());
+}
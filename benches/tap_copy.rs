@@ -0,0 +1,48 @@
+/*! Compares tapping small `Copy` values by reference and by value.
+
+Run with `cargo bench`. Both loops fold the same bytes through a tap that
+records a histogram; the only difference is whether the effect function
+receives `&u8` or `u8`.
+!*/
+
+#![feature(test)]
+
+extern crate tap;
+extern crate test;
+
+use tap::prelude::*;
+use test::{black_box, Bencher};
+
+const DATA: [u8; 256] = {
+	let mut out = [0; 256];
+	let mut idx = 0;
+	while idx < out.len() {
+		out[idx] = (idx * 7) as u8;
+		idx += 1;
+	}
+	out
+};
+
+#[bench]
+fn histogram_tap(b: &mut Bencher) {
+	b.iter(|| {
+		let mut hist = [0u32; 256];
+		let sum = black_box(&DATA)
+			.iter()
+			.map(|&n| n.tap(|n| hist[*n as usize] += 1) as u32)
+			.sum::<u32>();
+		(sum, hist)
+	});
+}
+
+#[bench]
+fn histogram_tap_copy(b: &mut Bencher) {
+	b.iter(|| {
+		let mut hist = [0u32; 256];
+		let sum = black_box(&DATA)
+			.iter()
+			.map(|&n| n.tap_copy(|n| hist[n as usize] += 1) as u32)
+			.sum::<u32>();
+		(sum, hist)
+	});
+}
@@ -0,0 +1,25 @@
+/*! Two hundred `.tap_dyn()` call sites, sharing one copy of the method.
+
+This is one half of the code-size comparison in `tests/codegen.rs`; the other
+half is `codegen_generic.rs`.
+!*/
+
+extern crate tap;
+
+use std::hint::black_box;
+use tap::prelude::*;
+
+macro_rules! sites {
+	($val:ident; $($hi:literal)*) => {
+		$( sites!(@row $val $hi; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19); )*
+	};
+	(@row $val:ident $hi:literal; $($lo:literal)*) => {
+		$( let $val = $val.tap_dyn(&|v| { black_box(*v + $hi * 20 + $lo); }); )*
+	};
+}
+
+fn main() {
+	let val = black_box(0u64);
+	sites!(val; 0 1 2 3 4 5 6 7 8 9);
+	black_box(val);
+}
@@ -0,0 +1,188 @@
+/*! # Allocation Counting
+
+This module provides [`CountingAllocator`], a global-allocator wrapper that
+counts every allocation made through it, and the statistics type it produces.
+Together with [`Tap::tap_alloc_count`], it allows tests to assert that a stage
+of a pipeline allocates exactly as much as expected – often, not at all.
+
+The counters are kept *per thread*. A measurement taken around an effect
+function sees only the allocations made by the thread running it, and so is not
+disturbed by other tests that the harness runs concurrently. Allocations made by
+threads that the effect function spawns are not counted.
+
+The counters only move when `CountingAllocator` is installed as the program’s
+`#[global_allocator]`. Without it, every measurement reports zero, and so every
+allocation assertion passes vacuously. Install it in the test binary:
+
+```rust
+use std::alloc::System;
+use tap::{alloc::CountingAllocator, tap::Tap};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+
+let v = vec![1, 2, 3].tap_alloc_count(
+  |v| v.iter().map(|n| n * 2).collect::<Vec<_>>(),
+  |stats| assert_eq!(stats.allocations, 1),
+);
+```
+
+[`CountingAllocator`]: struct.CountingAllocator.html
+[`Tap::tap_alloc_count`]: ../tap/trait.Tap.html#method.tap_alloc_count
+!*/
+
+use core::{
+	alloc::{GlobalAlloc, Layout},
+	cell::Cell,
+};
+use std::{alloc::System, thread_local};
+
+thread_local! {
+	static STATS: Cell<AllocStats> = const { Cell::new(AllocStats::ZERO) };
+}
+
+/// Counts of allocator activity on one thread.
+///
+/// Values returned by [`stats`] are running totals since the thread started.
+/// Values passed to the effect functions of allocation taps are the difference
+/// between two such totals.
+///
+/// [`stats`]: fn.stats.html
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+	/// The number of fresh allocations.
+	pub allocations: u64,
+	/// The number of deallocations.
+	pub deallocations: u64,
+	/// The number of reallocations, which are not counted as allocations.
+	pub reallocations: u64,
+	/// The number of bytes requested, including the new size of each
+	/// reallocation.
+	pub bytes_allocated: u64,
+	/// The number of bytes released, including the old size of each
+	/// reallocation.
+	pub bytes_deallocated: u64,
+}
+
+impl AllocStats {
+	/// Statistics recording no allocator activity.
+	pub const ZERO: Self = Self {
+		allocations: 0,
+		deallocations: 0,
+		reallocations: 0,
+		bytes_allocated: 0,
+		bytes_deallocated: 0,
+	};
+
+	/// Computes the activity between an `earlier` snapshot and this one.
+	#[inline]
+	pub fn since(self, earlier: Self) -> Self {
+		Self {
+			allocations: self.allocations.wrapping_sub(earlier.allocations),
+			deallocations: self
+				.deallocations
+				.wrapping_sub(earlier.deallocations),
+			reallocations: self
+				.reallocations
+				.wrapping_sub(earlier.reallocations),
+			bytes_allocated: self
+				.bytes_allocated
+				.wrapping_sub(earlier.bytes_allocated),
+			bytes_deallocated: self
+				.bytes_deallocated
+				.wrapping_sub(earlier.bytes_deallocated),
+		}
+	}
+
+	/// Tests whether any memory was allocated or reallocated.
+	#[inline]
+	pub fn allocated(&self) -> bool {
+		self.allocations != 0 || self.reallocations != 0
+	}
+}
+
+/// Takes a snapshot of the allocator activity on the current thread.
+#[inline]
+pub fn stats() -> AllocStats {
+	STATS.try_with(Cell::get).unwrap_or_default()
+}
+
+/// Applies an update to the current thread’s counters.
+///
+/// Allocations made while the thread is being torn down, after its
+/// thread-local storage has been destroyed, are not counted.
+#[inline]
+fn update(func: impl FnOnce(&mut AllocStats)) {
+	let _ = STATS.try_with(|cell| {
+		let mut stats = cell.get();
+		func(&mut stats);
+		cell.set(stats);
+	});
+}
+
+/// A global allocator that counts the activity passing through it.
+///
+/// This forwards every request to the wrapped allocator, which defaults to the
+/// system allocator, and records it in the current thread’s counters. See the
+/// [module documentation] for how to install it.
+///
+/// [module documentation]: index.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CountingAllocator<A = System> {
+	inner: A,
+}
+
+impl<A> CountingAllocator<A> {
+	/// Wraps an allocator.
+	#[inline]
+	pub const fn new(inner: A) -> Self {
+		Self { inner }
+	}
+}
+
+unsafe impl<A> GlobalAlloc for CountingAllocator<A>
+where
+	A: GlobalAlloc,
+{
+	#[inline]
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		update(|s| {
+			s.allocations += 1;
+			s.bytes_allocated += layout.size() as u64;
+		});
+		self.inner.alloc(layout)
+	}
+
+	#[inline]
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		update(|s| {
+			s.allocations += 1;
+			s.bytes_allocated += layout.size() as u64;
+		});
+		self.inner.alloc_zeroed(layout)
+	}
+
+	#[inline]
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		update(|s| {
+			s.deallocations += 1;
+			s.bytes_deallocated += layout.size() as u64;
+		});
+		self.inner.dealloc(ptr, layout)
+	}
+
+	#[inline]
+	unsafe fn realloc(
+		&self,
+		ptr: *mut u8,
+		layout: Layout,
+		new_size: usize,
+	) -> *mut u8 {
+		update(|s| {
+			s.reallocations += 1;
+			s.bytes_allocated += new_size as u64;
+			s.bytes_deallocated += layout.size() as u64;
+		});
+		self.inner.realloc(ptr, layout, new_size)
+	}
+}
@@ -0,0 +1,216 @@
+/*! # Circuit Breaking for Taps
+
+An effect function that talks to an external service, such as a metrics
+collector or a remote log, can fail repeatedly when that service is down, and
+keep paying for each failure on every tap. A [`CircuitBreaker`] stops calling
+an effect that keeps failing, and occasionally lets one call through to find
+out whether it has recovered.
+
+The breaker has three states:
+
+- **Closed**: every call runs. Consecutive failures are counted, and reaching
+  the failure threshold opens the circuit.
+- **Open**: no call runs. Once the reset timeout has passed since the circuit
+  opened, the next call moves it to half-open.
+- **HalfOpen**: exactly one trial call runs, and all others are skipped while
+  it is in flight. If it succeeds, the circuit closes; if it fails, the circuit
+  opens again, and the timeout restarts.
+
+[`Tap::tap_circuit`] runs an effect function through a breaker.
+
+```rust
+use std::time::Duration;
+use tap::{circuit_breaker::CircuitBreaker, tap::Tap};
+
+static EXPORTER: CircuitBreaker = CircuitBreaker::new(3, Duration::from_secs(30));
+
+let mut attempts = 0;
+for n in 0 .. 10 {
+  let _ = n.tap_circuit(&EXPORTER, |_| Err(attempts += 1));
+}
+assert_eq!(attempts, 3);
+```
+
+[`CircuitBreaker`]: struct.CircuitBreaker.html
+[`Tap::tap_circuit`]: ../tap/trait.Tap.html#method.tap_circuit
+!*/
+
+use core::{
+	fmt,
+	sync::atomic::{AtomicU32, AtomicU64, AtomicU8, Ordering},
+	time::Duration,
+};
+use std::{sync::OnceLock, time::Instant};
+
+/// The state of a [`CircuitBreaker`].
+///
+/// [`CircuitBreaker`]: struct.CircuitBreaker.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CircuitState {
+	/// Calls run, and failures are counted.
+	Closed,
+	/// Calls are skipped until the reset timeout passes.
+	Open,
+	/// A single trial call is running, and all others are skipped.
+	HalfOpen,
+}
+
+impl CircuitState {
+	#[inline]
+	fn from_u8(raw: u8) -> Self {
+		match raw {
+			CLOSED => Self::Closed,
+			OPEN => Self::Open,
+			_ => Self::HalfOpen,
+		}
+	}
+}
+
+const CLOSED: u8 = 0;
+const OPEN: u8 = 1;
+const HALF_OPEN: u8 = 2;
+
+/// Stops running an effect function that keeps failing.
+///
+/// See the [module documentation] for the states a breaker moves through. All
+/// of its state is held in atomics, so a single breaker can be shared by
+/// every thread that runs a tap. The clock starts at the first call, so a
+/// breaker can be built in a `static`.
+///
+/// [module documentation]: index.html
+pub struct CircuitBreaker {
+	failure_threshold: u32,
+	/// The reset timeout, in nanoseconds.
+	reset_timeout: u64,
+	state: AtomicU8,
+	/// The number of consecutive failures while closed.
+	failures: AtomicU32,
+	/// The nanoseconds, after `origin`, at which the circuit last opened.
+	opened_at: AtomicU64,
+	origin: OnceLock<Instant>,
+}
+
+impl CircuitBreaker {
+	/// Creates a closed breaker, which opens after `failure_threshold`
+	/// consecutive failures, and tries again `reset_timeout` after opening.
+	///
+	/// # Panics
+	///
+	/// This panics if `failure_threshold` is zero.
+	#[inline]
+	pub const fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+		assert!(failure_threshold != 0, "failure threshold must be nonzero");
+		let nanos = reset_timeout.as_nanos();
+		Self {
+			failure_threshold,
+			reset_timeout: if nanos > u64::MAX as u128 {
+				u64::MAX
+			} else {
+				nanos as u64
+			},
+			state: AtomicU8::new(CLOSED),
+			failures: AtomicU32::new(0),
+			opened_at: AtomicU64::new(0),
+			origin: OnceLock::new(),
+		}
+	}
+
+	/// Reports the current state of the breaker.
+	///
+	/// An open breaker whose timeout has passed still reports `Open`, until
+	/// the next call moves it to `HalfOpen`.
+	#[inline]
+	pub fn state(&self) -> CircuitState {
+		CircuitState::from_u8(self.state.load(Ordering::Acquire))
+	}
+
+	/// Runs `func` if the circuit allows it, and records its outcome.
+	///
+	/// This returns the result of `func`, or `None` if the call was skipped.
+	/// If `func` panics during a half-open trial, the trial counts as a
+	/// failure, and the circuit opens again.
+	pub fn call(
+		&self,
+		func: impl FnOnce() -> Result<(), ()>,
+	) -> Option<Result<(), ()>> {
+		let trial = match self.state.load(Ordering::Acquire) {
+			CLOSED => false,
+			OPEN => {
+				let opened_at = self.opened_at.load(Ordering::Acquire);
+				if self.now().saturating_sub(opened_at) < self.reset_timeout {
+					return None;
+				}
+				//  only the caller that wins this exchange makes the trial
+				self.state
+					.compare_exchange(
+						OPEN,
+						HALF_OPEN,
+						Ordering::AcqRel,
+						Ordering::Acquire,
+					)
+					.ok()?;
+				true
+			}
+			_ => return None,
+		};
+		let mut guard = TrialGuard {
+			breaker: self,
+			armed: trial,
+		};
+		let out = func();
+		guard.armed = false;
+		match (out, trial) {
+			(Ok(()), _) => {
+				self.failures.store(0, Ordering::Release);
+				if trial {
+					self.state.store(CLOSED, Ordering::Release);
+				}
+			}
+			(Err(()), true) => self.open(),
+			(Err(()), false) => {
+				let failures = self.failures.fetch_add(1, Ordering::AcqRel) + 1;
+				if failures >= self.failure_threshold {
+					self.open();
+				}
+			}
+		}
+		Some(out)
+	}
+
+	fn open(&self) {
+		self.failures.store(0, Ordering::Release);
+		self.opened_at.store(self.now(), Ordering::Release);
+		self.state.store(OPEN, Ordering::Release);
+	}
+
+	fn now(&self) -> u64 {
+		let origin = self.origin.get_or_init(Instant::now);
+		origin.elapsed().as_nanos() as u64
+	}
+}
+
+/// Reopens the circuit if a half-open trial unwinds before it reports its
+/// outcome, so that a panicking effect cannot leave the breaker half-open.
+struct TrialGuard<'a> {
+	breaker: &'a CircuitBreaker,
+	armed: bool,
+}
+
+impl Drop for TrialGuard<'_> {
+	fn drop(&mut self) {
+		if self.armed {
+			self.breaker.open();
+		}
+	}
+}
+
+impl fmt::Debug for CircuitBreaker {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("CircuitBreaker")
+			.field("state", &self.state())
+			.field("failures", &self.failures.load(Ordering::Relaxed))
+			.field("failure_threshold", &self.failure_threshold)
+			.field("reset_timeout", &Duration::from_nanos(self.reset_timeout))
+			.finish()
+	}
+}
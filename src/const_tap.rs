@@ -0,0 +1,148 @@
+/*! # Taps in Constant Contexts
+
+This module provides [`ConstTap`], a copy of the core tap and pipe methods that
+can be called while evaluating `const` and `static` items. It is only available
+when the `nightly-const` feature is enabled.
+
+The methods are not added to `Tap` and `Pipe` directly, because a `const` trait
+requires *every* method to be const-callable, and most taps – timing, logging,
+channel sends – cannot be. They are also named with a `_const` suffix, so that
+importing both traits through the prelude does not make `.tap()` ambiguous.
+
+```rust
+#![feature(const_trait_impl)]
+use tap::const_tap::ConstTap;
+
+const fn fill(table: &mut [u8; 4]) {
+  *table = [1, 2, 4, 8];
+}
+
+const POWERS: [u8; 4] = [0; 4].tap_mut_const(fill);
+assert_eq!(POWERS, [1, 2, 4, 8]);
+```
+
+# Const-callable methods
+
+| `ConstTap` method      | Equivalent of       |
+|------------------------|---------------------|
+| `tap_const`            | `Tap::tap`          |
+| `tap_mut_const`        | `Tap::tap_mut`      |
+| `tap_dbg_const`        | `Tap::tap_dbg`      |
+| `tap_mut_dbg_const`    | `Tap::tap_mut_dbg`  |
+| `pipe_const`           | `Pipe::pipe`        |
+| `pipe_ref_const`       | `Pipe::pipe_ref`    |
+| `pipe_ref_mut_const`   | `Pipe::pipe_ref_mut`|
+
+The view-converting taps, such as `tap_borrow` and `tap_deref`, are not
+included: they call trait methods (`Borrow::borrow`, `Deref::deref`) that are
+not yet `const` for most types in the standard library. The `_dbg` methods
+check `debug_assertions` of the `tap` crate itself, as their `Tap`
+counterparts do.
+
+# Stability
+
+This module is built on the `const_trait_impl` and `const_destruct` language
+features, which are incomplete and whose syntax has changed several times. It
+compiles against the nightly toolchain this crate is tested with, and may break
+on any other. Crates calling these methods in a constant context must also
+enable `#![feature(const_trait_impl)]` themselves.
+
+Effect functions must be `const fn` items, or `const` closures where the
+`const_closures` feature is enabled. Outside of constant contexts, the methods
+accept ordinary closures and behave exactly like their `Tap` and `Pipe`
+counterparts.
+
+[`ConstTap`]: trait.ConstTap.html
+!*/
+
+use core::marker::Destruct;
+
+/** Tapping and piping that can be evaluated at compile time.
+
+See the [module documentation] for the restrictions on its use.
+
+[module documentation]: index.html
+**/
+pub const trait ConstTap
+where
+	Self: Sized,
+{
+	/// Const-callable equivalent of `Tap::tap`.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	fn tap_const<F>(self, func: F) -> Self
+	where
+		F: [const] FnOnce(&Self) + [const] Destruct,
+	{
+		func(&self);
+		self
+	}
+
+	/// Const-callable equivalent of `Tap::tap_mut`.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	fn tap_mut_const<F>(mut self, func: F) -> Self
+	where
+		F: [const] FnOnce(&mut Self) + [const] Destruct,
+	{
+		func(&mut self);
+		self
+	}
+
+	/// Const-callable equivalent of `Tap::tap_dbg`.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	fn tap_dbg_const<F>(self, func: F) -> Self
+	where
+		F: [const] FnOnce(&Self) + [const] Destruct,
+	{
+		if cfg!(debug_assertions) {
+			func(&self);
+		}
+		self
+	}
+
+	/// Const-callable equivalent of `Tap::tap_mut_dbg`.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	fn tap_mut_dbg_const<F>(mut self, func: F) -> Self
+	where
+		F: [const] FnOnce(&mut Self) + [const] Destruct,
+	{
+		if cfg!(debug_assertions) {
+			func(&mut self);
+		}
+		self
+	}
+
+	/// Const-callable equivalent of `Pipe::pipe`.
+	#[inline(always)]
+	fn pipe_const<R, F>(self, func: F) -> R
+	where
+		F: [const] FnOnce(Self) -> R + [const] Destruct,
+	{
+		func(self)
+	}
+
+	/// Const-callable equivalent of `Pipe::pipe_ref`.
+	#[inline(always)]
+	fn pipe_ref_const<'a, R, F>(&'a self, func: F) -> R
+	where
+		F: [const] FnOnce(&'a Self) -> R + [const] Destruct,
+		R: 'a,
+	{
+		func(self)
+	}
+
+	/// Const-callable equivalent of `Pipe::pipe_ref_mut`.
+	#[inline(always)]
+	fn pipe_ref_mut_const<'a, R, F>(&'a mut self, func: F) -> R
+	where
+		F: [const] FnOnce(&'a mut Self) -> R + [const] Destruct,
+		R: 'a,
+	{
+		func(self)
+	}
+}
+
+impl<T> const ConstTap for T {}
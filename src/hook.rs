@@ -0,0 +1,102 @@
+/*! # Global Tap Hook
+
+When the `global-hook` feature is enabled, every call to [`Tap::tap`] and
+[`Tap::tap_mut`] reports itself to a single, process-wide callback before
+running its effect function. This enables crate-wide tracing of taps without
+touching any call site. The hook is stored in an `AtomicPtr`, so the feature has
+no effect on targets without pointer-width atomics.
+
+The callback is a plain function pointer, registered once with
+[`set_global_hook`]. Function pointers capture no state, and so are always
+`Send + Sync`; a hook that needs state must keep it in a `static`, and must
+synchronize access to it, as taps may run on any thread concurrently.
+
+Taps are generic over every type, including types that borrow non-`'static`
+data, so the hook cannot receive the tapped value as `&dyn Any`. Instead it
+receives a [`TapEvent`] describing the tap method, the name of the tapped type,
+and the source location of the call.
+
+# Performance
+
+With the feature disabled, none of this code exists, and taps compile exactly as
+they would without it. With the feature enabled, every `.tap()` and `.tap_mut()`
+performs an atomic load, and calls the hook if one is registered. This is cheap,
+but not free, and defeats the assumption that a tap with an empty effect
+function disappears entirely. The feature is intended for diagnostic builds.
+
+[`Tap::tap`]: ../tap/trait.Tap.html#method.tap
+[`Tap::tap_mut`]: ../tap/trait.Tap.html#method.tap_mut
+[`TapEvent`]: struct.TapEvent.html
+[`set_global_hook`]: fn.set_global_hook.html
+!*/
+
+use core::{
+	panic::Location,
+	ptr,
+	sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// The signature of a global tap hook.
+pub type TapHook = fn(&TapEvent);
+
+/// A description of a single tap, passed to the global hook.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct TapEvent {
+	/// The name of the tap method, such as `"tap"` or `"tap_mut"`.
+	pub method: &'static str,
+	/// The name of the tapped type, as produced by `core::any::type_name`.
+	pub type_name: &'static str,
+	/// The source location of the tap call.
+	pub location: &'static Location<'static>,
+}
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers the global tap hook.
+///
+/// The hook can only be set once. If a hook has already been registered, this
+/// returns the hook that was rejected.
+///
+/// # Examples
+///
+/// ```rust
+/// use tap::{hook::TapEvent, Tap};
+///
+/// fn trace(event: &TapEvent) {
+///   println!("{} on {} at {}", event.method, event.type_name, event.location);
+/// }
+///
+/// tap::set_global_hook(trace).unwrap();
+/// let five = 5.tap(|_| {});
+/// ```
+pub fn set_global_hook(hook: TapHook) -> Result<(), TapHook> {
+	HOOK.compare_exchange(
+		ptr::null_mut(),
+		hook as *mut (),
+		Ordering::AcqRel,
+		Ordering::Acquire,
+	)
+	.map(drop)
+	.map_err(|_| hook)
+}
+
+/// Reports a tap to the global hook, if one is registered.
+#[inline]
+pub(crate) fn dispatch(
+	method: &'static str,
+	type_name: &'static str,
+	location: &'static Location<'static>,
+) {
+	let hook = HOOK.load(Ordering::Acquire);
+	if hook.is_null() {
+		return;
+	}
+	//  SAFETY: the only non-null values ever stored are `TapHook` pointers.
+	let hook = unsafe { core::mem::transmute::<*mut (), TapHook>(hook) };
+	hook(&TapEvent {
+		method,
+		type_name,
+		location,
+	});
+}
@@ -0,0 +1,69 @@
+/*! # Inspection Under `Iterator` Naming
+
+The standard library calls item inspection [`Iterator::inspect`]. This module
+provides the same name for value inspection: [`Inspect::inspect`] and
+[`Inspect::inspect_mut`] are exactly [`Tap::tap`] and [`Tap::tap_mut`] under
+different names. `Tap` remains the canonical trait, and `Inspect` forwards to
+it.
+
+This trait is **not** part of the prelude. It is blanket-implemented on every
+type, including iterators, so bringing it into scope alongside `Iterator` makes
+`iter.inspect(..)` ambiguous. Import it by name in code that does not call
+`Iterator::inspect`. `Option` and `Result` have inherent `inspect` methods,
+which take precedence over this trait.
+
+```rust
+use tap::inspect::Inspect;
+
+let mut seen = 0;
+let len = "hello".to_string()
+  .inspect(|s| seen = s.len())
+  .inspect_mut(|s| s.push('!'))
+  .len();
+assert_eq!((seen, len), (5, 6));
+```
+
+[`Inspect::inspect`]: trait.Inspect.html#method.inspect
+[`Inspect::inspect_mut`]: trait.Inspect.html#method.inspect_mut
+[`Iterator::inspect`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.inspect
+[`Tap::tap`]: ../tap/trait.Tap.html#method.tap
+[`Tap::tap_mut`]: ../tap/trait.Tap.html#method.tap_mut
+!*/
+
+use crate::tap::Tap;
+
+/** Value inspection named after `Iterator::inspect`.
+
+This trait is implemented on all types, and each of its methods is equivalent
+to the `Tap` method it names.
+**/
+pub trait Inspect
+where
+	Self: Tap,
+{
+	/// Immutable access to a value.
+	///
+	/// This is equivalent to [`Tap::tap`].
+	///
+	/// [`Tap::tap`]: ../tap/trait.Tap.html#method.tap
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn inspect(self, func: impl FnOnce(&Self)) -> Self {
+		self.tap(func)
+	}
+
+	/// Mutable access to a value.
+	///
+	/// This is equivalent to [`Tap::tap_mut`].
+	///
+	/// [`Tap::tap_mut`]: ../tap/trait.Tap.html#method.tap_mut
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn inspect_mut(self, func: impl FnOnce(&mut Self)) -> Self {
+		self.tap_mut(func)
+	}
+}
+
+impl<T> Inspect for T where T: Sized {}
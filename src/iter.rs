@@ -0,0 +1,4331 @@
+/*! # Iterator Inspection
+
+This module provides taps over the *items* of an iterator, rather than over the
+iterator itself. `Tap::tap` on an iterator inspects the iterator value once;
+`TapIter::tap_each` inspects every item as it is produced.
+
+The adapters are lazy, like every other iterator adapter: the effect function
+runs only as items are pulled through, in the order they are pulled. Items taken
+from the back of a double-ended iterator are inspected when they are taken.
+
+Each adapter forwards `DoubleEndedIterator`, `ExactSizeIterator`, and
+`FusedIterator` from the iterator it wraps, so that tapping a pipeline does not
+remove any capability from it.
+
+Collections are not iterators, but can be iterated by reference. [`TapEach`]
+provides eager taps over the elements of such a collection, which visit every
+element immediately and return the collection itself.
+
+## Debug-only Adapters
+
+The `_dbg` methods, and the `_check` assertions, are selected by the
+`debug_assertions` setting that this crate is compiled with. In debug builds
+they return an adapter; in release builds they return the iterator itself, so
+that a release pipeline pays nothing for them. The two return types differ, and
+the code after the call sees whichever one the profile selected:
+
+- Some adapters are not double-ended, so `.rev()` after them compiles only in
+  release builds, when the iterator they would wrap is double-ended.
+- The adapters are `Clone` only when their effect functions are, and do not
+  forward the inherent methods of the iterator they wrap, such as
+  `vec::IntoIter::as_slice`.
+- A binding or signature that names the returned type matches only one profile.
+
+Code after a debug-only adapter should use only what both types provide. Build
+it with `--release` as well as in debug to check that it does.
+
+This module is enabled by the `iter` feature, which is on by default.
+
+[`TapEach`]: trait.TapEach.html
+!*/
+
+use core::{cmp, fmt, iter::FusedIterator, num::NonZeroUsize};
+
+#[cfg(feature = "alloc")]
+use liballoc::vec::{self, Vec};
+
+#[cfg(feature = "std")]
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
+
+/** Point-free inspection of the items of an iterator.
+
+This trait is implemented on all iterators. Its methods wrap the iterator in an
+adapter that passes each item to an effect function on the way through.
+**/
+pub trait TapIter
+where
+	Self: Sized + Iterator,
+{
+	/// Immutable access to each item of an iterator.
+	///
+	/// This behaves like `Iterator::inspect`, under the name used by the rest
+	/// of this crate.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut seen = vec![];
+	/// let sum: i32 = [1, 2, 3]
+	///   .iter()
+	///   .tap_each(|n| seen.push(**n))
+	///   .sum();
+	/// assert_eq!(sum, 6);
+	/// assert_eq!(seen, [1, 2, 3]);
+	/// ```
+	#[inline(always)]
+	fn tap_each<F>(self, func: F) -> TapEachIter<Self, F>
+	where
+		F: FnMut(&Self::Item),
+	{
+		TapEachIter { iter: self, func }
+	}
+
+	/// Immutable access to each item of an iterator, along with its index.
+	///
+	/// The index is the item’s position in the original sequence, counting
+	/// from the front, even when the item is taken from the back.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut seen = vec![];
+	/// let last = ['a', 'b', 'c']
+	///   .iter()
+	///   .tap_each_enumerate(|idx, c| seen.push((idx, **c)))
+	///   .next_back();
+	/// assert_eq!(last, Some(&'c'));
+	/// assert_eq!(seen, [(2, 'c')]);
+	/// ```
+	#[inline(always)]
+	fn tap_each_enumerate<F>(self, func: F) -> TapEachEnumerateIter<Self, F>
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		TapEachEnumerateIter {
+			iter: self,
+			func,
+			count: 0,
+		}
+	}
+
+	/// Mutable access to each item of an iterator.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let words = ["alpha", "beta"]
+	///   .iter()
+	///   .map(|w| w.to_string())
+	///   .tap_each_mut(|w| w.make_ascii_uppercase())
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(words, ["ALPHA", "BETA"]);
+	/// ```
+	#[inline(always)]
+	fn tap_each_mut<F>(self, func: F) -> TapEachMutIter<Self, F>
+	where
+		F: FnMut(&mut Self::Item),
+	{
+		TapEachMutIter { iter: self, func }
+	}
+
+	/// Skips items while a predicate holds, and reports the item that ended
+	/// the skipping.
+	///
+	/// This behaves like `Iterator::skip_while`, except that the first item for
+	/// which `pred` returns `false` is passed to `on_reject` before it is
+	/// yielded. `on_reject` runs at most once, and does not run at all if the
+	/// iterator is exhausted while still skipping.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut first = None;
+	/// let body = ["#", "# comment", "key = 1", "# trailing"]
+	///   .iter()
+	///   .tap_skip_while_inspect(|l| l.starts_with('#'), |l| first = Some(**l))
+	///   .count();
+	/// assert_eq!(body, 2);
+	/// assert_eq!(first, Some("key = 1"));
+	/// ```
+	#[inline(always)]
+	fn tap_skip_while_inspect<P, F>(
+		self,
+		pred: P,
+		on_reject: F,
+	) -> TapSkipWhileInspect<Self, P, F>
+	where
+		P: FnMut(&Self::Item) -> bool,
+		F: FnOnce(&Self::Item),
+	{
+		TapSkipWhileInspect {
+			iter: self,
+			pred,
+			on_reject: Some(on_reject),
+		}
+	}
+
+	/// Takes items while a predicate holds, and reports the item that ended
+	/// the taking.
+	///
+	/// This behaves like `Iterator::take_while`, except that the first item for
+	/// which `pred` returns `false` is passed to `on_stop` before it is
+	/// discarded. `on_stop` runs at most once, and does not run at all if the
+	/// iterator is exhausted while every item satisfies the predicate.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut stop = None;
+	/// let header = ["a: 1", "b: 2", "", "body"]
+	///   .iter()
+	///   .tap_take_while_inspect(|l| !l.is_empty(), |l| stop = Some(**l))
+	///   .count();
+	/// assert_eq!(header, 2);
+	/// assert_eq!(stop, Some(""));
+	/// ```
+	#[inline(always)]
+	fn tap_take_while_inspect<P, F>(
+		self,
+		pred: P,
+		on_stop: F,
+	) -> TapTakeWhileInspect<Self, P, F>
+	where
+		P: FnMut(&Self::Item) -> bool,
+		F: FnOnce(&Self::Item),
+	{
+		TapTakeWhileInspect {
+			iter: self,
+			pred,
+			on_stop: Some(on_stop),
+		}
+	}
+
+	/// Folds every item into an accumulator, showing the accumulator to an
+	/// observer after each step.
+	///
+	/// This behaves like `Iterator::fold`, and calls `observer` with a borrow
+	/// of the accumulator after each application of `func`. The observer is
+	/// not called with the initial value, and so is not called at all for an
+	/// empty iterator.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut trace = vec![];
+	/// let sum = [1, 2, 3]
+	///   .iter()
+	///   .tap_fold_inspect(0, |acc, n| acc + n, |acc| trace.push(*acc));
+	/// assert_eq!(sum, 6);
+	/// assert_eq!(trace, [1, 3, 6]);
+	/// ```
+	#[inline(always)]
+	fn tap_fold_inspect<B>(
+		self,
+		init: B,
+		mut func: impl FnMut(B, Self::Item) -> B,
+		mut observer: impl FnMut(&B),
+	) -> B {
+		self.fold(init, |acc, item| {
+			let acc = func(acc, item);
+			observer(&acc);
+			acc
+		})
+	}
+
+	/// Partitions the items into two collections, showing each item to an
+	/// observer for the side it is sorted into.
+	///
+	/// This behaves like `Iterator::partition` into two `Vec`s, and calls
+	/// `on_true` or `on_false` with each item after `pred` has classified it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut rejected = vec![];
+	/// let (evens, odds) = (1 .. 6).tap_partition_collect(
+	///   |n| n % 2 == 0,
+	///   |_| {},
+	///   |n| rejected.push(*n),
+	/// );
+	/// assert_eq!(evens, [2, 4]);
+	/// assert_eq!(odds, [1, 3, 5]);
+	/// assert_eq!(rejected, odds);
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	fn tap_partition_collect(
+		self,
+		mut pred: impl FnMut(&Self::Item) -> bool,
+		mut on_true: impl FnMut(&Self::Item),
+		mut on_false: impl FnMut(&Self::Item),
+	) -> (Vec<Self::Item>, Vec<Self::Item>) {
+		let mut yes = Vec::new();
+		let mut no = Vec::new();
+		for item in self {
+			if pred(&item) {
+				on_true(&item);
+				yes.push(item);
+			} else {
+				on_false(&item);
+				no.push(item);
+			}
+		}
+		(yes, no)
+	}
+
+	/// Partitions the items into two collections, counting each side.
+	///
+	/// This adds the number of items sorted into each side to the two counters,
+	/// which are not reset first.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let (mut hits, mut misses) = (0, 0);
+	/// let (big, small) = [3, 30, 300]
+	///   .iter()
+	///   .tap_partition_count(|n| **n >= 10, &mut hits, &mut misses);
+	/// assert_eq!((hits, misses), (2, 1));
+	/// assert_eq!(big.len(), hits);
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	fn tap_partition_count(
+		self,
+		pred: impl FnMut(&Self::Item) -> bool,
+		true_count: &mut usize,
+		false_count: &mut usize,
+	) -> (Vec<Self::Item>, Vec<Self::Item>) {
+		self.tap_partition_collect(
+			pred,
+			|_| *true_count += 1,
+			|_| *false_count += 1,
+		)
+	}
+
+	/// Collects the items into a `Vec`, and shows the result to an effect
+	/// function before returning it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut total = 0;
+	/// let words = ["tap", "pipe", "conv"]
+	///   .iter()
+	///   .map(|w| w.to_string())
+	///   .tap_collect_vec_inspect(|v| total = v.iter().map(String::len).sum());
+	/// assert_eq!(words.len(), 3);
+	/// assert_eq!(total, 11);
+	/// ```
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	fn tap_collect_vec_inspect(
+		self,
+		func: impl FnOnce(&[Self::Item]),
+	) -> Vec<Self::Item> {
+		let out = self.collect::<Vec<_>>();
+		func(&out);
+		out
+	}
+
+	/// Collects key/value pairs into a `HashMap`, and shows the result to an
+	/// effect function before returning it.
+	///
+	/// Later pairs overwrite earlier pairs with the same key, as with
+	/// `collect`; the effect function sees the map after deduplication.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let ports = vec![("http", 80), ("https", 443), ("http", 8080)]
+	///   .into_iter()
+	///   .tap_collect_hashmap_inspect(|m| assert_eq!(m.len(), 2));
+	/// assert_eq!(ports["http"], 8080);
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	fn tap_collect_hashmap_inspect<K, V>(
+		self,
+		func: impl FnOnce(&HashMap<K, V>),
+	) -> HashMap<K, V>
+	where
+		Self: Iterator<Item = (K, V)>,
+		K: Eq + Hash,
+	{
+		let out = self.collect::<HashMap<_, _>>();
+		func(&out);
+		out
+	}
+
+	/// Collects the items into a `HashSet`, and shows the result to an effect
+	/// function before returning it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut distinct = 0;
+	/// let set = [1, 2, 2, 3, 3, 3]
+	///   .iter()
+	///   .tap_collect_hashset_inspect(|s| distinct = s.len());
+	/// assert_eq!(distinct, 3);
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	fn tap_collect_hashset_inspect(
+		self,
+		func: impl FnOnce(&HashSet<Self::Item>),
+	) -> HashSet<Self::Item>
+	where
+		Self::Item: Eq + Hash,
+	{
+		let out = self.collect::<HashSet<_>>();
+		func(&out);
+		out
+	}
+
+	/// Consumes the iterator, passing each item to a function and counting
+	/// the items.
+	///
+	/// This behaves like `Iterator::for_each`, and adds the number of items to
+	/// `count`, which is not reset first.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let (mut sum, mut count) = (0, 0);
+	/// (1 ..= 4).tap_for_each_count(|n| sum += n, &mut count);
+	/// assert_eq!((sum, count), (10, 4));
+	/// ```
+	#[inline(always)]
+	fn tap_for_each_count(
+		self,
+		mut func: impl FnMut(Self::Item),
+		count: &mut usize,
+	) {
+		self.for_each(|item| {
+			func(item);
+			*count += 1;
+		});
+	}
+
+	/// Consumes the iterator, passing each item and its index to a function.
+	///
+	/// This is `Iterator::for_each` over `.enumerate()`, with the index and
+	/// item passed as separate arguments rather than as a tuple.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut lines = vec![];
+	/// ["a", "b"]
+	///   .iter()
+	///   .tap_for_each_with_index(|idx, s| lines.push(format!("{}:{}", idx, s)));
+	/// assert_eq!(lines, ["0:a", "1:b"]);
+	/// ```
+	#[inline(always)]
+	fn tap_for_each_with_index(self, mut func: impl FnMut(usize, Self::Item)) {
+		self.enumerate().for_each(|(idx, item)| func(idx, item));
+	}
+
+	/// Immutable access to every `step`th item of an iterator, along with its
+	/// index.
+	///
+	/// The effect function sees the items at indices `0`, `step`, `2 * step`,
+	/// and so on: the same items that `.step_by(step)` would keep. Every item
+	/// is still yielded. Indices count from the front of the original
+	/// sequence, as in [`.tap_each_enumerate()`].
+	///
+	/// # Panics
+	///
+	/// This panics if `step` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut progress = vec![];
+	/// let total = (0 .. 1000)
+	///   .tap_iter_nth(250, |idx, _| progress.push(idx))
+	///   .count();
+	/// assert_eq!(total, 1000);
+	/// assert_eq!(progress, [0, 250, 500, 750]);
+	/// ```
+	///
+	/// [`.tap_each_enumerate()`]: trait.TapIter.html#method.tap_each_enumerate
+	#[inline(always)]
+	#[track_caller]
+	fn tap_iter_nth<F>(self, step: usize, func: F) -> TapNthInspect<Self, F>
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		assert!(step != 0, "sampling interval must be nonzero");
+		TapNthInspect {
+			iter: self,
+			func,
+			step,
+			count: 0,
+		}
+	}
+
+	/// Alternates between this iterator and another, passing each item and
+	/// its source to an effect function.
+	///
+	/// The adapter takes one item from `self`, then one from `other`, and so
+	/// on, and stops as soon as the iterator whose turn it is runs dry. The
+	/// effect function receives `true` for items taken from `self` and `false`
+	/// for items taken from `other`. Items are yielded unchanged.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut trace = String::new();
+	/// let merged = vec![1, 3, 5]
+	///   .into_iter()
+	///   .tap_interleave_inspect(vec![2, 4].into_iter(), |left, _| {
+	///     trace.push(if left { 'L' } else { 'R' });
+	///   })
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(merged, [1, 2, 3, 4, 5]);
+	/// assert_eq!(trace, "LRLRL");
+	/// ```
+	#[inline(always)]
+	fn tap_interleave_inspect<U, F>(
+		self,
+		other: U,
+		func: F,
+	) -> TapInterleaveInspect<Self, U, F>
+	where
+		U: Iterator<Item = Self::Item>,
+		F: FnMut(bool, &Self::Item),
+	{
+		TapInterleaveInspect {
+			left: self,
+			right: other,
+			func,
+			on_left: true,
+			done: false,
+		}
+	}
+
+	/// Shows the items to an effect function in fixed-size batches, then
+	/// yields them one at a time.
+	///
+	/// The adapter pulls up to `size` items from the iterator into a buffer,
+	/// passes the whole batch to the effect function, and then yields the
+	/// buffered items individually before pulling the next batch. The final
+	/// batch holds whatever items remain, and so may be shorter than `size`;
+	/// an empty batch is never shown. The item type is unchanged.
+	///
+	/// Because each batch is filled before any of it is yielded, the wrapped
+	/// iterator runs up to `size - 1` items ahead of the consumer.
+	///
+	/// This is named so as not to collide with [`Tap::tap_batch`], which every
+	/// iterator also implements.
+	///
+	/// # Panics
+	///
+	/// This panics if `size` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut log = vec![];
+	/// let total = (1 ..= 5)
+	///   .tap_each_batch(2, |batch| log.push(batch.len()))
+	///   .sum::<i32>();
+	/// assert_eq!(total, 15);
+	/// assert_eq!(log, [2, 2, 1]);
+	/// ```
+	///
+	/// [`Tap::tap_batch`]: ../tap/trait.Tap.html#method.tap_batch
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	#[track_caller]
+	fn tap_each_batch<F>(self, size: usize, func: F) -> TapBatchIter<Self, F>
+	where
+		F: FnMut(&[Self::Item]),
+	{
+		assert!(size != 0, "batch size must be nonzero");
+		TapBatchIter {
+			iter: self,
+			func,
+			size,
+			batch: Vec::new().into_iter(),
+		}
+	}
+
+	/// Runs an effect function if the iterator turns out to be empty.
+	///
+	/// The effect function runs at most once: on the first item requested,
+	/// from either end, if that request yields `None`. Once any item has been
+	/// produced, the effect function is dropped without running.
+	///
+	/// Emptiness is only discovered by pulling from the iterator. An adapter
+	/// that is dropped before anything is requested from it never runs the
+	/// effect function, even if the iterator it wraps was empty.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut warned = false;
+	/// let total = Vec::<u32>::new()
+	///   .into_iter()
+	///   .tap_on_empty(|| warned = true)
+	///   .sum::<u32>();
+	/// assert_eq!(total, 0);
+	/// assert!(warned);
+	/// ```
+	#[inline(always)]
+	fn tap_on_empty<F>(self, func: F) -> TapOnEmpty<Self, F>
+	where
+		F: FnOnce(),
+	{
+		TapOnEmpty {
+			iter: self,
+			func: Some(func),
+		}
+	}
+
+	/// Shows the first item produced to an effect function.
+	///
+	/// The effect function runs once, on the first item this adapter yields,
+	/// from whichever end it is taken. It never runs for an empty iterator.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut header = None;
+	/// let rows = "id,name\n1,ann\n2,bob"
+	///   .lines()
+	///   .tap_first(|h| header = Some(h.to_string()))
+	///   .count();
+	/// assert_eq!(rows, 3);
+	/// assert_eq!(header.as_deref(), Some("id,name"));
+	/// ```
+	#[inline(always)]
+	fn tap_first<F>(self, func: F) -> TapFirst<Self, F>
+	where
+		F: FnOnce(&Self::Item),
+	{
+		TapFirst {
+			iter: self,
+			func: Some(func),
+		}
+	}
+
+	/// Shows the last item produced to an effect function.
+	///
+	/// An iterator only reveals that an item was its last by returning `None`
+	/// on the following request, at which point the item has already been
+	/// handed to the consumer. Rather than keep a clone of every item in case
+	/// it is the last, this adapter reads one item ahead: it holds the next
+	/// item internally, and when the wrapped iterator reports that nothing
+	/// follows it, runs the effect function on that item just before yielding
+	/// it. This places no `Clone` bound on the items, at the cost of pulling
+	/// each item from the wrapped iterator one step earlier than the consumer
+	/// asks for it.
+	///
+	/// The effect function runs only when the final item is actually yielded.
+	/// If the adapter is dropped before then, it does not run, even if the
+	/// item was already read ahead. It never runs for an empty iterator.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut last = None;
+	/// let total = (1 ..= 100)
+	///   .tap_last(|n| last = Some(*n))
+	///   .sum::<u32>();
+	/// assert_eq!(total, 5050);
+	/// assert_eq!(last, Some(100));
+	/// ```
+	#[inline(always)]
+	fn tap_last<F>(self, func: F) -> TapLast<Self, F>
+	where
+		F: FnOnce(&Self::Item),
+	{
+		TapLast {
+			iter: self,
+			func: Some(func),
+			ahead: None,
+		}
+	}
+
+	/// Shows the item at one index to an effect function.
+	///
+	/// The effect function runs once, when the item at index `n` (counting
+	/// from zero) is yielded, and never again. Nothing is skipped or consumed:
+	/// every item is still yielded. If the iterator ends before index `n`,
+	/// the effect function does not run.
+	///
+	/// Indices count items taken from the front only. Items taken from the
+	/// back with `next_back` pass through uncounted and never trigger the
+	/// effect, even when the item at index `n` is among them.
+	///
+	/// This differs from [`.tap_iter_nth()`], which samples every `n`th item
+	/// rather than a single one.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut bad = None;
+	/// let records = vec!["ok", "ok", "garbled", "ok"];
+	/// let valid = records
+	///   .iter()
+	///   .tap_nth(2, |r| bad = Some(r.to_string()))
+	///   .filter(|r| **r == "ok")
+	///   .count();
+	/// assert_eq!(valid, 3);
+	/// assert_eq!(bad.as_deref(), Some("garbled"));
+	/// ```
+	///
+	/// [`.tap_iter_nth()`]: trait.TapIter.html#method.tap_iter_nth
+	#[inline(always)]
+	fn tap_nth<F>(self, n: usize, func: F) -> TapNth<Self, F>
+	where
+		F: FnOnce(&Self::Item),
+	{
+		TapNth {
+			iter: self,
+			func: Some(func),
+			remaining: n,
+		}
+	}
+
+	/// Immutable access to every `n`th item of an iterator, along with its
+	/// index.
+	///
+	/// This is [`.tap_iter_nth()`] with the interval checked by the type
+	/// system rather than at run time, so it cannot panic. It is intended for
+	/// progress reporting over long streams.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::num::NonZeroUsize;
+	/// use tap::iter::TapIter;
+	///
+	/// let every = NonZeroUsize::new(1_000).unwrap();
+	/// let mut reports = vec![];
+	/// let rows = (0 .. 2_500)
+	///   .tap_every_nth(every, |idx, _| reports.push(format!("processed {} rows", idx)))
+	///   .count();
+	/// assert_eq!(rows, 2_500);
+	/// assert_eq!(reports, [
+	///   "processed 0 rows",
+	///   "processed 1000 rows",
+	///   "processed 2000 rows",
+	/// ]);
+	/// ```
+	///
+	/// [`.tap_iter_nth()`]: trait.TapIter.html#method.tap_iter_nth
+	#[inline(always)]
+	fn tap_every_nth<F>(self, n: NonZeroUsize, func: F) -> TapNthInspect<Self, F>
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		TapNthInspect {
+			iter: self,
+			func,
+			step: n.get(),
+			count: 0,
+		}
+	}
+
+	/// Immutable access to the success value of each `Ok` item.
+	///
+	/// Items are yielded unchanged, including the `Err` items, which pass
+	/// through without running the effect function.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut parsed = 0;
+	/// let nums = vec!["1", "x", "3"]
+	///   .into_iter()
+	///   .map(str::parse::<i32>)
+	///   .tap_oks(|_| parsed += 1)
+	///   .filter_map(Result::ok)
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(nums, [1, 3]);
+	/// assert_eq!(parsed, 2);
+	/// ```
+	#[inline(always)]
+	fn tap_oks<T, E, F>(self, func: F) -> TapOks<Self, F>
+	where
+		Self: Iterator<Item = Result<T, E>>,
+		F: FnMut(&T),
+	{
+		TapOks { iter: self, func }
+	}
+
+	/// Immutable access to the error value of each `Err` item.
+	///
+	/// Items are yielded unchanged, so failures can be logged as they flow
+	/// past and then discarded or collected further down the pipeline.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut log = vec![];
+	/// let nums = vec!["1", "x", "3"]
+	///   .into_iter()
+	///   .map(str::parse::<i32>)
+	///   .tap_errs(|e| log.push(e.to_string()))
+	///   .filter_map(Result::ok)
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(nums, [1, 3]);
+	/// assert_eq!(log, ["invalid digit found in string"]);
+	/// ```
+	#[inline(always)]
+	fn tap_errs<T, E, F>(self, func: F) -> TapErrs<Self, F>
+	where
+		Self: Iterator<Item = Result<T, E>>,
+		F: FnMut(&E),
+	{
+		TapErrs { iter: self, func }
+	}
+
+	/// Immutable access to the payload of each item, with a separate effect
+	/// function for each variant.
+	///
+	/// This combines [`.tap_oks()`] and [`.tap_errs()`] in a single adapter.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let (mut oks, mut errs) = (0, 0);
+	/// let nums = vec!["1", "x", "3"]
+	///   .into_iter()
+	///   .map(str::parse::<i32>)
+	///   .tap_results(|_| oks += 1, |_| errs += 1)
+	///   .filter_map(Result::ok)
+	///   .count();
+	/// assert_eq!((nums, oks, errs), (2, 2, 1));
+	/// ```
+	///
+	/// [`.tap_errs()`]: trait.TapIter.html#method.tap_errs
+	/// [`.tap_oks()`]: trait.TapIter.html#method.tap_oks
+	#[inline(always)]
+	fn tap_results<T, E, F, G>(
+		self,
+		on_ok: F,
+		on_err: G,
+	) -> TapResults<Self, F, G>
+	where
+		Self: Iterator<Item = Result<T, E>>,
+		F: FnMut(&T),
+		G: FnMut(&E),
+	{
+		TapResults {
+			iter: self,
+			on_ok,
+			on_err,
+		}
+	}
+
+	/// Immutable access to the value of each `Some` item.
+	///
+	/// Items are yielded unchanged, including the `None` items, which pass
+	/// through without running the effect function.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut present = vec![];
+	/// let total = vec![Some(2), None, Some(5)]
+	///   .into_iter()
+	///   .tap_somes(|n| present.push(*n))
+	///   .flatten()
+	///   .sum::<i32>();
+	/// assert_eq!(total, 7);
+	/// assert_eq!(present, [2, 5]);
+	/// ```
+	#[inline(always)]
+	fn tap_somes<T, F>(self, func: F) -> TapSomes<Self, F>
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(&T),
+	{
+		TapSomes { iter: self, func }
+	}
+
+	/// Runs an effect function for each `None` item.
+	///
+	/// A `None` carries no payload, so the effect function takes no
+	/// arguments. It is typically used to count the gaps in sparse data.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut gaps = 0;
+	/// let readings = vec![Some(1.5), None, None, Some(2.0)]
+	///   .into_iter()
+	///   .tap_nones(|| gaps += 1)
+	///   .flatten()
+	///   .count();
+	/// assert_eq!((readings, gaps), (2, 2));
+	/// ```
+	#[inline(always)]
+	fn tap_nones<T, F>(self, func: F) -> TapNones<Self, F>
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(),
+	{
+		TapNones { iter: self, func }
+	}
+
+	/// Runs an effect function for each item, chosen by its variant.
+	///
+	/// This combines [`.tap_somes()`] and [`.tap_nones()`] in a single
+	/// adapter.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let (mut hits, mut misses) = (0, 0);
+	/// let found = vec![Some('a'), None, Some('b')]
+	///   .into_iter()
+	///   .tap_options(|_| hits += 1, || misses += 1)
+	///   .flatten()
+	///   .collect::<String>();
+	/// assert_eq!((found.as_str(), hits, misses), ("ab", 2, 1));
+	/// ```
+	///
+	/// [`.tap_nones()`]: trait.TapIter.html#method.tap_nones
+	/// [`.tap_somes()`]: trait.TapIter.html#method.tap_somes
+	#[inline(always)]
+	fn tap_options<T, F, G>(
+		self,
+		on_some: F,
+		on_none: G,
+	) -> TapOptions<Self, F, G>
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(&T),
+		G: FnMut(),
+	{
+		TapOptions {
+			iter: self,
+			on_some,
+			on_none,
+		}
+	}
+
+	/// Reports how many items the iterator yielded, once it is dropped.
+	///
+	/// The adapter counts every item it yields, from either end, and passes
+	/// the total to the effect function when it is dropped. This covers a
+	/// pipeline that runs to exhaustion as well as one cut short by `take`,
+	/// `find`, or an early return, and reports `0` for an adapter that is
+	/// dropped without being used.
+	///
+	/// The effect function also runs when the adapter is dropped while a
+	/// panic unwinds, and then reports the items yielded before the panic.
+	/// If the effect function itself panics during unwinding, the process
+	/// aborts.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut through = None;
+	/// let first_big = (1 .. 100)
+	///   .filter(|n| n % 3 == 0)
+	///   .tap_count_on_drop(|n| through = Some(n))
+	///   .find(|n| *n > 10);
+	/// assert_eq!(first_big, Some(12));
+	/// assert_eq!(through, Some(4));
+	/// ```
+	#[inline(always)]
+	fn tap_count_on_drop<F>(self, func: F) -> TapCountOnDrop<Self, F>
+	where
+		F: FnOnce(usize),
+	{
+		TapCountOnDrop {
+			iter: self,
+			func: Some(func),
+			count: 0,
+		}
+	}
+
+	/// Passes each item that repeats an earlier item to an effect function.
+	///
+	/// The adapter keeps a clone of every distinct item it has yielded in a
+	/// `HashSet`, and shows the effect function each item already in the set.
+	/// Every repetition is reported, not only the first.
+	///
+	/// The set grows with the number of distinct items, and is only released
+	/// when the adapter is dropped. Use [`TapDuplicates::with_capacity_limit`]
+	/// to bound it for long or unbounded streams, and
+	/// [`TapIter::tap_duplicates_by_key`] to track a small key rather than
+	/// cloning each item.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut repeats = vec![];
+	/// let ids = [4, 8, 4, 15, 8, 4]
+	///   .iter()
+	///   .tap_duplicates(|id| repeats.push(**id))
+	///   .count();
+	/// assert_eq!(ids, 6);
+	/// assert_eq!(repeats, [4, 8, 4]);
+	/// ```
+	///
+	/// [`TapDuplicates::with_capacity_limit`]: struct.TapDuplicates.html#method.with_capacity_limit
+	/// [`TapIter::tap_duplicates_by_key`]: trait.TapIter.html#method.tap_duplicates_by_key
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	fn tap_duplicates<F>(self, func: F) -> TapDuplicatesOf<Self, F>
+	where
+		Self::Item: Clone + Eq + Hash,
+		F: FnMut(&Self::Item),
+	{
+		self.tap_duplicates_by_key(Clone::clone as fn(&Self::Item) -> _, func)
+	}
+
+	/// Passes each item whose key repeats the key of an earlier item to an
+	/// effect function.
+	///
+	/// This behaves like [`TapIter::tap_duplicates`], but tracks the keys
+	/// computed by `key` rather than the items themselves, so that large items
+	/// are not cloned and items can be compared on only part of their value.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let users = [(1, "ada"), (2, "grace"), (1, "ada l.")];
+	/// let mut clashes = vec![];
+	/// let names = users
+	///   .iter()
+	///   .tap_duplicates_by_key(|(id, _)| *id, |(_, name)| clashes.push(*name))
+	///   .map(|(_, name)| *name)
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(names, ["ada", "grace", "ada l."]);
+	/// assert_eq!(clashes, ["ada l."]);
+	/// ```
+	///
+	/// [`TapIter::tap_duplicates`]: trait.TapIter.html#method.tap_duplicates
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	fn tap_duplicates_by_key<K, KF, F>(
+		self,
+		key: KF,
+		func: F,
+	) -> TapDuplicates<Self, K, KF, F>
+	where
+		K: Eq + Hash,
+		KF: FnMut(&Self::Item) -> K,
+		F: FnMut(&Self::Item),
+	{
+		TapDuplicates {
+			iter: self,
+			key,
+			func,
+			seen: HashSet::new(),
+			limit: None,
+		}
+	}
+
+	/// Reports running statistics over the items, once iteration finishes.
+	///
+	/// The adapter folds each item it yields into a [`Stats`] summary of its
+	/// count, sum, mean, minimum, and maximum, without storing the items. The
+	/// mean is kept with Welford’s running update, which stays accurate for
+	/// long streams where the sum alone would lose precision. NaN items are
+	/// counted in `nan_count` and otherwise skipped, so that a single NaN does
+	/// not erase the rest of the summary.
+	///
+	/// The effect function receives the summary once: when either end of the
+	/// iterator first returns `None`, or when the adapter is dropped, whichever
+	/// comes first. A pipeline cut short by `take` or `find` therefore reports
+	/// the items that were actually pulled through.
+	///
+	/// See [`TapIter::tap_int_stats`] for integer items, whose sum this
+	/// adapter would round.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut summary = None;
+	/// let total: f32 = [1.5f32, 4.0, 2.5]
+	///   .iter()
+	///   .copied()
+	///   .tap_stats(|s| summary = Some(s))
+	///   .sum();
+	/// let summary = summary.unwrap();
+	/// assert_eq!(total, 8.0);
+	/// assert_eq!(summary.count, 3);
+	/// assert_eq!(summary.min, Some(1.5));
+	/// assert_eq!(summary.max, Some(4.0));
+	/// ```
+	///
+	/// [`Stats`]: struct.Stats.html
+	/// [`TapIter::tap_int_stats`]: trait.TapIter.html#method.tap_int_stats
+	#[inline(always)]
+	fn tap_stats<F>(self, func: F) -> TapStats<Self, F>
+	where
+		Self::Item: Copy + Into<f64>,
+		F: FnOnce(Stats),
+	{
+		TapStats {
+			iter: self,
+			func: Some(func),
+			stats: Stats::default(),
+		}
+	}
+
+	/// Reports exact running statistics over integer items, once iteration
+	/// finishes.
+	///
+	/// This behaves like [`TapIter::tap_stats`], but accumulates an
+	/// [`IntStats`] summary in `i128`, so that the sum, minimum, and maximum of
+	/// any stream of 64-bit or narrower integers are exact. A stream of `i128`
+	/// items can overflow the sum, which is then reported as `None`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut summary = None;
+	/// (0 .. 3)
+	///   .map(|_| u64::MAX)
+	///   .tap_int_stats(|s| summary = Some(s))
+	///   .for_each(drop);
+	/// let summary = summary.unwrap();
+	/// assert_eq!(summary.sum, Some(3 * u64::MAX as i128));
+	/// assert_eq!(summary.max, Some(u64::MAX as i128));
+	/// ```
+	///
+	/// [`IntStats`]: struct.IntStats.html
+	/// [`TapIter::tap_stats`]: trait.TapIter.html#method.tap_stats
+	#[inline(always)]
+	fn tap_int_stats<F>(self, func: F) -> TapIntStats<Self, F>
+	where
+		Self::Item: Copy + Into<i128>,
+		F: FnOnce(IntStats),
+	{
+		TapIntStats {
+			iter: self,
+			func: Some(func),
+			stats: IntStats::default(),
+		}
+	}
+
+	//  debug-build-only copies of the above methods
+	//
+	//  In release builds these return the iterator itself, rather than an
+	//  adapter with an empty effect, so that the rest of the pipeline sees
+	//  exactly the iterator it would have seen without the tap.
+
+	/// Calls `.tap_each()` only in debug builds, and is erased in release
+	/// builds.
+	///
+	/// In release builds, this returns `self` unchanged rather than wrapping
+	/// it in an adapter.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_each_dbg<F>(self, func: F) -> TapEachIter<Self, F>
+	where
+		F: FnMut(&Self::Item),
+	{
+		self.tap_each(func)
+	}
+
+	/// Calls `.tap_each()` only in debug builds, and is erased in release
+	/// builds.
+	///
+	/// In release builds, this returns `self` unchanged rather than wrapping
+	/// it in an adapter.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_each_dbg<F>(self, _: F) -> Self
+	where
+		F: FnMut(&Self::Item),
+	{
+		self
+	}
+
+	/// Calls `.tap_each_enumerate()` only in debug builds, and is erased in
+	/// release builds.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_each_enumerate_dbg<F>(self, func: F) -> TapEachEnumerateIter<Self, F>
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		self.tap_each_enumerate(func)
+	}
+
+	/// Calls `.tap_each_enumerate()` only in debug builds, and is erased in
+	/// release builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_each_enumerate_dbg<F>(self, _: F) -> Self
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		self
+	}
+
+	/// Calls `.tap_each_mut()` only in debug builds, and is erased in release
+	/// builds.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_each_mut_dbg<F>(self, func: F) -> TapEachMutIter<Self, F>
+	where
+		F: FnMut(&mut Self::Item),
+	{
+		self.tap_each_mut(func)
+	}
+
+	/// Calls `.tap_each_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_each_mut_dbg<F>(self, _: F) -> Self
+	where
+		F: FnMut(&mut Self::Item),
+	{
+		self
+	}
+
+	/// Calls `.tap_each_batch()` only in debug builds, and is erased in
+	/// release builds.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds. The adapter is not double-ended, so `.rev()` after this call
+	/// compiles only in release builds; see [debug-only adapters].
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(all(feature = "alloc", debug_assertions))]
+	#[inline(always)]
+	#[track_caller]
+	fn tap_each_batch_dbg<F>(self, size: usize, func: F) -> TapBatchIter<Self, F>
+	where
+		F: FnMut(&[Self::Item]),
+	{
+		self.tap_each_batch(size, func)
+	}
+
+	/// Calls `.tap_each_batch()` only in debug builds, and is erased in
+	/// release builds.
+	///
+	/// Release builds neither buffer the items nor check `size`.
+	#[cfg(all(feature = "alloc", not(debug_assertions)))]
+	#[inline(always)]
+	fn tap_each_batch_dbg<F>(self, _: usize, _: F) -> Self
+	where
+		F: FnMut(&[Self::Item]),
+	{
+		self
+	}
+
+	/// Calls `.tap_every_nth()` only in debug builds, and is erased in
+	/// release builds.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_every_nth_dbg<F>(
+		self,
+		n: NonZeroUsize,
+		func: F,
+	) -> TapNthInspect<Self, F>
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		self.tap_every_nth(n, func)
+	}
+
+	/// Calls `.tap_every_nth()` only in debug builds, and is erased in
+	/// release builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_every_nth_dbg<F>(self, _: NonZeroUsize, _: F) -> Self
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		self
+	}
+
+	/// Asserts, in debug builds, that the items arrive in ascending order.
+	///
+	/// Each item is compared with the one yielded before it, and the adapter
+	/// panics, naming both items and their indices, at the first pair that is
+	/// out of order. Equal neighbors are permitted. Items that cannot be
+	/// compared, such as a `NaN`, count as out of order.
+	///
+	/// The adapter keeps a clone of the last item it yielded, and checks order
+	/// only from the front. In release builds, this returns `self` unchanged,
+	/// and nothing is checked.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds. The adapter is not double-ended, so `.rev()` after this call
+	/// compiles only in release builds; see [debug-only adapters].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let merged = vec![1, 3, 3, 7]
+	///   .into_iter()
+	///   .tap_sorted_check()
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(merged, [1, 3, 3, 7]);
+	/// ```
+	///
+	/// In a debug build, `vec![9, 5].into_iter().tap_sorted_check().count()`
+	/// panics with “iterator is not sorted: item 1 (5) is out of order after
+	/// item 0 (9)”.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_sorted_check(self) -> TapPartialSortedCheck<Self>
+	where
+		Self::Item: PartialOrd + Clone + fmt::Debug,
+	{
+		TapSortedCheck::new(self, partial_order, false)
+	}
+
+	/// Asserts, in debug builds, that the items arrive in ascending order.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_sorted_check(self) -> Self
+	where
+		Self::Item: PartialOrd + Clone + fmt::Debug,
+	{
+		self
+	}
+
+	/// Asserts, in debug builds, that the items arrive in strictly ascending
+	/// order.
+	///
+	/// This is [`.tap_sorted_check()`], except that equal neighbors are also
+	/// out of order.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds. The adapter is not double-ended, so `.rev()` after this call
+	/// compiles only in release builds; see [debug-only adapters].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let ids = vec![2, 4, 8]
+	///   .into_iter()
+	///   .tap_strictly_sorted_check()
+	///   .count();
+	/// ```
+	///
+	/// [`.tap_sorted_check()`]: trait.TapIter.html#method.tap_sorted_check
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_strictly_sorted_check(self) -> TapPartialSortedCheck<Self>
+	where
+		Self::Item: PartialOrd + Clone + fmt::Debug,
+	{
+		TapSortedCheck::new(self, partial_order, true)
+	}
+
+	/// Asserts, in debug builds, that the items arrive in strictly ascending
+	/// order.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_strictly_sorted_check(self) -> Self
+	where
+		Self::Item: PartialOrd + Clone + fmt::Debug,
+	{
+		self
+	}
+
+	/// Asserts, in debug builds, that the items arrive sorted according to a
+	/// comparator.
+	///
+	/// This is the invariant that `<[T]>::sort_by` with the same comparator
+	/// establishes: no item compares `Greater` than the item after it.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds. The adapter is not double-ended, so `.rev()` after this call
+	/// compiles only in release builds; see [debug-only adapters].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let newest_first = vec![30, 20, 10]
+	///   .into_iter()
+	///   .tap_sorted_by_check(|a, b| b.cmp(a))
+	///   .count();
+	/// ```
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_sorted_by_check<C>(self, compare: C) -> TapSortedCheck<Self, C>
+	where
+		Self::Item: Clone + fmt::Debug,
+		C: FnMut(&Self::Item, &Self::Item) -> cmp::Ordering,
+	{
+		TapSortedCheck::new(self, compare, false)
+	}
+
+	/// Asserts, in debug builds, that the items arrive sorted according to a
+	/// comparator.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_sorted_by_check<C>(self, _: C) -> Self
+	where
+		Self::Item: Clone + fmt::Debug,
+		C: FnMut(&Self::Item, &Self::Item) -> cmp::Ordering,
+	{
+		self
+	}
+
+	/// Asserts, in debug builds, that the iterator keeps the promises of its
+	/// `size_hint`.
+	///
+	/// The adapter tracks the bounds that the hint places on the *total*
+	/// number of items: the items yielded so far, plus the hint for those
+	/// that remain. It panics if an item is yielded beyond the upper bound,
+	/// if the iterator ends short of the lower bound, or if either bound
+	/// becomes looser than it was, which is what an upper bound that stays
+	/// put while items are yielded amounts to. Items yielded from either end
+	/// are counted.
+	///
+	/// This is meant for testing hand-written iterators, whose wrong hints
+	/// otherwise only show up as misplaced allocations in `collect`. In
+	/// release builds, this returns `self` unchanged, and nothing is checked.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let evens = (0 .. 10)
+	///   .filter(|n| n % 2 == 0)
+	///   .tap_size_hint_check()
+	///   .count();
+	/// assert_eq!(evens, 5);
+	/// ```
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_size_hint_check(self) -> TapSizeHintCheck<Self> {
+		let (lower, upper) = self.size_hint();
+		TapSizeHintCheck {
+			iter: self,
+			count: 0,
+			lower,
+			upper,
+		}
+	}
+
+	/// Asserts, in debug builds, that the iterator keeps the promises of its
+	/// `size_hint`.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_size_hint_check(self) -> Self {
+		self
+	}
+
+	/// Asserts, in debug builds, that the iterator stays exhausted once it
+	/// has returned `None`.
+	///
+	/// Iterators that do not implement `FusedIterator` are allowed to resume
+	/// after returning `None`, but most code that drives an iterator by hand
+	/// assumes that they do not. This adapter remembers whether the iterator
+	/// has returned `None`, from either end, and panics if it then yields an
+	/// item, naming the item’s index among all the items yielded.
+	///
+	/// The adapter implements `FusedIterator` only when the iterator it wraps
+	/// does, since it passes a resumed item through after reporting it. In
+	/// release builds, this returns `self` unchanged, and nothing is checked.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut iter = vec![1, 2].into_iter().tap_fused_check();
+	/// while let Some(_) = iter.next() {}
+	/// assert_eq!(iter.next(), None);
+	/// ```
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_fused_check(self) -> TapFusedCheck<Self, fn(usize, &Self::Item)> {
+		self.tap_fused_check_with(fused_violation)
+	}
+
+	/// Asserts, in debug builds, that the iterator stays exhausted once it
+	/// has returned `None`.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_fused_check(self) -> Self {
+		self
+	}
+
+	/// Reports, in debug builds, any item the iterator yields after it has
+	/// returned `None`.
+	///
+	/// This is [`.tap_fused_check()`], except that rather than panicking, it
+	/// passes the index and a borrow of each resumed item to an effect
+	/// function, and then yields the item.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut resumed = vec![];
+	/// let mut iter = vec![1].into_iter().tap_fused_check_with(|idx, n| {
+	///   resumed.push((idx, *n));
+	/// });
+	/// assert_eq!(iter.by_ref().count(), 1);
+	/// ```
+	///
+	/// [`.tap_fused_check()`]: trait.TapIter.html#method.tap_fused_check
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_fused_check_with<F>(self, func: F) -> TapFusedCheck<Self, F>
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		TapFusedCheck {
+			iter: self,
+			func,
+			count: 0,
+			exhausted: false,
+		}
+	}
+
+	/// Reports, in debug builds, any item the iterator yields after it has
+	/// returned `None`.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_fused_check_with<F>(self, _: F) -> Self
+	where
+		F: FnMut(usize, &Self::Item),
+	{
+		self
+	}
+
+	/// Calls `.tap_somes()` only in debug builds, and is erased in release
+	/// builds.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_somes_dbg<T, F>(self, func: F) -> TapSomes<Self, F>
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(&T),
+	{
+		self.tap_somes(func)
+	}
+
+	/// Calls `.tap_somes()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_somes_dbg<T, F>(self, _: F) -> Self
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(&T),
+	{
+		self
+	}
+
+	/// Calls `.tap_nones()` only in debug builds, and is erased in release
+	/// builds.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_nones_dbg<T, F>(self, func: F) -> TapNones<Self, F>
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(),
+	{
+		self.tap_nones(func)
+	}
+
+	/// Calls `.tap_nones()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_nones_dbg<T, F>(self, _: F) -> Self
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(),
+	{
+		self
+	}
+
+	/// Calls `.tap_options()` only in debug builds, and is erased in release
+	/// builds.
+	///
+	/// The return type is an adapter in debug builds, and `Self` in release
+	/// builds; see [debug-only adapters] for the code this can break.
+	///
+	/// [debug-only adapters]: index.html#debug-only-adapters
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_options_dbg<T, F, G>(
+		self,
+		on_some: F,
+		on_none: G,
+	) -> TapOptions<Self, F, G>
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(&T),
+		G: FnMut(),
+	{
+		self.tap_options(on_some, on_none)
+	}
+
+	/// Calls `.tap_options()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_options_dbg<T, F, G>(self, _: F, _: G) -> Self
+	where
+		Self: Iterator<Item = Option<T>>,
+		F: FnMut(&T),
+		G: FnMut(),
+	{
+		self
+	}
+}
+
+impl<I> TapIter for I where I: Iterator {}
+
+/** Point-free inspection of the elements of a collection.
+
+This trait is implemented on every type that can be iterated by shared
+reference, such as `Vec`, arrays, and the map and set collections. Its methods
+visit each element immediately, in the collection’s iteration order, and then
+return the collection.
+
+The effect function receives whatever iterating `&Self` (or `&mut Self`)
+produces: `&T` for sequences, and `(&K, &V)` (or `(&K, &mut V)`) for maps.
+
+Iterators themselves are not iterable by reference, and so use the lazy
+[`TapIter`] methods of the same names instead.
+
+[`TapIter`]: trait.TapIter.html
+**/
+pub trait TapEach
+where
+	Self: Sized,
+{
+	/// Immutable access to each element of a collection.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapEach;
+	///
+	/// let mut total = 0;
+	/// let v = vec![1, 2, 3].tap_each(|n| total += n);
+	/// assert_eq!(total, 6);
+	/// ```
+	#[inline(always)]
+	fn tap_each<F>(self, func: F) -> Self
+	where
+		for<'a> &'a Self: IntoIterator,
+		F: for<'a> FnMut(<&'a Self as IntoIterator>::Item),
+	{
+		(&self).into_iter().for_each(func);
+		self
+	}
+
+	/// Mutable access to each element of a collection.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapEach;
+	///
+	/// let names = vec![String::from("Ana"), String::from("BO")]
+	///   .tap_each_mut(|s| s.make_ascii_lowercase());
+	/// assert_eq!(names, ["ana", "bo"]);
+	/// ```
+	#[inline(always)]
+	fn tap_each_mut<F>(mut self, func: F) -> Self
+	where
+		for<'a> &'a mut Self: IntoIterator,
+		F: for<'a> FnMut(<&'a mut Self as IntoIterator>::Item),
+	{
+		(&mut self).into_iter().for_each(func);
+		self
+	}
+
+	/// Calls `.tap_each()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_each_dbg<F>(self, func: F) -> Self
+	where
+		for<'a> &'a Self: IntoIterator,
+		F: for<'a> FnMut(<&'a Self as IntoIterator>::Item),
+	{
+		self.tap_each(func)
+	}
+
+	/// Calls `.tap_each()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_each_dbg<F>(self, _: F) -> Self
+	where
+		for<'a> &'a Self: IntoIterator,
+		F: for<'a> FnMut(<&'a Self as IntoIterator>::Item),
+	{
+		self
+	}
+
+	/// Calls `.tap_each_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(debug_assertions)]
+	#[inline(always)]
+	fn tap_each_mut_dbg<F>(self, func: F) -> Self
+	where
+		for<'a> &'a mut Self: IntoIterator,
+		F: for<'a> FnMut(<&'a mut Self as IntoIterator>::Item),
+	{
+		self.tap_each_mut(func)
+	}
+
+	/// Calls `.tap_each_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(not(debug_assertions))]
+	#[inline(always)]
+	fn tap_each_mut_dbg<F>(self, _: F) -> Self
+	where
+		for<'a> &'a mut Self: IntoIterator,
+		F: for<'a> FnMut(<&'a mut Self as IntoIterator>::Item),
+	{
+		self
+	}
+}
+
+impl<C> TapEach for C where for<'a> &'a C: IntoIterator {}
+
+/// An iterator that passes a borrow of each item to an effect function.
+///
+/// This is produced by [`TapIter::tap_each`].
+///
+/// [`TapIter::tap_each`]: trait.TapIter.html#method.tap_each
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapEachIter<I, F> {
+	iter: I,
+	func: F,
+}
+
+impl<I, F> Iterator for TapEachIter<I, F>
+where
+	I: Iterator,
+	F: FnMut(&I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		(self.func)(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapEachIter<I, F>
+where
+	I: DoubleEndedIterator,
+	F: FnMut(&I::Item),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		(self.func)(&item);
+		Some(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapEachIter<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnMut(&I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapEachIter<I, F>
+where
+	I: FusedIterator,
+	F: FnMut(&I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapEachIter<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapEachIter")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that passes the index and a borrow of each item to an effect
+/// function.
+///
+/// This is produced by [`TapIter::tap_each_enumerate`]. It is only
+/// double-ended when the wrapped iterator is both double-ended and exact-size,
+/// as the index of an item taken from the back is computed from the number of
+/// items remaining.
+///
+/// [`TapIter::tap_each_enumerate`]: trait.TapIter.html#method.tap_each_enumerate
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapEachEnumerateIter<I, F> {
+	iter: I,
+	func: F,
+	count: usize,
+}
+
+impl<I, F> Iterator for TapEachEnumerateIter<I, F>
+where
+	I: Iterator,
+	F: FnMut(usize, &I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		(self.func)(self.count, &item);
+		self.count += 1;
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapEachEnumerateIter<I, F>
+where
+	I: DoubleEndedIterator + ExactSizeIterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		(self.func)(self.count + self.iter.len(), &item);
+		Some(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapEachEnumerateIter<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapEachEnumerateIter<I, F>
+where
+	I: FusedIterator,
+	F: FnMut(usize, &I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapEachEnumerateIter<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapEachEnumerateIter")
+			.field("iter", &self.iter)
+			.field("count", &self.count)
+			.finish()
+	}
+}
+
+/// An iterator that passes a mutable borrow of each item to an effect function.
+///
+/// This is produced by [`TapIter::tap_each_mut`].
+///
+/// [`TapIter::tap_each_mut`]: trait.TapIter.html#method.tap_each_mut
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapEachMutIter<I, F> {
+	iter: I,
+	func: F,
+}
+
+impl<I, F> Iterator for TapEachMutIter<I, F>
+where
+	I: Iterator,
+	F: FnMut(&mut I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut item = self.iter.next()?;
+		(self.func)(&mut item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapEachMutIter<I, F>
+where
+	I: DoubleEndedIterator,
+	F: FnMut(&mut I::Item),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let mut item = self.iter.next_back()?;
+		(self.func)(&mut item);
+		Some(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapEachMutIter<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnMut(&mut I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapEachMutIter<I, F>
+where
+	I: FusedIterator,
+	F: FnMut(&mut I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapEachMutIter<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapEachMutIter")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that skips items while a predicate holds, and reports the item
+/// that ended the skipping.
+///
+/// This is produced by [`TapIter::tap_skip_while_inspect`].
+///
+/// [`TapIter::tap_skip_while_inspect`]: trait.TapIter.html#method.tap_skip_while_inspect
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapSkipWhileInspect<I, P, F> {
+	iter: I,
+	pred: P,
+	/// Present until the skipping ends.
+	on_reject: Option<F>,
+}
+
+impl<I, P, F> Iterator for TapSkipWhileInspect<I, P, F>
+where
+	I: Iterator,
+	P: FnMut(&I::Item) -> bool,
+	F: FnOnce(&I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let on_reject = match self.on_reject.take() {
+			Some(func) => func,
+			None => return self.iter.next(),
+		};
+		let pred = &mut self.pred;
+		let item = self.iter.find(|item| !pred(item));
+		match &item {
+			Some(item) => on_reject(item),
+			//  keep the callback, so that an iterator which resumes after
+			//  returning `None` continues skipping
+			None => self.on_reject = Some(on_reject),
+		}
+		item
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (lower, upper) = self.iter.size_hint();
+		match self.on_reject {
+			Some(_) => (0, upper),
+			None => (lower, upper),
+		}
+	}
+}
+
+impl<I, P, F> FusedIterator for TapSkipWhileInspect<I, P, F>
+where
+	I: FusedIterator,
+	P: FnMut(&I::Item) -> bool,
+	F: FnOnce(&I::Item),
+{
+}
+
+impl<I, P, F> fmt::Debug for TapSkipWhileInspect<I, P, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapSkipWhileInspect")
+			.field("iter", &self.iter)
+			.field("skipping", &self.on_reject.is_some())
+			.finish()
+	}
+}
+
+/// An iterator that takes items while a predicate holds, and reports the item
+/// that ended the taking.
+///
+/// This is produced by [`TapIter::tap_take_while_inspect`].
+///
+/// [`TapIter::tap_take_while_inspect`]: trait.TapIter.html#method.tap_take_while_inspect
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapTakeWhileInspect<I, P, F> {
+	iter: I,
+	pred: P,
+	/// Present until the taking ends.
+	on_stop: Option<F>,
+}
+
+impl<I, P, F> Iterator for TapTakeWhileInspect<I, P, F>
+where
+	I: Iterator,
+	P: FnMut(&I::Item) -> bool,
+	F: FnOnce(&I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.on_stop.as_ref()?;
+		let item = self.iter.next()?;
+		if (self.pred)(&item) {
+			return Some(item);
+		}
+		if let Some(on_stop) = self.on_stop.take() {
+			on_stop(&item);
+		}
+		None
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match self.on_stop {
+			Some(_) => (0, self.iter.size_hint().1),
+			None => (0, Some(0)),
+		}
+	}
+}
+
+impl<I, P, F> FusedIterator for TapTakeWhileInspect<I, P, F>
+where
+	I: FusedIterator,
+	P: FnMut(&I::Item) -> bool,
+	F: FnOnce(&I::Item),
+{
+}
+
+impl<I, P, F> fmt::Debug for TapTakeWhileInspect<I, P, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapTakeWhileInspect")
+			.field("iter", &self.iter)
+			.field("taking", &self.on_stop.is_some())
+			.finish()
+	}
+}
+
+/// An iterator that passes every `step`th item, and its index, to an effect
+/// function.
+///
+/// This is produced by [`TapIter::tap_iter_nth`] and
+/// [`TapIter::tap_every_nth`]. Like
+/// [`TapEachEnumerateIter`], it is only double-ended when the wrapped iterator
+/// is also exact-size.
+///
+/// [`TapEachEnumerateIter`]: struct.TapEachEnumerateIter.html
+/// [`TapIter::tap_every_nth`]: trait.TapIter.html#method.tap_every_nth
+/// [`TapIter::tap_iter_nth`]: trait.TapIter.html#method.tap_iter_nth
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapNthInspect<I, F> {
+	iter: I,
+	func: F,
+	step: usize,
+	count: usize,
+}
+
+impl<I, F> TapNthInspect<I, F>
+where
+	I: Iterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	fn visit(&mut self, idx: usize, item: &I::Item) {
+		if idx.is_multiple_of(self.step) {
+			(self.func)(idx, item);
+		}
+	}
+}
+
+impl<I, F> Iterator for TapNthInspect<I, F>
+where
+	I: Iterator,
+	F: FnMut(usize, &I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(self.count, &item);
+		self.count += 1;
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapNthInspect<I, F>
+where
+	I: DoubleEndedIterator + ExactSizeIterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(self.count + self.iter.len(), &item);
+		Some(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapNthInspect<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapNthInspect<I, F>
+where
+	I: FusedIterator,
+	F: FnMut(usize, &I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapNthInspect<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapNthInspect")
+			.field("iter", &self.iter)
+			.field("step", &self.step)
+			.field("count", &self.count)
+			.finish()
+	}
+}
+
+/// An iterator that alternates between two iterators, passing each item and
+/// its source to an effect function.
+///
+/// This is produced by [`TapIter::tap_interleave_inspect`]. It is always fused:
+/// once either side runs dry on its turn, it yields nothing further.
+///
+/// [`TapIter::tap_interleave_inspect`]: trait.TapIter.html#method.tap_interleave_inspect
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapInterleaveInspect<I, U, F> {
+	left: I,
+	right: U,
+	func: F,
+	on_left: bool,
+	done: bool,
+}
+
+impl<I, U, F> Iterator for TapInterleaveInspect<I, U, F>
+where
+	I: Iterator,
+	U: Iterator<Item = I::Item>,
+	F: FnMut(bool, &I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+		let next = if self.on_left {
+			self.left.next()
+		} else {
+			self.right.next()
+		};
+		match next {
+			Some(item) => {
+				(self.func)(self.on_left, &item);
+				self.on_left = !self.on_left;
+				Some(item)
+			}
+			None => {
+				self.done = true;
+				None
+			}
+		}
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.done {
+			return (0, Some(0));
+		}
+		let (left, right) = (self.left.size_hint(), self.right.size_hint());
+		let (first, second) = if self.on_left {
+			(left, right)
+		} else {
+			(right, left)
+		};
+		let lower = interleaved(Some(first.0), Some(second.0));
+		let upper = interleaved(first.1, second.1);
+		(lower.unwrap_or(usize::MAX), upper)
+	}
+}
+
+/// Counts the items an interleaving yields when its next turn goes to a side
+/// with `first` items remaining, and the other side has `second`. `None` stands
+/// for an unbounded side; a `None` result means the count is unbounded.
+#[inline]
+fn interleaved(first: Option<usize>, second: Option<usize>) -> Option<usize> {
+	match (first, second) {
+		(None, None) => None,
+		(Some(first), Some(second)) if first <= second => first.checked_mul(2),
+		(_, Some(second)) => second.checked_mul(2)?.checked_add(1),
+		(Some(first), None) => first.checked_mul(2),
+	}
+}
+
+impl<I, U, F> FusedIterator for TapInterleaveInspect<I, U, F>
+where
+	I: Iterator,
+	U: Iterator<Item = I::Item>,
+	F: FnMut(bool, &I::Item),
+{
+}
+
+impl<I, U, F> fmt::Debug for TapInterleaveInspect<I, U, F>
+where
+	I: fmt::Debug,
+	U: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapInterleaveInspect")
+			.field("left", &self.left)
+			.field("right", &self.right)
+			.field("on_left", &self.on_left)
+			.field("done", &self.done)
+			.finish()
+	}
+}
+
+/// An iterator that shows its items to an effect function in batches.
+///
+/// This is produced by [`TapIter::tap_each_batch`]. Batches are filled from
+/// the front, so unlike the other adapters it is never double-ended.
+///
+/// [`TapIter::tap_each_batch`]: trait.TapIter.html#method.tap_each_batch
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapBatchIter<I, F>
+where
+	I: Iterator,
+{
+	iter: I,
+	func: F,
+	size: usize,
+	batch: vec::IntoIter<I::Item>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> Iterator for TapBatchIter<I, F>
+where
+	I: Iterator,
+	F: FnMut(&[I::Item]),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some(item) = self.batch.next() {
+			return Some(item);
+		}
+		let batch = self.iter.by_ref().take(self.size).collect::<Vec<_>>();
+		if batch.is_empty() {
+			return None;
+		}
+		(self.func)(&batch);
+		self.batch = batch.into_iter();
+		self.batch.next()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let buffered = self.batch.len();
+		let (lower, upper) = self.iter.size_hint();
+		(
+			lower.saturating_add(buffered),
+			upper.and_then(|upper| upper.checked_add(buffered)),
+		)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> ExactSizeIterator for TapBatchIter<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnMut(&[I::Item]),
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> FusedIterator for TapBatchIter<I, F>
+where
+	I: FusedIterator,
+	F: FnMut(&[I::Item]),
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> fmt::Debug for TapBatchIter<I, F>
+where
+	I: Iterator + fmt::Debug,
+	I::Item: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapBatchIter")
+			.field("iter", &self.iter)
+			.field("size", &self.size)
+			.field("batch", &self.batch.as_slice())
+			.finish()
+	}
+}
+
+/// An iterator that runs an effect function if its first pull yields nothing.
+///
+/// This is produced by [`TapIter::tap_on_empty`].
+///
+/// [`TapIter::tap_on_empty`]: trait.TapIter.html#method.tap_on_empty
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapOnEmpty<I, F> {
+	iter: I,
+	func: Option<F>,
+}
+
+impl<I, F> TapOnEmpty<I, F>
+where
+	F: FnOnce(),
+{
+	#[inline]
+	fn check<T>(&mut self, item: Option<T>) -> Option<T> {
+		if let Some(func) = self.func.take() {
+			if item.is_none() {
+				func();
+			}
+		}
+		item
+	}
+}
+
+impl<I, F> Iterator for TapOnEmpty<I, F>
+where
+	I: Iterator,
+	F: FnOnce(),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next();
+		self.check(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapOnEmpty<I, F>
+where
+	I: DoubleEndedIterator,
+	F: FnOnce(),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back();
+		self.check(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapOnEmpty<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnOnce(),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapOnEmpty<I, F>
+where
+	I: FusedIterator,
+	F: FnOnce(),
+{
+}
+
+impl<I, F> fmt::Debug for TapOnEmpty<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapOnEmpty")
+			.field("iter", &self.iter)
+			.field("pending", &self.func.is_some())
+			.finish()
+	}
+}
+
+/// An iterator that shows its first item to an effect function.
+///
+/// This is produced by [`TapIter::tap_first`].
+///
+/// [`TapIter::tap_first`]: trait.TapIter.html#method.tap_first
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapFirst<I, F> {
+	iter: I,
+	func: Option<F>,
+}
+
+impl<I, F> TapFirst<I, F>
+where
+	I: Iterator,
+	F: FnOnce(&I::Item),
+{
+	#[inline]
+	fn visit(&mut self, item: Option<I::Item>) -> Option<I::Item> {
+		if let Some(item) = &item {
+			if let Some(func) = self.func.take() {
+				func(item);
+			}
+		}
+		item
+	}
+}
+
+impl<I, F> Iterator for TapFirst<I, F>
+where
+	I: Iterator,
+	F: FnOnce(&I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next();
+		self.visit(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapFirst<I, F>
+where
+	I: DoubleEndedIterator,
+	F: FnOnce(&I::Item),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back();
+		self.visit(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapFirst<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnOnce(&I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapFirst<I, F>
+where
+	I: FusedIterator,
+	F: FnOnce(&I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapFirst<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapFirst")
+			.field("iter", &self.iter)
+			.field("pending", &self.func.is_some())
+			.finish()
+	}
+}
+
+/// An iterator that shows its last item to an effect function.
+///
+/// This is produced by [`TapIter::tap_last`]. It reads one item ahead of its
+/// consumer, and so is never double-ended.
+///
+/// [`TapIter::tap_last`]: trait.TapIter.html#method.tap_last
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapLast<I, F>
+where
+	I: Iterator,
+{
+	iter: I,
+	func: Option<F>,
+	/// `None` before the first request; afterwards, the item read ahead.
+	ahead: Option<Option<I::Item>>,
+}
+
+impl<I, F> Iterator for TapLast<I, F>
+where
+	I: Iterator,
+	F: FnOnce(&I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = match self.ahead.take() {
+			Some(ahead) => ahead,
+			None => self.iter.next(),
+		};
+		let item = match item {
+			Some(item) => item,
+			None => {
+				self.ahead = Some(None);
+				return None;
+			}
+		};
+		let next = self.iter.next();
+		if next.is_none() {
+			if let Some(func) = self.func.take() {
+				func(&item);
+			}
+		}
+		self.ahead = Some(next);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (lower, upper) = self.iter.size_hint();
+		match &self.ahead {
+			None => (lower, upper),
+			Some(None) => (0, Some(0)),
+			Some(Some(_)) => (
+				lower.saturating_add(1),
+				upper.and_then(|upper| upper.checked_add(1)),
+			),
+		}
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapLast<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnOnce(&I::Item),
+{
+}
+
+impl<I, F> FusedIterator for TapLast<I, F>
+where
+	I: FusedIterator,
+	F: FnOnce(&I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapLast<I, F>
+where
+	I: Iterator + fmt::Debug,
+	I::Item: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapLast")
+			.field("iter", &self.iter)
+			.field("ahead", &self.ahead)
+			.field("pending", &self.func.is_some())
+			.finish()
+	}
+}
+
+/// An iterator that shows the item at one index to an effect function.
+///
+/// This is produced by [`TapIter::tap_nth`].
+///
+/// [`TapIter::tap_nth`]: trait.TapIter.html#method.tap_nth
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapNth<I, F> {
+	iter: I,
+	func: Option<F>,
+	/// The number of front items still to pass before the target index.
+	remaining: usize,
+}
+
+impl<I, F> Iterator for TapNth<I, F>
+where
+	I: Iterator,
+	F: FnOnce(&I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		if self.func.is_some() {
+			if self.remaining == 0 {
+				if let Some(func) = self.func.take() {
+					func(&item);
+				}
+			} else {
+				self.remaining -= 1;
+			}
+		}
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapNth<I, F>
+where
+	I: DoubleEndedIterator,
+	F: FnOnce(&I::Item),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back()
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapNth<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnOnce(&I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapNth<I, F>
+where
+	I: FusedIterator,
+	F: FnOnce(&I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapNth<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapNth")
+			.field("iter", &self.iter)
+			.field("remaining", &self.remaining)
+			.field("pending", &self.func.is_some())
+			.finish()
+	}
+}
+
+/// An iterator that passes the success value of each `Ok` item to an effect
+/// function.
+///
+/// This is produced by [`TapIter::tap_oks`].
+///
+/// [`TapIter::tap_oks`]: trait.TapIter.html#method.tap_oks
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapOks<I, F> {
+	iter: I,
+	func: F,
+}
+
+impl<I, F> TapOks<I, F> {
+	#[inline]
+	fn visit<T, E>(&mut self, item: &Result<T, E>)
+	where
+		F: FnMut(&T),
+	{
+		if let Ok(val) = item {
+			(self.func)(val);
+		}
+	}
+}
+
+impl<T, E, I, F> Iterator for TapOks<I, F>
+where
+	I: Iterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<T, E, I, F> DoubleEndedIterator for TapOks<I, F>
+where
+	I: DoubleEndedIterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(&item);
+		Some(item)
+	}
+}
+
+impl<T, E, I, F> ExactSizeIterator for TapOks<I, F>
+where
+	I: ExactSizeIterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<T, E, I, F> FusedIterator for TapOks<I, F>
+where
+	I: FusedIterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+{
+}
+
+impl<I, F> fmt::Debug for TapOks<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapOks")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that passes the error value of each `Err` item to an effect
+/// function.
+///
+/// This is produced by [`TapIter::tap_errs`].
+///
+/// [`TapIter::tap_errs`]: trait.TapIter.html#method.tap_errs
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapErrs<I, F> {
+	iter: I,
+	func: F,
+}
+
+impl<I, F> TapErrs<I, F> {
+	#[inline]
+	fn visit<T, E>(&mut self, item: &Result<T, E>)
+	where
+		F: FnMut(&E),
+	{
+		if let Err(err) = item {
+			(self.func)(err);
+		}
+	}
+}
+
+impl<T, E, I, F> Iterator for TapErrs<I, F>
+where
+	I: Iterator<Item = Result<T, E>>,
+	F: FnMut(&E),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<T, E, I, F> DoubleEndedIterator for TapErrs<I, F>
+where
+	I: DoubleEndedIterator<Item = Result<T, E>>,
+	F: FnMut(&E),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(&item);
+		Some(item)
+	}
+}
+
+impl<T, E, I, F> ExactSizeIterator for TapErrs<I, F>
+where
+	I: ExactSizeIterator<Item = Result<T, E>>,
+	F: FnMut(&E),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<T, E, I, F> FusedIterator for TapErrs<I, F>
+where
+	I: FusedIterator<Item = Result<T, E>>,
+	F: FnMut(&E),
+{
+}
+
+impl<I, F> fmt::Debug for TapErrs<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapErrs")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that passes the payload of each `Result` item to the effect
+/// function for its variant.
+///
+/// This is produced by [`TapIter::tap_results`].
+///
+/// [`TapIter::tap_results`]: trait.TapIter.html#method.tap_results
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapResults<I, F, G> {
+	iter: I,
+	on_ok: F,
+	on_err: G,
+}
+
+impl<I, F, G> TapResults<I, F, G> {
+	#[inline]
+	fn visit<T, E>(&mut self, item: &Result<T, E>)
+	where
+		F: FnMut(&T),
+		G: FnMut(&E),
+	{
+		match item {
+			Ok(val) => (self.on_ok)(val),
+			Err(err) => (self.on_err)(err),
+		}
+	}
+}
+
+impl<T, E, I, F, G> Iterator for TapResults<I, F, G>
+where
+	I: Iterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+	G: FnMut(&E),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<T, E, I, F, G> DoubleEndedIterator for TapResults<I, F, G>
+where
+	I: DoubleEndedIterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+	G: FnMut(&E),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(&item);
+		Some(item)
+	}
+}
+
+impl<T, E, I, F, G> ExactSizeIterator for TapResults<I, F, G>
+where
+	I: ExactSizeIterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+	G: FnMut(&E),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<T, E, I, F, G> FusedIterator for TapResults<I, F, G>
+where
+	I: FusedIterator<Item = Result<T, E>>,
+	F: FnMut(&T),
+	G: FnMut(&E),
+{
+}
+
+impl<I, F, G> fmt::Debug for TapResults<I, F, G>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapResults")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that passes the value of each `Some` item to an effect function.
+///
+/// This is produced by [`TapIter::tap_somes`].
+///
+/// [`TapIter::tap_somes`]: trait.TapIter.html#method.tap_somes
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapSomes<I, F> {
+	iter: I,
+	func: F,
+}
+
+impl<I, F> TapSomes<I, F> {
+	#[inline]
+	fn visit<T>(&mut self, item: &Option<T>)
+	where
+		F: FnMut(&T),
+	{
+		if let Some(val) = item {
+			(self.func)(val);
+		}
+	}
+}
+
+impl<T, I, F> Iterator for TapSomes<I, F>
+where
+	I: Iterator<Item = Option<T>>,
+	F: FnMut(&T),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<T, I, F> DoubleEndedIterator for TapSomes<I, F>
+where
+	I: DoubleEndedIterator<Item = Option<T>>,
+	F: FnMut(&T),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(&item);
+		Some(item)
+	}
+}
+
+impl<T, I, F> ExactSizeIterator for TapSomes<I, F>
+where
+	I: ExactSizeIterator<Item = Option<T>>,
+	F: FnMut(&T),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<T, I, F> FusedIterator for TapSomes<I, F>
+where
+	I: FusedIterator<Item = Option<T>>,
+	F: FnMut(&T),
+{
+}
+
+impl<I, F> fmt::Debug for TapSomes<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapSomes")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that runs an effect function for each `None` item.
+///
+/// This is produced by [`TapIter::tap_nones`].
+///
+/// [`TapIter::tap_nones`]: trait.TapIter.html#method.tap_nones
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapNones<I, F> {
+	iter: I,
+	func: F,
+}
+
+impl<I, F> TapNones<I, F> {
+	#[inline]
+	fn visit<T>(&mut self, item: &Option<T>)
+	where
+		F: FnMut(),
+	{
+		if item.is_none() {
+			(self.func)();
+		}
+	}
+}
+
+impl<T, I, F> Iterator for TapNones<I, F>
+where
+	I: Iterator<Item = Option<T>>,
+	F: FnMut(),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<T, I, F> DoubleEndedIterator for TapNones<I, F>
+where
+	I: DoubleEndedIterator<Item = Option<T>>,
+	F: FnMut(),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(&item);
+		Some(item)
+	}
+}
+
+impl<T, I, F> ExactSizeIterator for TapNones<I, F>
+where
+	I: ExactSizeIterator<Item = Option<T>>,
+	F: FnMut(),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<T, I, F> FusedIterator for TapNones<I, F>
+where
+	I: FusedIterator<Item = Option<T>>,
+	F: FnMut(),
+{
+}
+
+impl<I, F> fmt::Debug for TapNones<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapNones")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that runs the effect function for the variant of each `Option`
+/// item.
+///
+/// This is produced by [`TapIter::tap_options`].
+///
+/// [`TapIter::tap_options`]: trait.TapIter.html#method.tap_options
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapOptions<I, F, G> {
+	iter: I,
+	on_some: F,
+	on_none: G,
+}
+
+impl<I, F, G> TapOptions<I, F, G> {
+	#[inline]
+	fn visit<T>(&mut self, item: &Option<T>)
+	where
+		F: FnMut(&T),
+		G: FnMut(),
+	{
+		match item {
+			Some(val) => (self.on_some)(val),
+			None => (self.on_none)(),
+		}
+	}
+}
+
+impl<T, I, F, G> Iterator for TapOptions<I, F, G>
+where
+	I: Iterator<Item = Option<T>>,
+	F: FnMut(&T),
+	G: FnMut(),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<T, I, F, G> DoubleEndedIterator for TapOptions<I, F, G>
+where
+	I: DoubleEndedIterator<Item = Option<T>>,
+	F: FnMut(&T),
+	G: FnMut(),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(&item);
+		Some(item)
+	}
+}
+
+impl<T, I, F, G> ExactSizeIterator for TapOptions<I, F, G>
+where
+	I: ExactSizeIterator<Item = Option<T>>,
+	F: FnMut(&T),
+	G: FnMut(),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<T, I, F, G> FusedIterator for TapOptions<I, F, G>
+where
+	I: FusedIterator<Item = Option<T>>,
+	F: FnMut(&T),
+	G: FnMut(),
+{
+}
+
+impl<I, F, G> fmt::Debug for TapOptions<I, F, G>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapOptions")
+			.field("iter", &self.iter)
+			.finish()
+	}
+}
+
+/// An iterator that counts the items it yields, and reports the count to an
+/// effect function when it is dropped.
+///
+/// This is produced by [`TapIter::tap_count_on_drop`]. It is not `Clone`,
+/// since a clone would report a second count.
+///
+/// [`TapIter::tap_count_on_drop`]: trait.TapIter.html#method.tap_count_on_drop
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapCountOnDrop<I, F>
+where
+	F: FnOnce(usize),
+{
+	iter: I,
+	func: Option<F>,
+	count: usize,
+}
+
+impl<I, F> Iterator for TapCountOnDrop<I, F>
+where
+	I: Iterator,
+	F: FnOnce(usize),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.count += 1;
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapCountOnDrop<I, F>
+where
+	I: DoubleEndedIterator,
+	F: FnOnce(usize),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.count += 1;
+		Some(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapCountOnDrop<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnOnce(usize),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapCountOnDrop<I, F>
+where
+	I: FusedIterator,
+	F: FnOnce(usize),
+{
+}
+
+impl<I, F> Drop for TapCountOnDrop<I, F>
+where
+	F: FnOnce(usize),
+{
+	fn drop(&mut self) {
+		if let Some(func) = self.func.take() {
+			func(self.count);
+		}
+	}
+}
+
+impl<I, F> fmt::Debug for TapCountOnDrop<I, F>
+where
+	I: fmt::Debug,
+	F: FnOnce(usize),
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapCountOnDrop")
+			.field("iter", &self.iter)
+			.field("count", &self.count)
+			.finish()
+	}
+}
+
+/// Compares two items, treating incomparable items as out of order.
+#[cfg(debug_assertions)]
+fn partial_order<T>(a: &T, b: &T) -> cmp::Ordering
+where
+	T: PartialOrd,
+{
+	a.partial_cmp(b).unwrap_or(cmp::Ordering::Greater)
+}
+
+/// The adapter produced by [`TapIter::tap_sorted_check`] and
+/// [`TapIter::tap_strictly_sorted_check`], which compares items with
+/// `PartialOrd`.
+///
+/// [`TapIter::tap_sorted_check`]: trait.TapIter.html#method.tap_sorted_check
+/// [`TapIter::tap_strictly_sorted_check`]: trait.TapIter.html#method.tap_strictly_sorted_check
+pub type TapPartialSortedCheck<I> = TapSortedCheck<
+	I,
+	fn(&<I as Iterator>::Item, &<I as Iterator>::Item) -> cmp::Ordering,
+>;
+
+/// An iterator that asserts its items arrive in sorted order.
+///
+/// This is produced, in debug builds only, by [`TapIter::tap_sorted_check`]
+/// and its siblings. It is not double-ended, since it checks order from the
+/// front.
+///
+/// [`TapIter::tap_sorted_check`]: trait.TapIter.html#method.tap_sorted_check
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapSortedCheck<I, C>
+where
+	I: Iterator,
+{
+	iter: I,
+	compare: C,
+	strict: bool,
+	/// The index and a clone of the last item yielded.
+	last: Option<(usize, I::Item)>,
+}
+
+impl<I, C> TapSortedCheck<I, C>
+where
+	I: Iterator,
+{
+	#[cfg(debug_assertions)]
+	#[inline]
+	fn new(iter: I, compare: C, strict: bool) -> Self {
+		Self {
+			iter,
+			compare,
+			strict,
+			last: None,
+		}
+	}
+}
+
+impl<I, C> Iterator for TapSortedCheck<I, C>
+where
+	I: Iterator,
+	I::Item: Clone + fmt::Debug,
+	C: FnMut(&I::Item, &I::Item) -> cmp::Ordering,
+{
+	type Item = I::Item;
+
+	#[inline]
+	#[track_caller]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		let idx = match self.last.take() {
+			Some((idx, prev)) => {
+				let order = (self.compare)(&prev, &item);
+				let in_order = order == cmp::Ordering::Less
+					|| (!self.strict && order == cmp::Ordering::Equal);
+				assert!(
+					in_order,
+					"iterator is not sorted: item {} ({:?}) is out of order \
+					 after item {} ({:?})",
+					idx + 1,
+					item,
+					idx,
+					prev,
+				);
+				idx + 1
+			}
+			None => 0,
+		};
+		self.last = Some((idx, item.clone()));
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, C> ExactSizeIterator for TapSortedCheck<I, C>
+where
+	I: ExactSizeIterator,
+	I::Item: Clone + fmt::Debug,
+	C: FnMut(&I::Item, &I::Item) -> cmp::Ordering,
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, C> FusedIterator for TapSortedCheck<I, C>
+where
+	I: FusedIterator,
+	I::Item: Clone + fmt::Debug,
+	C: FnMut(&I::Item, &I::Item) -> cmp::Ordering,
+{
+}
+
+impl<I, C> fmt::Debug for TapSortedCheck<I, C>
+where
+	I: Iterator + fmt::Debug,
+	I::Item: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapSortedCheck")
+			.field("iter", &self.iter)
+			.field("strict", &self.strict)
+			.field("last", &self.last)
+			.finish()
+	}
+}
+
+/// An iterator that asserts the iterator it wraps keeps the promises of its
+/// `size_hint`.
+///
+/// This is produced, in debug builds only, by
+/// [`TapIter::tap_size_hint_check`].
+///
+/// [`TapIter::tap_size_hint_check`]: trait.TapIter.html#method.tap_size_hint_check
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapSizeHintCheck<I> {
+	iter: I,
+	/// The number of items yielded so far, from either end.
+	count: usize,
+	/// The tightest lower bound on the total number of items seen so far.
+	lower: usize,
+	/// The tightest upper bound on the total number of items seen so far.
+	upper: Option<usize>,
+}
+
+impl<I> TapSizeHintCheck<I>
+where
+	I: Iterator,
+{
+	/// Checks the hint after a pull from the wrapped iterator, which yielded
+	/// an item if `yielded` is set.
+	#[track_caller]
+	fn check(&mut self, yielded: bool) {
+		if !yielded {
+			assert!(
+				self.count >= self.lower,
+				"size_hint violated: the iterator ended after {} items, but \
+				 promised at least {}",
+				self.count,
+				self.lower,
+			);
+			return;
+		}
+		self.count += 1;
+		if let Some(upper) = self.upper {
+			assert!(
+				self.count <= upper,
+				"size_hint violated: the iterator yielded {} items, but \
+				 promised at most {}",
+				self.count,
+				upper,
+			);
+		}
+		let (lo, hi) = self.iter.size_hint();
+		let lower = self.count.saturating_add(lo);
+		let upper = hi.and_then(|hi| self.count.checked_add(hi));
+		assert!(
+			lower >= self.lower,
+			"size_hint widened: after {} items, the lower bound on the total \
+			 fell from {} to {}",
+			self.count,
+			self.lower,
+			lower,
+		);
+		match (self.upper, upper) {
+			(Some(before), Some(after)) => assert!(
+				after <= before,
+				"size_hint widened: after {} items, the upper bound on the \
+				 total rose from {} to {}",
+				self.count,
+				before,
+				after,
+			),
+			(Some(before), None) => panic!(
+				"size_hint widened: after {} items, the upper bound on the \
+				 total rose from {} to none",
+				self.count, before,
+			),
+			(None, _) => {}
+		}
+		self.lower = lower;
+		self.upper = upper;
+	}
+}
+
+impl<I> Iterator for TapSizeHintCheck<I>
+where
+	I: Iterator,
+{
+	type Item = I::Item;
+
+	#[inline]
+	#[track_caller]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next();
+		self.check(item.is_some());
+		item
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I> DoubleEndedIterator for TapSizeHintCheck<I>
+where
+	I: DoubleEndedIterator,
+{
+	#[inline]
+	#[track_caller]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back();
+		self.check(item.is_some());
+		item
+	}
+}
+
+impl<I> ExactSizeIterator for TapSizeHintCheck<I>
+where
+	I: ExactSizeIterator,
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I> FusedIterator for TapSizeHintCheck<I> where I: FusedIterator {}
+
+impl<I> fmt::Debug for TapSizeHintCheck<I>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapSizeHintCheck")
+			.field("iter", &self.iter)
+			.field("count", &self.count)
+			.field("lower", &self.lower)
+			.field("upper", &self.upper)
+			.finish()
+	}
+}
+
+/// The effect function of [`TapIter::tap_fused_check`].
+///
+/// [`TapIter::tap_fused_check`]: trait.TapIter.html#method.tap_fused_check
+#[cfg(debug_assertions)]
+#[track_caller]
+fn fused_violation<T>(idx: usize, _: &T) {
+	panic!(
+		"iterator is not fused: item {} was yielded after it returned None",
+		idx,
+	);
+}
+
+/// An iterator that reports any item yielded after its iterator has returned
+/// `None`.
+///
+/// This is produced, in debug builds only, by [`TapIter::tap_fused_check`]
+/// and [`TapIter::tap_fused_check_with`].
+///
+/// [`TapIter::tap_fused_check`]: trait.TapIter.html#method.tap_fused_check
+/// [`TapIter::tap_fused_check_with`]: trait.TapIter.html#method.tap_fused_check_with
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapFusedCheck<I, F> {
+	iter: I,
+	func: F,
+	/// The number of items yielded so far, from either end.
+	count: usize,
+	exhausted: bool,
+}
+
+impl<I, F> TapFusedCheck<I, F>
+where
+	I: Iterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	#[track_caller]
+	fn check(&mut self, item: Option<I::Item>) -> Option<I::Item> {
+		match item {
+			Some(item) => {
+				if self.exhausted {
+					(self.func)(self.count, &item);
+				}
+				self.count += 1;
+				Some(item)
+			}
+			None => {
+				self.exhausted = true;
+				None
+			}
+		}
+	}
+}
+
+impl<I, F> Iterator for TapFusedCheck<I, F>
+where
+	I: Iterator,
+	F: FnMut(usize, &I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	#[track_caller]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next();
+		self.check(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapFusedCheck<I, F>
+where
+	I: DoubleEndedIterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	#[track_caller]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back();
+		self.check(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapFusedCheck<I, F>
+where
+	I: ExactSizeIterator,
+	F: FnMut(usize, &I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapFusedCheck<I, F>
+where
+	I: FusedIterator,
+	F: FnMut(usize, &I::Item),
+{
+}
+
+impl<I, F> fmt::Debug for TapFusedCheck<I, F>
+where
+	I: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapFusedCheck")
+			.field("iter", &self.iter)
+			.field("count", &self.count)
+			.field("exhausted", &self.exhausted)
+			.finish()
+	}
+}
+
+/// The adapter produced by [`TapIter::tap_duplicates`], which tracks clones of
+/// the items themselves.
+///
+/// [`TapIter::tap_duplicates`]: trait.TapIter.html#method.tap_duplicates
+#[cfg(feature = "std")]
+pub type TapDuplicatesOf<I, F> = TapDuplicates<
+	I,
+	<I as Iterator>::Item,
+	fn(&<I as Iterator>::Item) -> <I as Iterator>::Item,
+	F,
+>;
+
+/// An iterator that passes each repeated item to an effect function.
+///
+/// This is produced by [`TapIter::tap_duplicates`] and
+/// [`TapIter::tap_duplicates_by_key`].
+///
+/// [`TapIter::tap_duplicates`]: trait.TapIter.html#method.tap_duplicates
+/// [`TapIter::tap_duplicates_by_key`]: trait.TapIter.html#method.tap_duplicates_by_key
+#[cfg(feature = "std")]
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapDuplicates<I, K, KF, F> {
+	iter: I,
+	key: KF,
+	func: F,
+	seen: HashSet<K>,
+	limit: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl<I, K, KF, F> TapDuplicates<I, K, KF, F> {
+	/// Tracks at most `limit` distinct keys.
+	///
+	/// Once `limit` keys are tracked, the keys of later items are no longer
+	/// recorded, so their repetitions are not reported. Repetitions of keys
+	/// tracked before the limit was reached are still reported.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::iter::TapIter;
+	///
+	/// let mut repeats = vec![];
+	/// [1, 2, 3, 1, 2, 3]
+	///   .iter()
+	///   .tap_duplicates(|n| repeats.push(**n))
+	///   .with_capacity_limit(2)
+	///   .for_each(drop);
+	/// assert_eq!(repeats, [1, 2]);
+	/// ```
+	#[inline]
+	pub fn with_capacity_limit(self, limit: usize) -> Self {
+		Self {
+			limit: Some(limit),
+			..self
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<I, K, KF, F> TapDuplicates<I, K, KF, F>
+where
+	I: Iterator,
+	K: Eq + Hash,
+	KF: FnMut(&I::Item) -> K,
+	F: FnMut(&I::Item),
+{
+	#[inline]
+	fn visit(&mut self, item: &I::Item) {
+		let key = (self.key)(item);
+		if self.seen.contains(&key) {
+			(self.func)(item);
+		} else if !matches!(self.limit, Some(limit) if self.seen.len() >= limit)
+		{
+			self.seen.insert(key);
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<I, K, KF, F> Iterator for TapDuplicates<I, K, KF, F>
+where
+	I: Iterator,
+	K: Eq + Hash,
+	KF: FnMut(&I::Item) -> K,
+	F: FnMut(&I::Item),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+		self.visit(&item);
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<I, K, KF, F> DoubleEndedIterator for TapDuplicates<I, K, KF, F>
+where
+	I: DoubleEndedIterator,
+	K: Eq + Hash,
+	KF: FnMut(&I::Item) -> K,
+	F: FnMut(&I::Item),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back()?;
+		self.visit(&item);
+		Some(item)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<I, K, KF, F> ExactSizeIterator for TapDuplicates<I, K, KF, F>
+where
+	I: ExactSizeIterator,
+	K: Eq + Hash,
+	KF: FnMut(&I::Item) -> K,
+	F: FnMut(&I::Item),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<I, K, KF, F> FusedIterator for TapDuplicates<I, K, KF, F>
+where
+	I: FusedIterator,
+	K: Eq + Hash,
+	KF: FnMut(&I::Item) -> K,
+	F: FnMut(&I::Item),
+{
+}
+
+#[cfg(feature = "std")]
+impl<I, K, KF, F> fmt::Debug for TapDuplicates<I, K, KF, F>
+where
+	I: fmt::Debug,
+	K: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapDuplicates")
+			.field("iter", &self.iter)
+			.field("seen", &self.seen)
+			.field("limit", &self.limit)
+			.finish()
+	}
+}
+
+/// Running statistics over a stream of floating-point values.
+///
+/// This is reported by [`TapIter::tap_stats`]. NaN values are not folded into
+/// any of the other fields; they are only counted in `nan_count`. The `min`,
+/// `max`, and `mean` fields are `None` when no other value was seen.
+///
+/// [`TapIter::tap_stats`]: trait.TapIter.html#method.tap_stats
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+	/// The number of values seen, excluding NaNs.
+	pub count: usize,
+	/// The number of NaN values seen.
+	pub nan_count: usize,
+	/// The sum of the values seen, excluding NaNs.
+	pub sum: f64,
+	/// The arithmetic mean of the values seen, excluding NaNs.
+	pub mean: Option<f64>,
+	/// The smallest value seen.
+	pub min: Option<f64>,
+	/// The largest value seen.
+	pub max: Option<f64>,
+}
+
+impl Stats {
+	#[inline]
+	fn record(&mut self, value: f64) {
+		if value.is_nan() {
+			self.nan_count += 1;
+			return;
+		}
+		self.count += 1;
+		self.sum += value;
+		let mean = self.mean.unwrap_or(0.0);
+		self.mean = Some(mean + (value - mean) / self.count as f64);
+		self.min = Some(self.min.map_or(value, |min| min.min(value)));
+		self.max = Some(self.max.map_or(value, |max| max.max(value)));
+	}
+}
+
+/// Exact running statistics over a stream of integers.
+///
+/// This is reported by [`TapIter::tap_int_stats`]. The `min` and `max` fields
+/// are `None` when no value was seen.
+///
+/// The sum is checked rather than wrapping. It cannot overflow for items of
+/// 64 bits or narrower, but a stream of `i128` items can exceed it, and then
+/// `sum` is `None` for the rest of the stream. The count, minimum, and maximum
+/// remain exact.
+///
+/// [`TapIter::tap_int_stats`]: trait.TapIter.html#method.tap_int_stats
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IntStats {
+	/// The number of values seen.
+	pub count: usize,
+	/// The sum of the values seen, or `None` if it overflowed `i128`.
+	pub sum: Option<i128>,
+	/// The smallest value seen.
+	pub min: Option<i128>,
+	/// The largest value seen.
+	pub max: Option<i128>,
+}
+
+impl Default for IntStats {
+	#[inline]
+	fn default() -> Self {
+		Self {
+			count: 0,
+			sum: Some(0),
+			min: None,
+			max: None,
+		}
+	}
+}
+
+impl IntStats {
+	/// Computes the arithmetic mean from the exact sum, or `None` if no value
+	/// was seen or the sum overflowed.
+	#[inline]
+	pub fn mean(&self) -> Option<f64> {
+		if self.count == 0 {
+			return None;
+		}
+		Some(self.sum? as f64 / self.count as f64)
+	}
+
+	#[inline]
+	fn record(&mut self, value: i128) {
+		self.count += 1;
+		self.sum = self.sum.and_then(|sum| sum.checked_add(value));
+		self.min = Some(self.min.map_or(value, |min| min.min(value)));
+		self.max = Some(self.max.map_or(value, |max| max.max(value)));
+	}
+}
+
+/// An iterator that accumulates running statistics over its items, and
+/// reports them to an effect function when iteration finishes.
+///
+/// This is produced by [`TapIter::tap_stats`]. It is not `Clone`, since a
+/// clone would report a second summary.
+///
+/// [`TapIter::tap_stats`]: trait.TapIter.html#method.tap_stats
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapStats<I, F>
+where
+	F: FnOnce(Stats),
+{
+	iter: I,
+	func: Option<F>,
+	stats: Stats,
+}
+
+impl<I, F> TapStats<I, F>
+where
+	I: Iterator,
+	I::Item: Copy + Into<f64>,
+	F: FnOnce(Stats),
+{
+	#[inline]
+	fn visit(&mut self, item: Option<I::Item>) -> Option<I::Item> {
+		match item {
+			Some(item) => {
+				self.stats.record(item.into());
+				Some(item)
+			}
+			None => {
+				self.finish();
+				None
+			}
+		}
+	}
+}
+
+impl<I, F> TapStats<I, F>
+where
+	F: FnOnce(Stats),
+{
+	#[inline]
+	fn finish(&mut self) {
+		if let Some(func) = self.func.take() {
+			func(self.stats);
+		}
+	}
+}
+
+impl<I, F> Iterator for TapStats<I, F>
+where
+	I: Iterator,
+	I::Item: Copy + Into<f64>,
+	F: FnOnce(Stats),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next();
+		self.visit(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapStats<I, F>
+where
+	I: DoubleEndedIterator,
+	I::Item: Copy + Into<f64>,
+	F: FnOnce(Stats),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back();
+		self.visit(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapStats<I, F>
+where
+	I: ExactSizeIterator,
+	I::Item: Copy + Into<f64>,
+	F: FnOnce(Stats),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapStats<I, F>
+where
+	I: FusedIterator,
+	I::Item: Copy + Into<f64>,
+	F: FnOnce(Stats),
+{
+}
+
+impl<I, F> Drop for TapStats<I, F>
+where
+	F: FnOnce(Stats),
+{
+	fn drop(&mut self) {
+		self.finish();
+	}
+}
+
+impl<I, F> fmt::Debug for TapStats<I, F>
+where
+	I: fmt::Debug,
+	F: FnOnce(Stats),
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapStats")
+			.field("iter", &self.iter)
+			.field("stats", &self.stats)
+			.finish()
+	}
+}
+
+/// An iterator that accumulates exact integer statistics over its items, and
+/// reports them to an effect function when iteration finishes.
+///
+/// This is produced by [`TapIter::tap_int_stats`]. It is not `Clone`, since
+/// a clone would report a second summary.
+///
+/// [`TapIter::tap_int_stats`]: trait.TapIter.html#method.tap_int_stats
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TapIntStats<I, F>
+where
+	F: FnOnce(IntStats),
+{
+	iter: I,
+	func: Option<F>,
+	stats: IntStats,
+}
+
+impl<I, F> TapIntStats<I, F>
+where
+	I: Iterator,
+	I::Item: Copy + Into<i128>,
+	F: FnOnce(IntStats),
+{
+	#[inline]
+	fn visit(&mut self, item: Option<I::Item>) -> Option<I::Item> {
+		match item {
+			Some(item) => {
+				self.stats.record(item.into());
+				Some(item)
+			}
+			None => {
+				self.finish();
+				None
+			}
+		}
+	}
+}
+
+impl<I, F> TapIntStats<I, F>
+where
+	F: FnOnce(IntStats),
+{
+	#[inline]
+	fn finish(&mut self) {
+		if let Some(func) = self.func.take() {
+			func(self.stats);
+		}
+	}
+}
+
+impl<I, F> Iterator for TapIntStats<I, F>
+where
+	I: Iterator,
+	I::Item: Copy + Into<i128>,
+	F: FnOnce(IntStats),
+{
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next();
+		self.visit(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<I, F> DoubleEndedIterator for TapIntStats<I, F>
+where
+	I: DoubleEndedIterator,
+	I::Item: Copy + Into<i128>,
+	F: FnOnce(IntStats),
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next_back();
+		self.visit(item)
+	}
+}
+
+impl<I, F> ExactSizeIterator for TapIntStats<I, F>
+where
+	I: ExactSizeIterator,
+	I::Item: Copy + Into<i128>,
+	F: FnOnce(IntStats),
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.iter.len()
+	}
+}
+
+impl<I, F> FusedIterator for TapIntStats<I, F>
+where
+	I: FusedIterator,
+	I::Item: Copy + Into<i128>,
+	F: FnOnce(IntStats),
+{
+}
+
+impl<I, F> Drop for TapIntStats<I, F>
+where
+	F: FnOnce(IntStats),
+{
+	fn drop(&mut self) {
+		self.finish();
+	}
+}
+
+impl<I, F> fmt::Debug for TapIntStats<I, F>
+where
+	I: fmt::Debug,
+	F: FnOnce(IntStats),
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("TapIntStats")
+			.field("iter", &self.iter)
+			.field("stats", &self.stats)
+			.finish()
+	}
+}
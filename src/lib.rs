@@ -1,4 +1,8 @@
 #![feature(try_trait_v2)]
+#![cfg_attr(
+	feature = "nightly-const",
+	feature(const_trait_impl, const_destruct)
+)]
 /*! # `tap` – Syntactical Plumb-Lines
 
 Rust permits functions that take a `self` receiver to be written in “dot-call”
@@ -133,16 +137,88 @@ implementation, and does nothing else.
 #![cfg_attr(debug_assertions, warn(missing_docs))]
 #![cfg_attr(not(debug_assertions), deny(missing_docs))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc as liballoc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "anyhow")]
+extern crate anyhow;
+
+#[cfg(feature = "approx")]
+extern crate approx;
+
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+
+#[cfg(feature = "metrics")]
+extern crate metrics;
+
+#[cfg(feature = "opentelemetry")]
+extern crate opentelemetry;
+
+#[cfg(feature = "profiling")]
+#[doc(hidden)]
+pub extern crate profiling;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "derive")]
+extern crate tap_derive;
+
+#[cfg(feature = "counting-alloc")]
+pub mod alloc;
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+pub mod circuit_breaker;
+#[cfg(feature = "nightly-const")]
+pub mod const_tap;
 pub mod conv;
+#[cfg(all(feature = "global-hook", target_has_atomic = "ptr"))]
+pub mod hook;
+pub mod inspect;
+#[cfg(feature = "iter")]
+pub mod iter;
+pub mod mem;
+#[cfg(feature = "rayon")]
+pub mod par;
 pub mod pipe;
+#[cfg(feature = "std")]
+pub mod profile;
 pub mod tap;
+#[cfg(feature = "std")]
+pub mod testing;
 
 /// Reëxports all traits in one place, for easy import.
 pub mod prelude {
 	#[doc(inline)]
 	pub use crate::{conv::*, pipe::*, tap::*};
+
+	#[cfg(feature = "iter")]
+	#[doc(inline)]
+	pub use crate::iter::*;
+
+	#[cfg(feature = "nightly-const")]
+	#[doc(inline)]
+	pub use crate::const_tap::*;
+
+	#[cfg(feature = "rayon")]
+	#[doc(inline)]
+	pub use crate::par::*;
+
+	#[cfg(feature = "derive")]
+	#[doc(inline)]
+	pub use tap_derive::Tap;
 }
 
 // also make traits available at crate root
 #[doc(inline)]
 pub use prelude::*;
+
+#[cfg(all(feature = "global-hook", target_has_atomic = "ptr"))]
+#[doc(inline)]
+pub use hook::set_global_hook;
@@ -0,0 +1,230 @@
+/*! # Memory Inspection
+
+## Inspection Before Forgetting
+
+Unsafe code that manages memory by hand often transfers ownership of a value
+out of Rust’s control, for example by handing a raw pointer to C and then
+calling [`mem::forget`] on the owning handle so that its destructor does not
+run. This module provides a point-free form of that pattern, so that the value
+can be inspected at the last moment before it is forgotten.
+
+Forgetting a value is *safe* in Rust: it can never cause undefined behavior on
+its own, but it leaks every resource the value owns. It is only correct when
+some other party has taken over responsibility for those resources. The effect
+function runs before the value is forgotten, and must not stash a copy of any
+handle that the new owner expects to have exclusive access to.
+
+If the effect function panics, the value is dropped normally during unwinding
+rather than forgotten. Code that has already transferred ownership elsewhere
+must not allow the effect function to panic, or the resources will be freed
+twice.
+
+## Footprint Reporting
+
+[`Tap::tap_mem`] reports the memory footprint of a value as a [`MemReport`]. The
+stack size is always exact; the heap size is an estimate supplied by the
+[`HeapSize`] trait. The estimate is based on the *capacity* of each allocation,
+not its length, since capacity is what the allocator actually handed out. It
+does not include allocator bookkeeping or the control bytes of hash tables, and
+so is a lower bound.
+
+Types opt in to footprint reporting by implementing `HeapSize`. The provided
+method reports an unknown heap footprint, so an empty implementation is enough
+for a type whose heap usage is opaque.
+
+[`HeapSize`]: trait.HeapSize.html
+[`MemReport`]: struct.MemReport.html
+[`Tap::tap_mem`]: ../tap/trait.Tap.html#method.tap_mem
+[`mem::forget`]: https://doc.rust-lang.org/core/mem/fn.forget.html
+!*/
+
+use core::mem;
+
+#[cfg(feature = "alloc")]
+use liballoc::{boxed::Box, string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// Inspects a value, and then forgets it without running its destructor.
+///
+/// See the [module documentation] for the requirements on correct use.
+///
+/// # Examples
+///
+/// ```rust
+/// use tap::mem::inspect_then_forget;
+///
+/// let buf = vec![1u8, 2, 3];
+/// let ptr = buf.as_ptr();
+/// inspect_then_forget(buf, |b| assert_eq!(b.len(), 3));
+/// // the allocation is now owned by `ptr`, and must be reclaimed manually
+/// # unsafe { drop(Vec::from_raw_parts(ptr as *mut u8, 3, 3)); }
+/// ```
+///
+/// [module documentation]: index.html
+#[inline(always)]
+pub fn inspect_then_forget<T>(value: T, func: impl FnOnce(&T)) {
+	func(&value);
+	mem::forget(value);
+}
+
+/// The memory footprint of a value, as reported by [`Tap::tap_mem`].
+///
+/// [`Tap::tap_mem`]: ../tap/trait.Tap.html#method.tap_mem
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemReport {
+	/// The inline size of the value, as given by `mem::size_of_val`.
+	pub stack: usize,
+	/// The estimated number of heap bytes owned by the value, if known.
+	pub heap_estimate: Option<usize>,
+}
+
+/** Estimates the heap memory owned by a value.
+
+The estimate counts the capacity of every allocation the value owns, including
+allocations owned by its elements. Containers report an unknown footprint if any
+of their elements do.
+
+# Examples
+
+```rust
+use tap::mem::HeapSize;
+
+# #[cfg(feature = "alloc")] {
+let v: Vec<u32> = Vec::with_capacity(4);
+assert_eq!(v.heap_size(), Some(16));
+# }
+
+struct Opaque;
+impl HeapSize for Opaque {}
+assert_eq!(Opaque.heap_size(), None);
+```
+**/
+pub trait HeapSize {
+	/// Estimates the number of heap bytes owned by `self`, or `None` if the
+	/// type does not know its heap footprint.
+	#[inline]
+	fn heap_size(&self) -> Option<usize> {
+		None
+	}
+}
+
+macro_rules! no_heap {
+	($($t:ty),* $(,)?) => { $(
+		impl HeapSize for $t {
+			#[inline]
+			fn heap_size(&self) -> Option<usize> {
+				Some(0)
+			}
+		}
+	)* };
+}
+
+no_heap!(
+	(),
+	bool,
+	char,
+	u8,
+	u16,
+	u32,
+	u64,
+	u128,
+	usize,
+	i8,
+	i16,
+	i32,
+	i64,
+	i128,
+	isize,
+	f32,
+	f64,
+);
+
+/// Sums the heap footprint of each element, failing if any is unknown.
+#[cfg(feature = "alloc")]
+fn elements_heap_size<'a, T>(
+	items: impl IntoIterator<Item = &'a T>,
+) -> Option<usize>
+where
+	T: 'a + HeapSize,
+{
+	items
+		.into_iter()
+		.try_fold(0usize, |sum, item| Some(sum + item.heap_size()?))
+}
+
+impl<T> HeapSize for Option<T>
+where
+	T: HeapSize,
+{
+	#[inline]
+	fn heap_size(&self) -> Option<usize> {
+		match self {
+			Some(val) => val.heap_size(),
+			None => Some(0),
+		}
+	}
+}
+
+impl<T, E> HeapSize for Result<T, E>
+where
+	T: HeapSize,
+	E: HeapSize,
+{
+	#[inline]
+	fn heap_size(&self) -> Option<usize> {
+		match self {
+			Ok(val) => val.heap_size(),
+			Err(err) => err.heap_size(),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl HeapSize for String {
+	#[inline]
+	fn heap_size(&self) -> Option<usize> {
+		Some(self.capacity())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HeapSize for Vec<T>
+where
+	T: HeapSize,
+{
+	#[inline]
+	fn heap_size(&self) -> Option<usize> {
+		let own = self.capacity() * mem::size_of::<T>();
+		Some(own + elements_heap_size(self)?)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HeapSize for Box<[T]>
+where
+	T: HeapSize,
+{
+	#[inline]
+	fn heap_size(&self) -> Option<usize> {
+		let own = mem::size_of_val::<[T]>(self);
+		Some(own + elements_heap_size(self.iter())?)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> HeapSize for HashMap<K, V, S>
+where
+	K: HeapSize,
+	V: HeapSize,
+	S: BuildHasher,
+{
+	#[inline]
+	fn heap_size(&self) -> Option<usize> {
+		let own = self.capacity() * mem::size_of::<(K, V)>();
+		let keys = elements_heap_size(self.keys())?;
+		let values = elements_heap_size(self.values())?;
+		Some(own + keys + values)
+	}
+}
@@ -0,0 +1,90 @@
+/*! # Parallel Inspection
+
+This module integrates the tapping idiom with [`rayon`]’s parallel iterators. It
+is only available when the `rayon` feature is enabled.
+
+Rayon already provides `ParallelIterator::inspect`; the methods here wrap it
+under the names used by the rest of this crate, and are re-exported from the
+prelude alongside the other tap traits.
+
+[`rayon`]: https://docs.rs/rayon
+!*/
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::iter::{Inspect, ParallelIterator};
+
+/** Point-free inspection of parallel iterators.
+
+The effect functions given to these methods may be run concurrently on many
+threads, and so must be `Fn + Send + Sync`, rather than the `FnOnce` accepted by
+the scalar taps.
+**/
+pub trait TapParIter
+where
+	Self: ParallelIterator,
+{
+	/// Immutable access to each item of a parallel iterator.
+	///
+	/// This is a direct wrapper over `ParallelIterator::inspect`. Items are
+	/// visited in no particular order.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # extern crate rayon;
+	/// # extern crate tap;
+	/// use rayon::prelude::*;
+	/// use std::sync::atomic::{AtomicI32, Ordering};
+	/// use tap::par::TapParIter;
+	///
+	/// let seen = AtomicI32::new(0);
+	/// let sum: i32 = (1 ..= 4)
+	///   .into_par_iter()
+	///   .tap_par_each(|n| { seen.fetch_add(*n, Ordering::Relaxed); })
+	///   .sum();
+	/// assert_eq!(sum, seen.into_inner());
+	/// ```
+	#[inline(always)]
+	fn tap_par_each<F>(self, func: F) -> Inspect<Self, F>
+	where
+		F: Fn(&Self::Item) + Send + Sync,
+	{
+		self.inspect(func)
+	}
+
+	/// Counts the items of a parallel iterator as they pass by.
+	///
+	/// Each item increments `counter` with a relaxed atomic add. The count is
+	/// complete once the iterator has been driven to completion.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # extern crate rayon;
+	/// # extern crate tap;
+	/// use rayon::prelude::*;
+	/// use std::sync::atomic::AtomicUsize;
+	/// use tap::par::TapParIter;
+	///
+	/// let count = AtomicUsize::new(0);
+	/// let evens = (0 .. 100)
+	///   .into_par_iter()
+	///   .tap_par_count(&count)
+	///   .filter(|n| n % 2 == 0)
+	///   .count();
+	/// assert_eq!(evens, 50);
+	/// assert_eq!(count.into_inner(), 100);
+	/// ```
+	#[inline(always)]
+	fn tap_par_count(
+		self,
+		counter: &AtomicUsize,
+	) -> Inspect<Self, impl Fn(&Self::Item) + Send + Sync + '_> {
+		self.inspect(move |_| {
+			counter.fetch_add(1, Ordering::Relaxed);
+		})
+	}
+}
+
+impl<I> TapParIter for I where I: ParallelIterator {}
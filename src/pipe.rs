@@ -11,14 +11,53 @@ you do have a use for them.
 This module is as much of a [UFCS] method syntax that can be provided as a
 library, rather than in the language grammar.
 
+## Correspondence with `Tap`
+
+Every view that `Tap` offers has a `Pipe` analogue. Most are named by replacing
+`tap_` with `pipe_`; the exceptions are historical, and are listed here.
+
+| `Tap`                | `Pipe`                |
+|----------------------|-----------------------|
+| `tap`                | `pipe_ref`            |
+| `tap_mut`            | `pipe_ref_mut`        |
+| `tap_borrow`         | `pipe_borrow`         |
+| `tap_borrow_mut`     | `pipe_borrow_mut`     |
+| `tap_ref`            | `pipe_as_ref`         |
+| `tap_ref_mut`        | `pipe_as_mut`         |
+| `tap_deref`          | `pipe_deref`          |
+| `tap_deref_mut`      | `pipe_deref_mut`      |
+| `tap_index`          | `pipe_index`          |
+| `tap_index_mut`      | `pipe_index_mut`      |
+| `tap_display`        | `pipe_display`        |
+| `tap_debug`          | `pipe_debug`          |
+
+In addition, `.pipe()` moves the value itself into the function, and
+`.pipe_into::<T>()` forwards to `Into<T>`, like `Conv::conv`. `.pipe_with()`
+and `.pipe_ref_with()` pass a context argument alongside the value.
+
+```rust
+use tap::pipe::Pipe;
+
+let shout = Box::new(String::from("hello"))
+  .pipe_deref(String::as_str)
+  .pipe_borrow(str::to_uppercase)
+  .pipe_into::<Box<str>>();
+assert_eq!(&*shout, "HELLO");
+```
+
 [UFCS]: https://en.wikipedia.org/wiki/Uniform_Function_Call_Syntax
 !*/
 
 use core::{
 	borrow::{Borrow, BorrowMut},
-	ops::{Deref, DerefMut},
+	fmt::{Debug, Display},
+	hint,
+	ops::{Deref, DerefMut, Index, IndexMut},
 };
 
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
 /** Provides universal suffix-position call syntax for any function.
 
 This trait provides methods that allow any closure or free function to be placed
@@ -78,6 +117,183 @@ pub trait Pipe {
 		func(self)
 	}
 
+	/// Pipes by value, then inspects the result.
+	///
+	/// This is equivalent to `.pipe(transform).tap(inspect)`, for the common
+	/// case where the inspection is closely tied to the transformation. The
+	/// transformation runs first, and the inspection receives a borrow of its
+	/// output before the output is returned.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::pipe::Pipe;
+	///
+	/// let num = "42".pipe_tap(str::parse::<i32>, |r| assert!(r.is_ok()));
+	/// assert_eq!(num, Ok(42));
+	/// ```
+	#[inline(always)]
+	fn pipe_tap<R>(
+		self,
+		transform: impl FnOnce(Self) -> R,
+		inspect: impl FnOnce(&R),
+	) -> R
+	where
+		Self: Sized,
+		R: Sized,
+	{
+		let out = transform(self);
+		inspect(&out);
+		out
+	}
+
+	/// Pipes by value, together with a context argument.
+	///
+	/// This passes `self` and `ctx` into a two-argument pipe function, so that
+	/// functions shaped like `(value, context) -> output` can be placed in
+	/// suffix position without a closure that captures the context. The
+	/// context is taken by value; pass a reference to lend it instead.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::collections::HashMap;
+	/// use tap::pipe::Pipe;
+	///
+	/// fn lookup(key: &str, table: &HashMap<&str, i32>) -> Option<i32> {
+	///   table.get(key).copied()
+	/// }
+	///
+	/// let table = HashMap::from([("a", 1), ("b", 2)]);
+	/// assert_eq!("b".pipe_with(&table, lookup), Some(2));
+	/// assert_eq!("c".pipe_with(&table, lookup), None);
+	/// ```
+	#[inline(always)]
+	fn pipe_with<C, R>(self, ctx: C, func: impl FnOnce(Self, C) -> R) -> R
+	where
+		Self: Sized,
+		R: Sized,
+	{
+		func(self, ctx)
+	}
+
+	/// Pipes by value, reporting when the pipe function is slow.
+	///
+	/// This function is identical to [`Pipe::pipe`], except that the
+	/// execution of the pipe function is timed, and `on_slow` is called with
+	/// the measured duration only if it exceeds `threshold`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::{thread, time::Duration};
+	/// use tap::pipe::Pipe;
+	///
+	/// let mut took = None;
+	/// let out = 5.pipe_slow_warn(
+	///   Duration::from_millis(1),
+	///   |x| { thread::sleep(Duration::from_millis(5)); x * 2 },
+	///   |d| took = Some(d),
+	/// );
+	/// assert_eq!(out, 10);
+	/// assert!(took.unwrap() > Duration::from_millis(1));
+	/// ```
+	///
+	/// [`Pipe::pipe`]: trait.Pipe.html#method.pipe
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	fn pipe_slow_warn<R>(
+		self,
+		threshold: Duration,
+		func: impl FnOnce(Self) -> R,
+		on_slow: impl FnOnce(Duration),
+	) -> R
+	where
+		Self: Sized,
+		R: Sized,
+	{
+		let start = Instant::now();
+		let out = func(self);
+		let elapsed = start.elapsed();
+		if elapsed > threshold {
+			on_slow(elapsed);
+		}
+		out
+	}
+
+	/// Calls `.pipe_slow_warn()` with a reporter that emits a `log` warning.
+	///
+	/// The warning includes both the threshold and the measured duration.
+	#[cfg(all(feature = "std", feature = "log"))]
+	#[inline(always)]
+	fn pipe_slow_log<R>(
+		self,
+		threshold: Duration,
+		func: impl FnOnce(Self) -> R,
+	) -> R
+	where
+		Self: Sized,
+		R: Sized,
+	{
+		self.pipe_slow_warn(threshold, func, |elapsed| {
+			log::warn!(
+				"pipe took {:?}, exceeding its threshold of {:?}",
+				elapsed,
+				threshold,
+			)
+		})
+	}
+
+	/// Calls `.pipe_slow_warn()` with a reporter that emits a `tracing`
+	/// warning.
+	///
+	/// The warning event records both the threshold and the measured duration
+	/// as fields.
+	#[cfg(all(feature = "std", feature = "tracing"))]
+	#[inline(always)]
+	fn pipe_slow_trace<R>(
+		self,
+		threshold: Duration,
+		func: impl FnOnce(Self) -> R,
+	) -> R
+	where
+		Self: Sized,
+		R: Sized,
+	{
+		self.pipe_slow_warn(threshold, func, |elapsed| {
+			tracing::warn!(?threshold, ?elapsed, "pipe exceeded its threshold")
+		})
+	}
+
+	/// Pipes by value through an optimization barrier.
+	///
+	/// This function moves `self` through `core::hint::black_box`, and
+	/// returns the result. Unlike [`Tap::tap_black_box`], which only hides a
+	/// borrow, this also hides the identity of the value from the optimizer,
+	/// so later steps of the expression cannot be constant-folded with the
+	/// steps before it.
+	///
+	/// This is intended **only** for benchmarking.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::pipe::Pipe;
+	///
+	/// // without the barrier, this sum could be computed at compile time
+	/// let sum = (0 .. 100u64).pipe_black_box().sum::<u64>();
+	/// assert_eq!(sum, 4950);
+	/// ```
+	///
+	/// [`Tap::tap_black_box`]: ../tap/trait.Tap.html#method.tap_black_box
+	#[inline(always)]
+	fn pipe_black_box(self) -> Self
+	where
+		Self: Sized,
+	{
+		hint::black_box(self)
+	}
+
 	/// Borrows `self` and passes that borrow into the pipe function.
 	///
 	/// # Examples
@@ -101,6 +317,37 @@ pub trait Pipe {
 		func(self)
 	}
 
+	/// Borrows `self` and passes that borrow into the pipe function, together
+	/// with a context argument.
+	///
+	/// This is the borrowing counterpart of [`Pipe::pipe_with`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::pipe::Pipe;
+	///
+	/// fn scaled(v: &Vec<i32>, factor: i32) -> Vec<i32> {
+	///   v.iter().map(|x| x * factor).collect()
+	/// }
+	/// let vec = vec![1, 2, 3];
+	/// assert_eq!(vec.pipe_ref_with(10, scaled), [10, 20, 30]);
+	/// assert_eq!(vec.len(), 3);
+	/// ```
+	///
+	/// [`Pipe::pipe_with`]: trait.Pipe.html#method.pipe_with
+	#[inline(always)]
+	fn pipe_ref_with<'a, C, R>(
+		&'a self,
+		ctx: C,
+		func: impl FnOnce(&'a Self, C) -> R,
+	) -> R
+	where
+		R: 'a + Sized,
+	{
+		func(self, ctx)
+	}
+
 	/// Mutably borrows `self` and passes that borrow into the pipe function.
 	///
 	/// # Examples
@@ -229,6 +476,100 @@ pub trait Pipe {
 	{
 		func(DerefMut::deref_mut(self))
 	}
+
+	/// Borrows `self`, then passes `&self[index]` into the pipe function.
+	///
+	/// This panics exactly when the `Index` implementation does.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::pipe::Pipe;
+	///
+	/// let words = vec!["alpha", "beta", "gamma"];
+	/// let joined = words.pipe_index(1 .., |rest: &[&str]| rest.join("+"));
+	/// assert_eq!(joined, "beta+gamma");
+	/// ```
+	#[inline(always)]
+	fn pipe_index<'a, Idx, R>(
+		&'a self,
+		index: Idx,
+		func: impl FnOnce(&'a Self::Output) -> R,
+	) -> R
+	where
+		Self: Index<Idx>,
+		Self::Output: 'a,
+		R: 'a + Sized,
+	{
+		func(&self[index])
+	}
+
+	/// Mutably borrows `self`, then passes `&mut self[index]` into the pipe
+	/// function.
+	///
+	/// This panics exactly when the `IndexMut` implementation does.
+	#[inline(always)]
+	fn pipe_index_mut<'a, Idx, R>(
+		&'a mut self,
+		index: Idx,
+		func: impl FnOnce(&'a mut Self::Output) -> R,
+	) -> R
+	where
+		Self: IndexMut<Idx>,
+		Self::Output: 'a,
+		R: 'a + Sized,
+	{
+		func(&mut self[index])
+	}
+
+	/// Borrows `self` as a `Display` trait object, and passes it into the
+	/// pipe function.
+	#[inline(always)]
+	fn pipe_display<'a, R>(
+		&'a self,
+		func: impl FnOnce(&'a dyn Display) -> R,
+	) -> R
+	where
+		Self: Sized + Display,
+		R: 'a + Sized,
+	{
+		func(self)
+	}
+
+	/// Borrows `self` as a `Debug` trait object, and passes it into the pipe
+	/// function.
+	#[inline(always)]
+	fn pipe_debug<'a, R>(&'a self, func: impl FnOnce(&'a dyn Debug) -> R) -> R
+	where
+		Self: Sized + Debug,
+		R: 'a + Sized,
+	{
+		func(self)
+	}
+
+	/// Converts `self` into `T`, through its `Into<T>` implementation.
+	///
+	/// This is the same operation as [`Conv::conv`], made available on `Pipe`
+	/// so that a chain of pipes can end in a conversion without importing a
+	/// second trait.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::pipe::Pipe;
+	///
+	/// let wide = 5u8.pipe(|n| n * 2).pipe_into::<u64>();
+	/// assert_eq!(wide, 10);
+	/// ```
+	///
+	/// [`Conv::conv`]: ../conv/trait.Conv.html#method.conv
+	#[inline(always)]
+	fn pipe_into<T>(self) -> T
+	where
+		Self: Sized + Into<T>,
+	{
+		self.into()
+	}
 }
 
 impl<T> Pipe for T where T: ?Sized {}
@@ -20,23 +20,86 @@ use tap::tap::*;
 # fn make_vec() -> Vec<i32> { vec![] }
 
 // taps take ordinary closures, which can use deref coercion
-make_vec().tap_mut(|v| v.sort());
+let _ = make_vec().tap_mut(|v| v.sort());
 // `Vec<T>` implements `BorrowMut<[T]>`,
-make_vec().tap_borrow_mut(<[_]>::sort);
+let _ = make_vec().tap_borrow_mut(<[_]>::sort);
 // and `AsMut<[T]>`,
-make_vec().tap_ref_mut(<[_]>::sort);
+let _ = make_vec().tap_ref_mut(<[_]>::sort);
 // and `DerefMut<Target = [T]>,
-make_vec().tap_deref_mut(<[_]>::sort);
+let _ = make_vec().tap_deref_mut(<[_]>::sort);
 // but has no inherent method `sort`.
 // make_vec().tap_mut(Vec::sort);
 ```
 !*/
+use crate::mem::{HeapSize, MemReport};
 use core::ops::{ControlFlow, Try};
 use core::{
 	borrow::{Borrow, BorrowMut},
-	ops::{Deref, DerefMut},
+	cmp, hint,
+	mem::{self, MaybeUninit},
+	ops::{Deref, DerefMut, Index, IndexMut, RangeBounds},
+	pin::Pin,
+	slice::{self, SliceIndex},
 };
 
+#[cfg(any(
+	target_has_atomic = "8",
+	target_has_atomic = "ptr",
+	target_has_atomic = "64",
+))]
+use core::sync::atomic::Ordering;
+
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::AtomicBool;
+
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::AtomicUsize;
+
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+
+use core::fmt::{Debug, Display, Write};
+
+#[cfg(feature = "alloc")]
+use liballoc::{
+	boxed::Box,
+	collections::{BTreeMap, BTreeSet, VecDeque},
+	format, rc,
+	string::{String, ToString},
+	sync,
+	vec::Vec,
+};
+
+#[cfg(feature = "approx")]
+use approx::AbsDiffEq;
+
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::NoUninit;
+
+#[cfg(feature = "counting-alloc")]
+use crate::alloc::AllocStats;
+
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+use crate::circuit_breaker::CircuitBreaker;
+
+#[cfg(feature = "opentelemetry")]
+use opentelemetry::{trace::get_active_span, Key, KeyValue, Value};
+
+#[cfg(feature = "std")]
+use std::{
+	collections::{HashMap, HashSet},
+	hash::{BuildHasher, Hash},
+	sync::{mpsc::Sender, Mutex, Once, PoisonError},
+	thread::{self, ThreadId},
+	time::{Duration, Instant},
+};
+
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+use std::sync::OnceLock;
+
 /** Point-free value inspection and modification.
 
 This trait provides methods that permit viewing the value of an expression
@@ -75,7 +138,15 @@ where
 	///   .process_value();
 	/// ```
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap(self, func: impl FnOnce(&Self)) -> Self {
+		#[cfg(all(feature = "global-hook", target_has_atomic = "ptr"))]
+		crate::hook::dispatch(
+			"tap",
+			core::any::type_name::<Self>(),
+			core::panic::Location::caller(),
+		);
 		func(&self);
 		self
 	}
@@ -113,11 +184,206 @@ where
 	/// produce the collection is more complex, for example, an iterator
 	/// pipeline collected into a vector.
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap_mut(mut self, func: impl FnOnce(&mut Self)) -> Self {
+		#[cfg(all(feature = "global-hook", target_has_atomic = "ptr"))]
+		crate::hook::dispatch(
+			"tap_mut",
+			core::any::type_name::<Self>(),
+			core::panic::Location::caller(),
+		);
 		func(&mut self);
 		self
 	}
 
+	/// Access to a copy of a value.
+	///
+	/// This function is identical to [`Tap::tap`], except that the effect
+	/// function receives a bitwise copy of the value, rather than an `&Self`.
+	/// For small `Copy` types such as integers, characters, and flags, this
+	/// spares the effect function a dereference, and allows functions that
+	/// take the value itself to be passed by path.
+	///
+	/// Prefer `.tap()` for types larger than a few machine words, where the
+	/// copy costs more than the borrow. For small types the two compile to the
+	/// same code once inlined; `benches/tap_copy.rs` compares them.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut hist = [0usize; 4];
+	/// let sum: u8 = [1u8, 3, 3, 0]
+	///   .iter()
+	///   .map(|&b| b.tap_copy(|b| hist[b as usize] += 1))
+	///   .sum();
+	/// assert_eq!(hist, [1, 1, 0, 2]);
+	/// ```
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_copy(self, func: impl FnOnce(Self)) -> Self
+	where
+		Self: Copy,
+	{
+		func(self);
+		self
+	}
+
+	/// Immutable access to a value alongside a second, borrowed value.
+	///
+	/// This function is identical to [`Tap::tap`], except that the effect
+	/// function also receives `other`. It is useful for inspecting a value
+	/// against a reference point, such as a previous state or a baseline,
+	/// when the effect function is a named function that cannot capture it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// fn grew(now: &Vec<u8>, before: &Vec<u8>) {
+	///   assert!(now.len() > before.len());
+	/// }
+	///
+	/// let before = vec![1];
+	/// let after = before.clone()
+	///   .tap_mut(|v| v.push(2))
+	///   .tap_with_ref(&before, grew);
+	/// ```
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_with_ref<U>(self, other: &U, func: impl FnOnce(&Self, &U)) -> Self
+	where
+		U: ?Sized,
+	{
+		func(&self, other);
+		self
+	}
+
+	/// Mutable access to a value alongside a second, borrowed value.
+	///
+	/// This function is identical to [`Tap::tap_mut`], except that the effect
+	/// function also receives `other`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let defaults = [1, 2, 3];
+	/// let merged = [0, 5, 0].tap_with_ref_mut(&defaults, |v, d| {
+	///   for (v, d) in v.iter_mut().zip(d) {
+	///     if *v == 0 { *v = *d; }
+	///   }
+	/// });
+	/// assert_eq!(merged, [1, 5, 3]);
+	/// ```
+	///
+	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_with_ref_mut<U>(
+		mut self,
+		other: &U,
+		func: impl FnOnce(&mut Self, &U),
+	) -> Self
+	where
+		U: ?Sized,
+	{
+		func(&mut self, other);
+		self
+	}
+
+	/// Immutable access to a value, with an effect function that may bail out
+	/// early.
+	///
+	/// This function is identical to [`Tap::tap`], except that the effect
+	/// function returns `Option<()>`, so that it can use `?` to give up
+	/// partway through when something it needs is absent. The return value
+	/// is discarded: `None` and `Some(())` are treated the same, and neither
+	/// has any observable effect on the tapped value.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::collections::HashMap;
+	/// use tap::tap::Tap;
+	///
+	/// let names: HashMap<u32, &str> = vec![(1, "ada")].into_iter().collect();
+	/// let mut log = vec![];
+	/// let ids = vec![1, 2]
+	///   .into_iter()
+	///   .map(|id| id.tap_maybe(|id| {
+	///     let name = names.get(id)?;
+	///     log.push(format!("{}: {}", id, name));
+	///     Some(())
+	///   }))
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(ids, [1, 2]);
+	/// assert_eq!(log, ["1: ada"]);
+	/// ```
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_maybe(self, func: impl FnOnce(&Self) -> Option<()>) -> Self {
+		let _ = func(&self);
+		self
+	}
+
+	/// Replaces a value with a transformed value of the same type.
+	///
+	/// This is [`Pipe::pipe`] restricted to functions from `Self` to `Self`.
+	/// It exists so that type-preserving steps, such as builder methods that
+	/// take and return `self`, read as one more link in a chain of taps
+	/// rather than as the end of the chain.
+	///
+	/// Choose between the three by the shape of the step:
+	///
+	/// - `.tap_mut()` for functions that take `&mut Self`;
+	/// - `.tap_apply()` for functions that take `Self` and return `Self`;
+	/// - `.pipe()` for functions that return any other type.
+	///
+	/// Unlike the other methods of this trait, the function’s result *is*
+	/// the result of the call: `.tap_apply()` does not keep the original
+	/// value.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// #[derive(Debug, Default, PartialEq)]
+	/// struct Opts { retries: u8, verbose: bool }
+	///
+	/// impl Opts {
+	///   fn verbose(self) -> Self { Opts { verbose: true, ..self } }
+	/// }
+	///
+	/// let opts = Opts::default()
+	///   .tap_mut(|o| o.retries = 3)
+	///   .tap_apply(Opts::verbose);
+	/// assert_eq!(opts, Opts { retries: 3, verbose: true });
+	/// ```
+	///
+	/// [`Pipe::pipe`]: ../pipe/trait.Pipe.html#method.pipe
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_apply(self, func: impl FnOnce(Self) -> Self) -> Self {
+		func(self)
+	}
+
 	/// Immutable access to the `Borrow<B>` of a value.
 	///
 	/// This function is identcal to [`Tap::tap`], except that the effect
@@ -126,6 +392,8 @@ where
 	///
 	/// [`Tap::tap`]: trait.Tap.html#method.tap
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap_borrow<B>(self, func: impl FnOnce(&B)) -> Self
 	where
 		Self: Borrow<B>,
@@ -143,6 +411,8 @@ where
 	///
 	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap_borrow_mut<B>(mut self, func: impl FnOnce(&mut B)) -> Self
 	where
 		Self: BorrowMut<B>,
@@ -160,6 +430,8 @@ where
 	///
 	/// [`Tap::tap`]: trait.Tap.html#method.tap
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap_ref<R>(self, func: impl FnOnce(&R)) -> Self
 	where
 		Self: AsRef<R>,
@@ -177,6 +449,8 @@ where
 	///
 	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap_ref_mut<R>(mut self, func: impl FnOnce(&mut R)) -> Self
 	where
 		Self: AsMut<R>,
@@ -194,6 +468,8 @@ where
 	///
 	/// [`Tap::tap`]: trait.Tap.html#method.tap
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap_deref<T>(self, func: impl FnOnce(&T)) -> Self
 	where
 		Self: Deref<Target = T>,
@@ -203,6 +479,39 @@ where
 		self
 	}
 
+	/// Immutable access to the length of the `Deref::Target` of a value.
+	///
+	/// This function is identical to [`Tap::tap_deref`], except that the
+	/// effect function receives the length of the target, as reported by
+	/// [`HasLen`], rather than a borrow of it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::Arc;
+	/// use tap::tap::Tap;
+	///
+	/// # #[cfg(feature = "alloc")] {
+	/// let mut size = 0;
+	/// let shared = Arc::new(vec![1, 2, 3]).tap_deref_len(|n| size = n);
+	/// assert_eq!(size, 3);
+	/// # }
+	/// ```
+	///
+	/// [`HasLen`]: trait.HasLen.html
+	/// [`Tap::tap_deref`]: trait.Tap.html#method.tap_deref
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_deref_len(self, func: impl FnOnce(usize)) -> Self
+	where
+		Self: Deref,
+		Self::Target: HasLen,
+	{
+		func(HasLen::len(Deref::deref(&self)));
+		self
+	}
+
 	/// Mutable access to the `Deref::Target` of a value.
 	///
 	/// This function is identical to [`Tap::tap_mut`], except that the effect
@@ -211,6 +520,8 @@ where
 	///
 	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
 	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
 	fn tap_deref_mut<T>(mut self, func: impl FnOnce(&mut T)) -> Self
 	where
 		Self: DerefMut + Deref<Target = T>,
@@ -220,260 +531,5053 @@ where
 		self
 	}
 
-	//  debug-build-only copies of the above methods
-
-	/// Calls `.tap()` only in debug builds, and is erased in release builds.
+	/// Immutable access to the `Deref::Target` of a value’s `Deref::Target`.
+	///
+	/// Smart pointers around collections, such as `Arc<Vec<T>>` or
+	/// `Box<String>`, dereference first to the collection and only then to the
+	/// slice or `str` view that is usually of interest. This function goes
+	/// through both levels, so the effect function receives that view.
+	///
+	/// Deeper chains are rare enough not to warrant their own methods. For
+	/// those, tap the value itself and dereference as many times as needed:
+	/// `.tap(|v| func(&***v))`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::Arc;
+	/// use tap::tap::Tap;
+	///
+	/// fn checksum(bytes: &[u8]) -> u8 {
+	///   bytes.iter().fold(0, |acc, b| acc ^ b)
+	/// }
+	///
+	/// let mut sum = 0;
+	/// let shared = Arc::new(vec![1u8, 2, 4])
+	///   .tap_deref2(|s| sum = checksum(s));
+	/// assert_eq!(sum, 7);
+	/// ```
 	#[inline(always)]
-	fn tap_dbg(self, func: impl FnOnce(&Self)) -> Self {
-		if cfg!(debug_assertions) {
-			func(&self);
-		}
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_deref2<T, U>(self, func: impl FnOnce(&U)) -> Self
+	where
+		Self: Deref<Target = T>,
+		T: Deref<Target = U> + ?Sized,
+		U: ?Sized,
+	{
+		func(&**self);
 		self
 	}
 
-	/// Calls `.tap_mut()` only in debug builds, and is erased in release
-	/// builds.
+	/// Mutable access to the `Deref::Target` of a value’s `Deref::Target`.
+	///
+	/// This requires both levels to implement `DerefMut`, so it is available
+	/// on `Box<Vec<T>>` but not on `Arc<Vec<T>>` or `Rc<String>`, whose
+	/// contents are shared.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let name = Box::new(String::from("tap"))
+	///   .tap_deref2_mut(str::make_ascii_uppercase);
+	/// assert_eq!(*name, "TAP");
+	/// ```
 	#[inline(always)]
-	fn tap_mut_dbg(mut self, func: impl FnOnce(&mut Self)) -> Self {
-		if cfg!(debug_assertions) {
-			func(&mut self);
-		}
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_deref2_mut<T, U>(mut self, func: impl FnOnce(&mut U)) -> Self
+	where
+		Self: DerefMut + Deref<Target = T>,
+		T: DerefMut + Deref<Target = U> + ?Sized,
+		U: ?Sized,
+	{
+		func(&mut **self);
 		self
 	}
 
-	/// Calls `.tap_borrow()` only in debug builds, and is erased in release
-	/// builds.
+	/// Immutable access to the `Display` view of a value.
+	///
+	/// This is equivalent to `.tap_ref::<dyn Display>()`, but needs neither the
+	/// turbofish nor an `AsRef` implementation. The effect function receives a
+	/// trait object, so that it can be handed directly to formatting machinery
+	/// that does not know the value’s type.
+	///
+	/// There is no mutable counterpart, as `Display` has no mutating methods.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::fmt::Display;
+	/// use tap::tap::Tap;
+	///
+	/// fn log(label: &str, value: &dyn Display) -> String {
+	///   format!("{}: {}", label, value)
+	/// }
+	///
+	/// let mut line = String::new();
+	/// let port = 8080.tap_display(|d| line = log("port", d));
+	/// assert_eq!(line, "port: 8080");
+	/// ```
 	#[inline(always)]
-	fn tap_borrow_dbg<B>(self, func: impl FnOnce(&B)) -> Self
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_display(self, func: impl FnOnce(&dyn Display)) -> Self
 	where
-		Self: Borrow<B>,
-		B: ?Sized,
+		Self: Display,
 	{
-		if cfg!(debug_assertions) {
-			func(Borrow::<B>::borrow(&self));
-		}
+		func(&self);
 		self
 	}
 
-	/// Calls `.tap_borrow_mut()` only in debug builds, and is erased in release
-	/// builds.
+	/// Immutable access to the `Debug` view of a value.
+	///
+	/// This is the `Debug` counterpart of [`.tap_display()`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut line = String::new();
+	/// let pair = (1, "two").tap_debug(|d| line = format!("{:?}", d));
+	/// assert_eq!(line, r#"(1, "two")"#);
+	/// ```
+	///
+	/// [`.tap_display()`]: trait.Tap.html#method.tap_display
 	#[inline(always)]
-	fn tap_borrow_mut_dbg<B>(mut self, func: impl FnOnce(&mut B)) -> Self
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_debug(self, func: impl FnOnce(&dyn Debug)) -> Self
 	where
-		Self: BorrowMut<B>,
-		B: ?Sized,
+		Self: Debug,
 	{
-		if cfg!(debug_assertions) {
-			func(BorrowMut::<B>::borrow_mut(&mut self));
-		}
+		func(&self);
 		self
 	}
 
-	/// Calls `.tap_ref()` only in debug builds, and is erased in release
-	/// builds.
+	/// Writes text about a value into a `fmt::Write` sink.
+	///
+	/// The effect function receives the sink, as a trait object, and a borrow
+	/// of the value. This is useful for accumulating a textual log of the
+	/// stages of a pipeline into a `String`.
+	///
+	/// Any error returned by the effect function is ignored, as the tap has
+	/// no way to report it. Writing into a `String` never fails; for sinks
+	/// that can, write through [`Tap::tap`] instead and handle the error
+	/// there.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut report = String::new();
+	/// let words = vec!["a", "bb"]
+	///   .tap_write(&mut report, |w, v| write!(w, "{} words; ", v.len()))
+	///   .into_iter()
+	///   .map(str::len)
+	///   .sum::<usize>()
+	///   .tap_write(&mut report, |w, n| write!(w, "{} letters", n));
+	/// assert_eq!(report, "2 words; 3 letters");
+	/// ```
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
 	#[inline(always)]
-	fn tap_ref_dbg<R>(self, func: impl FnOnce(&R)) -> Self
-	where
-		Self: AsRef<R>,
-		R: ?Sized,
-	{
-		if cfg!(debug_assertions) {
-			func(AsRef::<R>::as_ref(&self));
-		}
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_write(
+		self,
+		sink: &mut impl Write,
+		func: impl FnOnce(&mut dyn Write, &Self) -> core::fmt::Result,
+	) -> Self {
+		let _ = func(sink, &self);
 		self
 	}
 
-	/// Calls `.tap_ref_mut()` only in debug builds, and is erased in release
-	/// builds.
+	/// Writes the `Display` rendering of a value, and a newline, into a
+	/// `fmt::Write` sink.
+	///
+	/// As with [`Tap::tap_write`], any error from the sink is ignored.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::{pipe::Pipe, tap::Tap};
+	///
+	/// let mut log = String::new();
+	/// let n = 6
+	///   .tap_writeln(&mut log)
+	///   .pipe(|n| n * 7)
+	///   .tap_writeln(&mut log);
+	/// assert_eq!(log, "6\n42\n");
+	/// ```
+	///
+	/// [`Tap::tap_write`]: trait.Tap.html#method.tap_write
 	#[inline(always)]
-	fn tap_ref_mut_dbg<R>(mut self, func: impl FnOnce(&mut R)) -> Self
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_writeln(self, sink: &mut impl Write) -> Self
 	where
-		Self: AsMut<R>,
-		R: ?Sized,
+		Self: Display,
 	{
-		if cfg!(debug_assertions) {
-			func(AsMut::<R>::as_mut(&mut self));
-		}
+		let _ = writeln!(sink, "{}", self);
 		self
 	}
 
-	/// Calls `.tap_deref()` only in debug builds, and is erased in release
-	/// builds.
+	/// Immutable access to an indexed element of a value.
+	///
+	/// This passes `&self[index]` to the effect function. It panics exactly
+	/// when the `Index` implementation does: for example, when a `Vec` index
+	/// is out of bounds, or when a `HashMap` key is absent.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let evens = vec![0, 2, 4, 6]
+	///   .tap_index(1 .. 3, |mid: &[i32]| assert_eq!(mid, [2, 4]));
+	/// ```
 	#[inline(always)]
-	fn tap_deref_dbg<T>(self, func: impl FnOnce(&T)) -> Self
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_index<Idx>(self, index: Idx, func: impl FnOnce(&Self::Output)) -> Self
 	where
-		Self: Deref<Target = T>,
-		T: ?Sized,
+		Self: Index<Idx>,
 	{
-		if cfg!(debug_assertions) {
-			func(Deref::deref(&self));
-		}
+		func(&self[index]);
 		self
 	}
 
-	/// Calls `.tap_deref_mut()` only in debug builds, and is erased in release
-	/// builds.
+	/// Mutable access to an indexed element of a value.
+	///
+	/// This passes `&mut self[index]` to the effect function, and panics
+	/// exactly when the `IndexMut` implementation does.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let rows = vec!["name".to_string(), "tap".to_string()]
+	///   .tap_index_mut(0, |hdr| hdr.make_ascii_uppercase());
+	/// assert_eq!(rows, ["NAME", "tap"]);
+	/// ```
 	#[inline(always)]
-	fn tap_deref_mut_dbg<T>(mut self, func: impl FnOnce(&mut T)) -> Self
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_index_mut<Idx>(
+		mut self,
+		index: Idx,
+		func: impl FnOnce(&mut Self::Output),
+	) -> Self
 	where
-		Self: DerefMut + Deref<Target = T>,
-		T: ?Sized,
+		Self: IndexMut<Idx>,
+	{
+		func(&mut self[index]);
+		self
+	}
+
+	/// Immutable access to a window of a value’s slice view.
+	///
+	/// This views the value as a slice through `AsRef<[T]>`, indexes it with
+	/// `range`, and passes the result to the effect function. `range` may be
+	/// any slice index: a range of any kind yields a sub-slice, and a single
+	/// `usize` yields one element. Out-of-range or inverted ranges panic,
+	/// exactly as standard slice indexing does; see [`.tap_range_checked()`]
+	/// for a form that does not.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// fn check_magic(hdr: &[u8]) {
+	///   assert_eq!(hdr, b"\x7fELF");
+	/// }
+	///
+	/// let packet = b"\x7fELF\x02\x01".to_vec().tap_range(0 .. 4, check_magic);
+	/// ```
+	///
+	/// [`.tap_range_checked()`]: trait.Tap.html#method.tap_range_checked
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_range<R, T>(self, range: R, func: impl FnOnce(&R::Output)) -> Self
+	where
+		Self: AsRef<[T]>,
+		R: SliceIndex<[T]>,
+	{
+		func(&self.as_ref()[range]);
+		self
+	}
+
+	/// Mutable access to a window of a value’s slice view.
+	///
+	/// This is the mutable counterpart of [`.tap_range()`], viewing the value
+	/// through `AsMut<[T]>`, and panics under the same conditions.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let buf = vec![9u8; 6].tap_range_mut(2 .. 4, |s| s.fill(0));
+	/// assert_eq!(buf, [9, 9, 0, 0, 9, 9]);
+	/// ```
+	///
+	/// [`.tap_range()`]: trait.Tap.html#method.tap_range
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_range_mut<R, T>(
+		mut self,
+		range: R,
+		func: impl FnOnce(&mut R::Output),
+	) -> Self
+	where
+		Self: AsMut<[T]>,
+		R: SliceIndex<[T]>,
+	{
+		func(&mut self.as_mut()[range]);
+		self
+	}
+
+	/// Immutable access to a window of a value’s slice view, if it is in
+	/// bounds.
+	///
+	/// This is the non-panicking form of [`.tap_range()`]: when `range` is out
+	/// of bounds or inverted, the effect function is skipped.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let short = [1, 2]
+	///   .tap_range_checked(0 .. 4, |_| unreachable!())
+	///   .tap_range_checked(1 .., |tail| assert_eq!(tail, [2]));
+	/// ```
+	///
+	/// [`.tap_range()`]: trait.Tap.html#method.tap_range
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_range_checked<R, T>(
+		self,
+		range: R,
+		func: impl FnOnce(&R::Output),
+	) -> Self
+	where
+		Self: AsRef<[T]>,
+		R: SliceIndex<[T]>,
+	{
+		if let Some(window) = self.as_ref().get(range) {
+			func(window);
+		}
+		self
+	}
+
+	/// Mutable access to a window of a value’s slice view, if it is in bounds.
+	///
+	/// This is the non-panicking form of [`.tap_range_mut()`].
+	///
+	/// [`.tap_range_mut()`]: trait.Tap.html#method.tap_range_mut
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_range_mut_checked<R, T>(
+		mut self,
+		range: R,
+		func: impl FnOnce(&mut R::Output),
+	) -> Self
+	where
+		Self: AsMut<[T]>,
+		R: SliceIndex<[T]>,
+	{
+		if let Some(window) = self.as_mut().get_mut(range) {
+			func(window);
+		}
+		self
+	}
+
+	/// Immutable access to a part of a value found by a fallible getter.
+	///
+	/// This passes a borrow of the value to `get`, and, if it finds something,
+	/// passes that to the effect function. It generalizes [`TapGet::tap_get`]
+	/// to any `Option`-returning accessor.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut first = None;
+	/// let words = vec!["alpha", "beta"]
+	///   .tap_lookup(|v| v.first(), |w| first = Some(*w))
+	///   .tap_lookup(|v| v.get(9), |_| unreachable!());
+	/// assert_eq!(first, Some("alpha"));
+	/// ```
+	///
+	/// [`TapGet::tap_get`]: trait.TapGet.html#method.tap_get
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_lookup<V>(
+		self,
+		get: impl FnOnce(&Self) -> Option<&V>,
+		func: impl FnOnce(&V),
+	) -> Self
+	where
+		V: ?Sized,
+	{
+		if let Some(val) = get(&self) {
+			func(val);
+		}
+		self
+	}
+
+	/// Mutable access to a part of a value found by a fallible getter.
+	///
+	/// This is the mutable counterpart of [`.tap_lookup()`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let words = vec![String::from("alpha")]
+	///   .tap_lookup_mut(|v| v.last_mut(), |w| w.push('!'));
+	/// assert_eq!(words, ["alpha!"]);
+	/// ```
+	///
+	/// [`.tap_lookup()`]: trait.Tap.html#method.tap_lookup
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_lookup_mut<V>(
+		mut self,
+		get: impl FnOnce(&mut Self) -> Option<&mut V>,
+		func: impl FnOnce(&mut V),
+	) -> Self
+	where
+		V: ?Sized,
+	{
+		if let Some(val) = get(&mut self) {
+			func(val);
+		}
+		self
+	}
+
+	/// Immutable access to a part of a value, chosen by a projection.
+	///
+	/// The projection receives a borrow of the value and returns a borrow of
+	/// any part of it, typically a field, which is then passed to the effect
+	/// function. This covers the common case that [`.tap_borrow()`] and its
+	/// siblings cannot: a view that is not expressible as a standard conversion
+	/// trait.
+	///
+	/// With the `derive` feature enabled, `#[derive(Tap)]` generates named
+	/// `tap_<field>` shorthands for this on each field of a struct.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// struct Db { url: String }
+	/// struct Config { database: Db }
+	///
+	/// let mut logged = String::new();
+	/// let config = Config { database: Db { url: "pg://".into() } }
+	///   .tap_at(|c| &c.database.url, |url| logged = url.clone());
+	/// assert_eq!(logged, "pg://");
+	/// ```
+	///
+	/// [`.tap_borrow()`]: trait.Tap.html#method.tap_borrow
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_at<P>(
+		self,
+		proj: impl FnOnce(&Self) -> &P,
+		func: impl FnOnce(&P),
+	) -> Self
+	where
+		P: ?Sized,
+	{
+		func(proj(&self));
+		self
+	}
+
+	/// Mutable access to a part of a value, chosen by a projection.
+	///
+	/// This is the mutable counterpart of [`.tap_at()`]. The projection borrows
+	/// the value mutably and returns a mutable borrow of some part of it; that
+	/// borrow ends when the effect function returns, before the value is
+	/// returned from the tap.
+	///
+	/// Unlike [`.tap_borrow_mut()`], which can only reach the views that a type
+	/// has chosen to expose through `BorrowMut`, the projection can reach any
+	/// field, including through `match` on an enum.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// struct Request { headers: Vec<(String, String)> }
+	///
+	/// let req = Request { headers: vec![] }
+	///   .tap_at_mut(|r| &mut r.headers, |h| h.push(("Host".into(), "x".into())));
+	/// assert_eq!(req.headers.len(), 1);
+	/// ```
+	///
+	/// [`.tap_at()`]: trait.Tap.html#method.tap_at
+	/// [`.tap_borrow_mut()`]: trait.Tap.html#method.tap_borrow_mut
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_at_mut<P>(
+		mut self,
+		proj: impl FnOnce(&mut Self) -> &mut P,
+		func: impl FnOnce(&mut P),
+	) -> Self
+	where
+		P: ?Sized,
+	{
+		func(proj(&mut self));
+		self
+	}
+
+	/// Computes a derived value from a borrow, and returns it alongside the
+	/// original.
+	///
+	/// The function runs before the tap returns, and receives only a shared
+	/// borrow, so the original value is returned untouched. This is useful
+	/// when a pipeline needs both a value and a cheap summary of it, without
+	/// binding the value to a name first.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let (words, count) = vec!["tap", "pipe", "conv"].tee(Vec::len);
+	/// assert_eq!(count, 3);
+	/// assert_eq!(words, ["tap", "pipe", "conv"]);
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tee<R>(self, func: impl FnOnce(&Self) -> R) -> (Self, R) {
+		let out = func(&self);
+		(self, out)
+	}
+
+	/// Mutable access to a value, by a sequence of effect functions.
+	///
+	/// This function is identical to calling [`Tap::tap_mut`] once for each
+	/// function produced by `funcs`. The functions are applied in iteration
+	/// order, and each one observes the modifications made by all of its
+	/// predecessors. An empty sequence returns the value unchanged.
+	///
+	/// This is useful when the set of modifications is assembled at runtime,
+	/// rather than written out as a fixed chain of `.tap_mut()` calls.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let steps: Vec<Box<dyn FnOnce(&mut Vec<i32>)>> = vec![
+	///   Box::new(|v| v.push(1)),
+	///   Box::new(|v| v.push(2)),
+	///   Box::new(|v| v.reverse()),
+	/// ];
+	/// let out = vec![0].tap_batch(steps);
+	/// assert_eq!(out, [2, 1, 0]);
+	/// ```
+	///
+	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_batch<F>(mut self, funcs: impl IntoIterator<Item = F>) -> Self
+	where
+		F: FnOnce(&mut Self),
+	{
+		for func in funcs {
+			func(&mut self);
+		}
+		self
+	}
+
+	/// Counts a value as it passes by.
+	///
+	/// This function increments `counter` by one, with relaxed ordering. It is
+	/// a shorthand for the most common tap effect, which requires no closure.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::atomic::AtomicUsize;
+	/// use tap::tap::Tap;
+	///
+	/// let seen = AtomicUsize::new(0);
+	/// let sum = (0 .. 10).map(|n| n.tap_count(&seen)).sum::<i32>();
+	/// assert_eq!(seen.into_inner(), 10);
+	/// ```
+	#[cfg(target_has_atomic = "ptr")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_count(self, counter: &AtomicUsize) -> Self {
+		counter.fetch_add(1, Ordering::Relaxed);
+		self
+	}
+
+	/// Counts a value as it passes by, if it satisfies a predicate.
+	///
+	/// This function is identical to [`Tap::tap_count`], except that `counter`
+	/// is only incremented when `pred` returns `true`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::atomic::AtomicUsize;
+	/// use tap::tap::Tap;
+	///
+	/// let odds = AtomicUsize::new(0);
+	/// for n in 0 .. 10 {
+	///   let _ = n.tap_count_if(&odds, |n| n % 2 == 1);
+	/// }
+	/// assert_eq!(odds.into_inner(), 5);
+	/// ```
+	///
+	/// [`Tap::tap_count`]: trait.Tap.html#method.tap_count
+	#[cfg(target_has_atomic = "ptr")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_count_if(
+		self,
+		counter: &AtomicUsize,
+		pred: impl FnOnce(&Self) -> bool,
+	) -> Self {
+		if pred(&self) {
+			counter.fetch_add(1, Ordering::Relaxed);
+		}
+		self
+	}
+
+	/// Immutable access to every `n`th value that passes a counter.
+	///
+	/// Each call increments `counter`, and runs the effect function when the
+	/// incremented count is a multiple of `n`: on the `n`th, `2n`th, and later
+	/// calls. This allows a hot loop to report progress without paying for an
+	/// effect on every iteration. Sharing one counter between threads samples
+	/// their combined traffic.
+	///
+	/// # Panics
+	///
+	/// This panics if `n` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::atomic::AtomicUsize;
+	/// use tap::tap::Tap;
+	///
+	/// let counter = AtomicUsize::new(0);
+	/// let mut reports = vec![];
+	/// for n in 0 .. 10 {
+	///   let _ = n.tap_each_nth(4, &counter, |n| reports.push(*n));
+	/// }
+	/// assert_eq!(reports, [3, 7]);
+	/// ```
+	#[cfg(target_has_atomic = "ptr")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_each_nth(
+		self,
+		n: usize,
+		counter: &AtomicUsize,
+		func: impl FnOnce(&Self),
+	) -> Self {
+		assert!(n != 0, "sampling interval must be nonzero");
+		let count = counter.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+		if count.is_multiple_of(n) {
+			func(&self);
+		}
+		self
+	}
+
+	/// Immutable access to `n` out of every `m` values that pass a counter.
+	///
+	/// Each call increments `counter`, and runs the effect function for the
+	/// first `n` calls of every run of `m`. This samples a fixed fraction,
+	/// `n / m`, of the traffic without a random number generator, so the
+	/// same sequence of calls always samples the same values. When `n` is at
+	/// least `m`, every call runs the effect.
+	///
+	/// # Panics
+	///
+	/// This panics if `m` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::atomic::AtomicU64;
+	/// use tap::tap::Tap;
+	///
+	/// let counter = AtomicU64::new(0);
+	/// let mut sampled = vec![];
+	/// for n in 0 .. 10 {
+	///   let _ = n.tap_sample_n_in_m(2, 5, &counter, |n| sampled.push(*n));
+	/// }
+	/// assert_eq!(sampled, [0, 1, 5, 6]);
+	/// ```
+	#[cfg(target_has_atomic = "64")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_sample_n_in_m(
+		self,
+		n: u64,
+		m: u64,
+		counter: &AtomicU64,
+		func: impl FnOnce(&Self),
+	) -> Self {
+		assert!(m != 0, "sampling period must be nonzero");
+		if counter.fetch_add(1, Ordering::Relaxed) % m < n {
+			func(&self);
+		}
+		self
+	}
+
+	/// Immutable access to a value, with probability `rate`.
+	///
+	/// Each call draws a random `f64` in `[0, 1)` from the thread-local
+	/// generator of the [`rand`] crate, and runs the effect function if it is
+	/// below `rate`. A `rate` of `0.0` or less never runs the effect, and a
+	/// `rate` of `1.0` or more always does. Use this to sample an expensive
+	/// effect, such as serializing a value for a log, in a high-throughput
+	/// pipeline; use [`Tap::tap_sample_n_in_m`] when the sample must be
+	/// reproducible.
+	///
+	/// This requires the `rand` feature.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut logged = 0;
+	/// for n in 0 .. 1000 {
+	///   let _ = n.tap_sample(0.1, |_| logged += 1);
+	/// }
+	/// assert!(logged < 1000);
+	/// ```
+	///
+	/// [`Tap::tap_sample_n_in_m`]: trait.Tap.html#method.tap_sample_n_in_m
+	/// [`rand`]: https://docs.rs/rand/0.9
+	#[cfg(feature = "rand")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_sample(self, rate: f64, func: impl FnOnce(&Self)) -> Self {
+		if rand::random::<f64>() < rate {
+			func(&self);
+		}
+		self
+	}
+
+	/// Adds an amount derived from a value to a counter.
+	///
+	/// This function passes a borrow of the value to `amount`, and adds the
+	/// result to `counter` with relaxed ordering. It is useful for size
+	/// accounting, such as tracking the number of bytes processed.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::atomic::AtomicU64;
+	/// use tap::tap::Tap;
+	///
+	/// let bytes = AtomicU64::new(0);
+	/// let lines = ["hello", "world!"]
+	///   .iter()
+	///   .map(|s| s.tap_add(&bytes, |s| s.len() as u64))
+	///   .count();
+	/// assert_eq!(bytes.into_inner(), 11);
+	/// ```
+	#[cfg(target_has_atomic = "64")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_add(
+		self,
+		counter: &AtomicU64,
+		amount: impl FnOnce(&Self) -> u64,
+	) -> Self {
+		counter.fetch_add(amount(&self), Ordering::Relaxed);
+		self
+	}
+
+	/// Immutable access to a value, which is then forgotten.
+	///
+	/// This function passes a borrow of the value to the effect function, and
+	/// then calls `mem::forget` on it, so its destructor never runs. As the
+	/// value no longer exists once this returns, it produces `()` and must be
+	/// the last step of an expression.
+	///
+	/// See the [`mem`] module for the requirements on correct use.
+	///
+	/// [`mem`]: ../mem/index.html
+	#[inline(always)]
+	#[track_caller]
+	fn tap_before_forget(self, func: impl FnOnce(&Self)) {
+		crate::mem::inspect_then_forget(self, func)
+	}
+
+	/// Passes a borrow of a value through an optimization barrier.
+	///
+	/// This function hands `&self` to `core::hint::black_box`, which forces
+	/// the optimizer to assume that the value is observed at this point. It
+	/// lets benchmarks keep a computation from being folded away, and can be
+	/// inserted into or removed from an expression without changing its
+	/// shape.
+	///
+	/// This is intended **only** for benchmarking. `black_box` is a best-effort
+	/// hint, and provides no guarantees that code relying on it for
+	/// correctness can use.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let sum = (0 .. 100u64).sum::<u64>().tap_black_box();
+	/// assert_eq!(sum, 4950);
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_black_box(self) -> Self {
+		hint::black_box(&self);
+		self
+	}
+
+	/// Pinned mutable access to a value.
+	///
+	/// This function is identical to [`Tap::tap_mut`], except that the effect
+	/// function receives a `Pin<&mut Self>` produced by `Pin::new`. It allows
+	/// APIs that take a pinned receiver, which are common in `async` code, to
+	/// be called inline without a separate pinning binding.
+	///
+	/// Only `Unpin` types can be pinned this way, as the value is moved out of
+	/// the pin when it is returned. Types that are `!Unpin` must be pinned
+	/// explicitly, with `Box::pin` or `core::pin::pin!`, and cannot be
+	/// returned by value afterwards.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::{future::Future, pin::Pin, task::{Context, Poll}};
+	/// use tap::tap::Tap;
+	///
+	/// struct Ready(u8);
+	/// impl Future for Ready {
+	///   type Output = u8;
+	///   fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<u8> {
+	///     Poll::Ready(self.0)
+	///   }
+	/// }
+	///
+	/// let ready = Ready(5).tap_pin(|mut fut| {
+	///   let mut cx = Context::from_waker(std::task::Waker::noop());
+	///   assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(5));
+	/// });
+	/// ```
+	///
+	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_pin(mut self, func: impl FnOnce(Pin<&mut Self>)) -> Self
+	where
+		Self: Unpin,
+	{
+		func(Pin::new(&mut self));
+		self
+	}
+
+	/// Records a copy of a value into a vector.
+	///
+	/// This function clones the value and pushes the clone onto `sink`. It
+	/// provides a lightweight trail of every value that passed through a
+	/// point in an expression, for later assertions in tests or diagnostics.
+	///
+	/// Every call costs a full clone of the value, and `sink` grows without
+	/// bound. Use [`Tap::tap_push_with`] to record only a projection.
+	///
+	/// This is the tap sometimes called `tap_accumulate`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut trail = Vec::new();
+	/// let total = (1 .. 4)
+	///   .map(|n| (n * n).tap_push(&mut trail))
+	///   .sum::<i32>();
+	/// assert_eq!(total, 14);
+	/// assert_eq!(trail, [1, 4, 9]);
+	/// ```
+	///
+	/// [`Tap::tap_push_with`]: trait.Tap.html#method.tap_push_with
+	#[cfg(feature = "alloc")]
+	#[doc(alias = "tap_accumulate")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_push(self, sink: &mut Vec<Self>) -> Self
+	where
+		Self: Clone,
+	{
+		sink.push(self.clone());
+		self
+	}
+
+	/// Records a projection of a value into a vector.
+	///
+	/// This function is identical to [`Tap::tap_push`], except that rather
+	/// than cloning the whole value, it pushes the output of `project`. It is
+	/// the tap sometimes called `tap_accumulate_mapped`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut lens = Vec::new();
+	/// let words = ["one", "three"]
+	///   .iter()
+	///   .map(|w| w.to_string().tap_push_with(&mut lens, String::len))
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(lens, [3, 5]);
+	/// ```
+	///
+	/// [`Tap::tap_push`]: trait.Tap.html#method.tap_push
+	#[cfg(feature = "alloc")]
+	#[doc(alias = "tap_accumulate_mapped")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_push_with<U>(
+		self,
+		sink: &mut Vec<U>,
+		project: impl FnOnce(&Self) -> U,
+	) -> Self {
+		sink.push(project(&self));
+		self
+	}
+
+	/// Immutable access to a value and its predecessor, only when it has
+	/// changed.
+	///
+	/// `last` holds the previously seen value. If it holds a value that
+	/// differs from `self`, the effect function receives the new value and
+	/// then the old one, and `last` is updated to a clone of `self`. If it is
+	/// `None`, it is initialized with a clone of `self`, and the effect
+	/// function does not run.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mut prev = None;
+	/// let mut transitions = vec![];
+	/// for state in vec!["idle", "idle", "busy", "idle"] {
+	///   let _ = state.tap_on_change(&mut prev, |new, old| {
+	///     transitions.push(format!("{} -> {}", old, new));
+	///   });
+	/// }
+	/// assert_eq!(transitions, ["idle -> busy", "busy -> idle"]);
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_on_change(
+		self,
+		last: &mut Option<Self>,
+		func: impl FnOnce(&Self, &Self),
+	) -> Self
+	where
+		Self: PartialEq + Clone,
+	{
+		match last {
+			Some(prev) if *prev != self => {
+				func(&self, prev);
+				*prev = self.clone();
+			}
+			Some(_) => {}
+			None => *last = Some(self.clone()),
+		}
+		self
+	}
+
+	/// Mutable access to a value, reporting whether the mutation changed it.
+	///
+	/// This clones the value, runs the effect function on the original, and
+	/// compares the result against the clone. It returns the mutated value
+	/// together with `true` if the two differ, or `false` if the effect left
+	/// the value equal to what it was.
+	///
+	/// Each call pays for one `Clone` and one `PartialEq` comparison, whether
+	/// or not the value changes. For large values, prefer having the effect
+	/// report its own changes through [`Tap::tap_mut`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let (name, dirty) = "  tap ".to_string()
+	///   .tap_mut_if_changed(|s| *s = s.trim().to_string());
+	/// assert_eq!((name.as_str(), dirty), ("tap", true));
+	///
+	/// let (name, dirty) = name.tap_mut_if_changed(|s| *s = s.trim().to_string());
+	/// assert_eq!((name.as_str(), dirty), ("tap", false));
+	/// ```
+	///
+	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_mut_if_changed(mut self, func: impl FnOnce(&mut Self)) -> (Self, bool)
+	where
+		Self: Clone + PartialEq,
+	{
+		let before = self.clone();
+		func(&mut self);
+		let changed = self != before;
+		(self, changed)
+	}
+
+	/// Defers an effect function until the value leaves scope.
+	///
+	/// This wraps the value in a [`TapGuard`], which dereferences to it, and
+	/// which passes a borrow of it to the effect function when the guard is
+	/// dropped. The effect runs exactly once, after any modifications made
+	/// through the guard, and does not run if the guard is forgotten or
+	/// unwrapped with [`TapGuard::into_inner`].
+	///
+	/// The guard stores the effect function as a boxed trait object so that
+	/// its type does not need to name the closure. A boxed closure that is not
+	/// tied to a lifetime parameter of the guard must be `'static`, so the
+	/// effect function cannot borrow from its environment; share state with it
+	/// through `Rc` or `Arc` instead.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::{cell::Cell, rc::Rc};
+	/// use tap::tap::Tap;
+	///
+	/// let last = Rc::new(Cell::new(0));
+	/// {
+	///   let sink = last.clone();
+	///   let mut total = 0.tap_on_drop(move |t| sink.set(*t));
+	///   *total += 5;
+	///   *total += 10;
+	/// }
+	/// assert_eq!(last.get(), 15);
+	/// ```
+	///
+	/// [`TapGuard`]: struct.TapGuard.html
+	/// [`TapGuard::into_inner`]: struct.TapGuard.html#method.into_inner
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	#[must_use = "dropping the guard immediately runs the effect and discards the value"]
+	#[track_caller]
+	fn tap_on_drop(self, func: impl FnOnce(&Self) + 'static) -> TapGuard<Self> {
+		TapGuard {
+			value: mem::ManuallyDrop::new(self),
+			func: Some(Box::new(func)),
+		}
+	}
+
+	/// Traps into an attached debugger when a value satisfies a predicate.
+	///
+	/// In debug builds, this function passes a borrow of the value to `pred`,
+	/// and executes a hardware breakpoint instruction if it returns `true`. In
+	/// release builds, neither the predicate nor the trap are compiled.
+	///
+	/// The trap is `int3` on x86, `brk` on AArch64, and `bkpt` on ARM. Other
+	/// targets panic instead. **Executing a breakpoint without an attached
+	/// debugger terminates the process**, which is why this method requires
+	/// opting in to the `debugger` feature.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let checked = (0 .. 10)
+	///   .map(|n| n.tap_breakpoint_when(|n| *n > 100))
+	///   .count();
+	/// ```
+	#[cfg(feature = "debugger")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_breakpoint_when(self, pred: impl FnOnce(&Self) -> bool) -> Self {
+		if cfg!(debug_assertions) && pred(&self) {
+			breakpoint();
+		}
+		self
+	}
+
+	/// Immutable access to a value, through a trait-object effect function.
+	///
+	/// This function is identical to [`Tap::tap`], except that it is not
+	/// generic over the type of the effect function. Each distinct closure
+	/// passed to `.tap()` produces its own copy of the method; every call to
+	/// `.tap_dyn()` on a given type shares one copy, and pays for an indirect
+	/// call instead. The effect closures themselves are still compiled once
+	/// each, and additionally receive a vtable.
+	///
+	/// Prefer this for cold diagnostic taps in large codebases, where the
+	/// number of call sites makes monomorphization a noticeable share of
+	/// compile time and binary size. Prefer `.tap()` on hot paths, where the
+	/// effect function should be inlined.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let val = 5.tap_dyn(&|v| assert_eq!(*v, 5));
+	/// let val = val.tap_mut_dyn(&mut |v| *v += 1);
+	/// assert_eq!(val, 6);
+	/// ```
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_dyn(self, func: &dyn Fn(&Self)) -> Self {
+		func(&self);
+		self
+	}
+
+	/// Mutable access to a value, through a trait-object effect function.
+	///
+	/// This function is identical to [`Tap::tap_mut`], except that it is not
+	/// generic over the type of the effect function. See [`Tap::tap_dyn`] for
+	/// when to prefer it.
+	///
+	/// [`Tap::tap_dyn`]: trait.Tap.html#method.tap_dyn
+	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_mut_dyn(mut self, func: &mut dyn FnMut(&mut Self)) -> Self {
+		func(&mut self);
+		self
+	}
+
+	/// Immutable access to the raw bytes of a value.
+	///
+	/// This function views the memory of `self` as a byte slice, using
+	/// `bytemuck::bytes_of`, and passes it to the effect function. It is
+	/// useful for debugging serialization, endianness, and layout problems.
+	///
+	/// The `NoUninit` bound guarantees that the value contains no padding,
+	/// which would otherwise be uninitialized memory that cannot be read.
+	/// Types without a `NoUninit` implementation can use
+	/// [`Tap::tap_bit_pattern_unchecked`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let word = 0x0102_0304u32
+	///   .to_be()
+	///   .tap_bit_pattern(|b| assert_eq!(b, [1, 2, 3, 4]));
+	/// ```
+	///
+	/// [`Tap::tap_bit_pattern_unchecked`]: trait.Tap.html#method.tap_bit_pattern_unchecked
+	#[cfg(feature = "bytemuck")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_bit_pattern(self, func: impl FnOnce(&[u8])) -> Self
+	where
+		Self: NoUninit,
+	{
+		func(bytemuck::bytes_of(&self));
+		self
+	}
+
+	/// Immutable access to the raw bytes of a value, without checking that
+	/// they are initialized.
+	///
+	/// This function is identical to `.tap_bit_pattern()`, except that it
+	/// accepts any type.
+	///
+	/// # Safety
+	///
+	/// Every byte of `self` must be initialized. In particular, `Self` must not
+	/// contain padding between or after its fields, and must not contain
+	/// `MaybeUninit` or union fields whose bytes have not been written.
+	/// Reading uninitialized bytes is undefined behavior, even if the effect
+	/// function does not inspect them.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let pair = unsafe {
+	///   [1u16, 2].tap_bit_pattern_unchecked(|b| assert_eq!(b.len(), 4))
+	/// };
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	unsafe fn tap_bit_pattern_unchecked(self, func: impl FnOnce(&[u8])) -> Self {
+		func(slice::from_raw_parts(
+			&self as *const Self as *const u8,
+			mem::size_of::<Self>(),
+		));
+		self
+	}
+
+	/// Immutable access to a hexadecimal rendering of the raw bytes of a
+	/// value.
+	///
+	/// This function is identical to `.tap_bit_pattern()`, except that the
+	/// bytes are formatted as space-separated, two-digit, lowercase hex, in
+	/// memory order.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let val = 0xCAFEu16
+	///   .to_le()
+	///   .tap_hex_dump_bytes(|hex| assert_eq!(hex, "fe ca"));
+	/// ```
+	#[cfg(all(feature = "alloc", feature = "bytemuck"))]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_hex_dump_bytes(self, func: impl FnOnce(&str)) -> Self
+	where
+		Self: NoUninit,
+	{
+		func(&hex_dump(bytemuck::bytes_of(&self)));
+		self
+	}
+
+	/// Asserts that the raw bytes of a value match an expected pattern.
+	///
+	/// This compares the bytes that `.tap_bit_pattern()` would show against
+	/// `expected`, and panics if they differ in length or in any byte. The
+	/// panic message prints both patterns in hex, in memory order, with the
+	/// differing bytes marked. It is useful for pinning down an exact wire
+	/// format inline in encoding code.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let len = 0x0102u16.to_be().tap_assert_bit_pattern(&[0x01, 0x02]);
+	/// ```
+	///
+	/// ```rust,should_panic
+	/// use tap::tap::Tap;
+	///
+	/// // bit pattern mismatch
+	/// // expected: 01 02
+	/// //   actual: 02 01
+	/// //           ^^ ^^
+	/// let len = [0x02u8, 0x01].tap_assert_bit_pattern(&[0x01, 0x02]);
+	/// ```
+	#[cfg(feature = "bytemuck")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_assert_bit_pattern(self, expected: &[u8]) -> Self
+	where
+		Self: NoUninit,
+	{
+		let actual = bytemuck::bytes_of(&self);
+		if actual != expected {
+			panic!(
+				"bit pattern mismatch\nexpected: {}\n  actual: {}\n          {}",
+				HexBytes(expected),
+				HexBytes(actual),
+				HexDiff(expected, actual),
+			);
+		}
+		self
+	}
+
+	/// Reports the memory footprint of a value.
+	///
+	/// The effect function receives a [`MemReport`] holding the inline size of
+	/// the value and an estimate of the heap memory it owns, as computed by
+	/// [`HeapSize`]. See the [`mem`] module for the limits of that estimate.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// # #[cfg(feature = "alloc")] {
+	/// let v: Vec<u64> = Vec::with_capacity(8);
+	/// let v = v.tap_mem(|report| {
+	///   assert_eq!(report.stack, std::mem::size_of::<Vec<u64>>());
+	///   assert_eq!(report.heap_estimate, Some(64));
+	/// });
+	/// # }
+	/// ```
+	///
+	/// [`HeapSize`]: ../mem/trait.HeapSize.html
+	/// [`MemReport`]: ../mem/struct.MemReport.html
+	/// [`mem`]: ../mem/index.html
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_mem(self, func: impl FnOnce(MemReport)) -> Self
+	where
+		Self: HeapSize,
+	{
+		func(MemReport {
+			stack: mem::size_of_val(&self),
+			heap_estimate: self.heap_size(),
+		});
+		self
+	}
+
+	/// Asserts, at compile time, that a value is `Send`.
+	///
+	/// This function does nothing at runtime. Placing it in an expression
+	/// causes compilation to fail with an unsatisfied-bound error if the value
+	/// at that point is not `Send`, which documents and enforces the
+	/// requirement where it matters rather than where the value is
+	/// eventually sent.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let data = vec![1, 2, 3].tap_ensure_send();
+	/// ```
+	///
+	/// ```rust,compile_fail
+	/// use std::rc::Rc;
+	/// use tap::tap::Tap;
+	///
+	/// let data = Rc::new(5).tap_ensure_send();
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_ensure_send(self) -> Self
+	where
+		Self: Send,
+	{
+		self
+	}
+
+	/// Asserts, at compile time, that a value is `Sync`.
+	///
+	/// This function does nothing at runtime, and is the `Sync` counterpart of
+	/// [`Tap::tap_ensure_send`]. It additionally requires `Copy`, so that it is
+	/// only used on values that can be freely shared by reference or by copy.
+	///
+	/// ```rust,compile_fail
+	/// use std::cell::Cell;
+	/// use tap::tap::Tap;
+	///
+	/// let _ = (&Cell::new(5)).tap_ensure_sync();
+	/// ```
+	///
+	/// [`Tap::tap_ensure_send`]: trait.Tap.html#method.tap_ensure_send
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_ensure_sync(self) -> Self
+	where
+		Self: Sync + Copy,
+	{
+		self
+	}
+
+	/// Asserts, at compile time, that a value is `Unpin`.
+	///
+	/// This function does nothing at runtime.
+	///
+	/// ```rust,compile_fail
+	/// use std::marker::PhantomPinned;
+	/// use tap::tap::Tap;
+	///
+	/// let pinned = PhantomPinned.tap_ensure_unpin();
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_ensure_unpin(self) -> Self
+	where
+		Self: Unpin,
+	{
+		self
+	}
+
+	/// Asserts, at compile time, that a value is `'static`.
+	///
+	/// This function does nothing at runtime. It fails to compile if the value
+	/// borrows from any non-`'static` data.
+	///
+	/// ```rust,compile_fail
+	/// use tap::tap::Tap;
+	///
+	/// let text = String::from("hello");
+	/// let _ = text.as_str().tap_ensure_static();
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_ensure_static(self) -> Self
+	where
+		Self: 'static,
+	{
+		self
+	}
+
+	/// Immutable access to a value, only in builds that enable the
+	/// `test-hooks` feature.
+	///
+	/// This is intended for invariant checks that should run under
+	/// `cargo test`, but not in ordinary debug builds. A library cannot observe
+	/// `cfg(test)` in the crate that depends on it, so the effect function is
+	/// instead gated on this crate’s `test-hooks` feature, which the dependent
+	/// crate enables only for its tests by naming `tap` a second time as a
+	/// dev-dependency:
+	///
+	/// ```toml
+	/// [dependencies]
+	/// tap = "1"
+	///
+	/// [dev-dependencies]
+	/// tap = { version = "1", features = ["test-hooks"] }
+	/// ```
+	///
+	/// This requires Cargo’s version 2 feature resolver, which is the default
+	/// from edition 2021 and can be selected with `resolver = "2"` in older
+	/// manifests. The version 1 resolver merges dev-dependency features into
+	/// every build, which would enable the hooks everywhere.
+	///
+	/// Without the feature, the effect function is not run. It is still
+	/// type-checked, as part of the calling code, and the call is optimized
+	/// away.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let sorted = vec![1, 2, 3]
+	///   .tap_test(|v| assert!(v.windows(2).all(|w| w[0] <= w[1])));
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_test(self, func: impl FnOnce(&Self)) -> Self {
+		if cfg!(feature = "test-hooks") {
+			func(&self);
+		}
+		self
+	}
+
+	/// Asserts that a number is within `epsilon` of `expected`.
+	///
+	/// The value is converted to `f64` and compared by absolute difference:
+	/// this panics unless `|self - expected| <= epsilon`. A `NaN` on either
+	/// side always fails the check.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let mean = [1.0f32, 2.0, 4.0].iter().sum::<f32>() / 3.0;
+	/// let mean = mean.tap_assert_approx_eq(2.333, 0.001);
+	/// ```
+	///
+	/// ```rust,should_panic
+	/// use tap::tap::Tap;
+	///
+	/// // expected 0.3 ± 0.00000000000000001, got 0.30000000000000004
+	/// let sum = (0.1 + 0.2).tap_assert_approx_eq(0.3, 1e-17);
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_assert_approx_eq(self, expected: f64, epsilon: f64) -> Self
+	where
+		Self: Into<f64> + Copy,
+	{
+		let actual = self.into();
+		//  `NaN` compares false, and so is never within bounds
+		let within = (actual - expected).abs() <= epsilon;
+		if !within {
+			panic!("expected {} ± {}, got {}", expected, epsilon, actual);
+		}
+		self
+	}
+
+	/// Asserts that a value is within `epsilon` of `expected`, according to
+	/// [`approx::AbsDiffEq`].
+	///
+	/// Unlike [`.tap_assert_approx_eq()`], this does not convert to `f64`, and
+	/// so works for any type that implements `AbsDiffEq`, such as the vector
+	/// and matrix types of the linear-algebra crates built on `approx`. For
+	/// floats, `Epsilon` is the float type itself.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::Tap;
+	///
+	/// let third = (1.0f32 / 3.0).tap_assert_abs_diff_eq(0.333_333, 1e-6);
+	/// ```
+	///
+	/// [`.tap_assert_approx_eq()`]: trait.Tap.html#method.tap_assert_approx_eq
+	/// [`approx::AbsDiffEq`]: https://docs.rs/approx/0.5/approx/trait.AbsDiffEq.html
+	#[cfg(feature = "approx")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_assert_abs_diff_eq(
+		self,
+		expected: Self,
+		epsilon: <Self as AbsDiffEq>::Epsilon,
+	) -> Self
+	where
+		Self: AbsDiffEq + Debug,
+		<Self as AbsDiffEq>::Epsilon: Clone + Debug,
+	{
+		if !self.abs_diff_eq(&expected, epsilon.clone()) {
+			panic!("expected {:?} ± {:?}, got {:?}", expected, epsilon, self);
+		}
+		self
+	}
+
+	//  debug-build-only copies of the above methods
+
+	/// Calls `.tap()` only in debug builds, and is erased in release builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_dbg(self, func: impl FnOnce(&Self)) -> Self {
+		if cfg!(debug_assertions) {
+			func(&self);
+		}
+		self
+	}
+
+	/// Calls `.tap_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_mut_dbg(mut self, func: impl FnOnce(&mut Self)) -> Self {
+		if cfg!(debug_assertions) {
+			func(&mut self);
+		}
+		self
+	}
+
+	/// Calls `.tap_copy()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_copy_dbg(self, func: impl FnOnce(Self)) -> Self
+	where
+		Self: Copy,
+	{
+		if cfg!(debug_assertions) {
+			func(self);
+		}
+		self
+	}
+
+	/// Calls `.tap_pin()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_pin_dbg(mut self, func: impl FnOnce(Pin<&mut Self>)) -> Self
+	where
+		Self: Unpin,
+	{
+		if cfg!(debug_assertions) {
+			func(Pin::new(&mut self));
+		}
+		self
+	}
+
+	/// Calls `.tap_dyn()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_dyn_dbg(self, func: &dyn Fn(&Self)) -> Self {
+		if cfg!(debug_assertions) {
+			func(&self);
+		}
+		self
+	}
+
+	/// Calls `.tap_mut_dyn()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_mut_dyn_dbg(mut self, func: &mut dyn FnMut(&mut Self)) -> Self {
+		if cfg!(debug_assertions) {
+			func(&mut self);
+		}
+		self
+	}
+
+	/// Calls `.tap_borrow()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_borrow_dbg<B>(self, func: impl FnOnce(&B)) -> Self
+	where
+		Self: Borrow<B>,
+		B: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(Borrow::<B>::borrow(&self));
+		}
+		self
+	}
+
+	/// Calls `.tap_borrow_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_borrow_mut_dbg<B>(mut self, func: impl FnOnce(&mut B)) -> Self
+	where
+		Self: BorrowMut<B>,
+		B: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(BorrowMut::<B>::borrow_mut(&mut self));
+		}
+		self
+	}
+
+	/// Calls `.tap_ref()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_ref_dbg<R>(self, func: impl FnOnce(&R)) -> Self
+	where
+		Self: AsRef<R>,
+		R: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(AsRef::<R>::as_ref(&self));
+		}
+		self
+	}
+
+	/// Calls `.tap_ref_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_ref_mut_dbg<R>(mut self, func: impl FnOnce(&mut R)) -> Self
+	where
+		Self: AsMut<R>,
+		R: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(AsMut::<R>::as_mut(&mut self));
+		}
+		self
+	}
+
+	/// Calls `.tap_deref()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_deref_dbg<T>(self, func: impl FnOnce(&T)) -> Self
+	where
+		Self: Deref<Target = T>,
+		T: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(Deref::deref(&self));
+		}
+		self
+	}
+
+	/// Calls `.tap_deref_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_deref_mut_dbg<T>(mut self, func: impl FnOnce(&mut T)) -> Self
+	where
+		Self: DerefMut + Deref<Target = T>,
+		T: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(DerefMut::deref_mut(&mut self));
+		}
+		self
+	}
+
+	/// Calls `.tap_deref2()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_deref2_dbg<T, U>(self, func: impl FnOnce(&U)) -> Self
+	where
+		Self: Deref<Target = T>,
+		T: Deref<Target = U> + ?Sized,
+		U: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(&**self);
+		}
+		self
+	}
+
+	/// Calls `.tap_deref2_mut()` only in debug builds, and is erased in
+	/// release builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_deref2_mut_dbg<T, U>(mut self, func: impl FnOnce(&mut U)) -> Self
+	where
+		Self: DerefMut + Deref<Target = T>,
+		T: DerefMut + Deref<Target = U> + ?Sized,
+		U: ?Sized,
+	{
+		if cfg!(debug_assertions) {
+			func(&mut **self);
+		}
+		self
+	}
+
+	/// Calls `.tap_display()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_display_dbg(self, func: impl FnOnce(&dyn Display)) -> Self
+	where
+		Self: Display,
+	{
+		if cfg!(debug_assertions) {
+			func(&self);
+		}
+		self
+	}
+
+	/// Calls `.tap_debug()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_debug_dbg(self, func: impl FnOnce(&dyn Debug)) -> Self
+	where
+		Self: Debug,
+	{
+		if cfg!(debug_assertions) {
+			func(&self);
+		}
+		self
+	}
+
+	/// Calls `.tap_push()` only in debug builds, and is erased in release
+	/// builds.
+	///
+	/// Release builds neither clone the value nor grow `sink`. This is the tap
+	/// sometimes called `tap_accumulate_dbg`.
+	#[cfg(feature = "alloc")]
+	#[doc(alias = "tap_accumulate_dbg")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_push_dbg(self, sink: &mut Vec<Self>) -> Self
+	where
+		Self: Clone,
+	{
+		if cfg!(debug_assertions) {
+			return self.tap_push(sink);
+		}
+		self
+	}
+
+	/// Calls `.tap_push_with()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_push_with_dbg<U>(
+		self,
+		sink: &mut Vec<U>,
+		project: impl FnOnce(&Self) -> U,
+	) -> Self {
+		if cfg!(debug_assertions) {
+			return self.tap_push_with(sink, project);
+		}
+		self
+	}
+
+	/// Calls `.tap_on_change()` only in debug builds, and is erased in
+	/// release builds.
+	///
+	/// Release builds neither compare the value nor update `last`.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_on_change_dbg(
+		self,
+		last: &mut Option<Self>,
+		func: impl FnOnce(&Self, &Self),
+	) -> Self
+	where
+		Self: PartialEq + Clone,
+	{
+		if cfg!(debug_assertions) {
+			return self.tap_on_change(last, func);
+		}
+		self
+	}
+
+	/// Calls `.tap_assert_approx_eq()` only in debug builds, and is erased in
+	/// release builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_assert_approx_eq_dbg(self, expected: f64, epsilon: f64) -> Self
+	where
+		Self: Into<f64> + Copy,
+	{
+		if cfg!(debug_assertions) {
+			return self.tap_assert_approx_eq(expected, epsilon);
+		}
+		self
+	}
+
+	/// Calls `.tap_assert_bit_pattern()` only in debug builds, and is erased
+	/// in release builds.
+	#[cfg(feature = "bytemuck")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_assert_bit_pattern_dbg(self, expected: &[u8]) -> Self
+	where
+		Self: NoUninit,
+	{
+		if cfg!(debug_assertions) {
+			return self.tap_assert_bit_pattern(expected);
+		}
+		self
+	}
+
+	/// Immutable access to a value, along with the time elapsed since some
+	/// starting point.
+	///
+	/// This function computes `since.elapsed()` and passes it to the effect
+	/// function alongside a borrow of the value. It is useful for measuring how
+	/// long the production of a value took, without breaking the expression
+	/// apart to insert timing statements.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::time::Instant;
+	/// use tap::tap::Tap;
+	/// # fn expensive() -> i32 { 5 }
+	///
+	/// let start = Instant::now();
+	/// let val = expensive()
+	///   .tap_elapsed(start, |v, d| println!("produced {} in {:?}", v, d));
+	/// ```
+	///
+	/// To measure the time between successive executions of a single call
+	/// site, rather than from a fixed starting point, use the
+	/// [`tap_elapsed_since_last!`] macro.
+	///
+	/// [`tap_elapsed_since_last!`]: ../macro.tap_elapsed_since_last.html
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_elapsed(
+		self,
+		since: Instant,
+		func: impl FnOnce(&Self, Duration),
+	) -> Self {
+		func(&self, since.elapsed());
+		self
+	}
+
+	/// Immutable access to a value, along with the ID of the current thread.
+	///
+	/// This function passes `std::thread::current().id()` to the effect
+	/// function alongside a borrow of the value. It is useful for tracking
+	/// which thread processes a value when debugging data races or
+	/// work-distribution problems.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::thread;
+	/// use tap::tap::Tap;
+	///
+	/// let main = thread::current().id();
+	/// let _ = 5.tap_thread_id(|_, id| assert_eq!(id, main));
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_thread_id(self, func: impl FnOnce(&Self, ThreadId)) -> Self {
+		func(&self, thread::current().id());
+		self
+	}
+
+	/// Immutable access to a value, along with the name of the current
+	/// thread.
+	///
+	/// This function passes `std::thread::current().name()` to the effect
+	/// function alongside a borrow of the value. Threads that were not given a
+	/// name when spawned report `None`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::thread;
+	/// use tap::tap::Tap;
+	///
+	/// thread::Builder::new()
+	///   .name("worker".into())
+	///   .spawn(|| 5.tap_thread_name(|_, name| assert_eq!(name, Some("worker"))))
+	///   .unwrap()
+	///   .join()
+	///   .unwrap();
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_thread_name(self, func: impl FnOnce(&Self, Option<&str>)) -> Self {
+		func(&self, thread::current().name());
+		self
+	}
+
+	/// Calls `.tap_thread_id()` only in debug builds, and is erased in release
+	/// builds.
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_thread_id_dbg(self, func: impl FnOnce(&Self, ThreadId)) -> Self {
+		if cfg!(debug_assertions) {
+			func(&self, thread::current().id());
+		}
+		self
+	}
+
+	/// Calls `.tap_thread_name()` only in debug builds, and is erased in
+	/// release builds.
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_thread_name_dbg(
+		self,
+		func: impl FnOnce(&Self, Option<&str>),
+	) -> Self {
+		if cfg!(debug_assertions) {
+			func(&self, thread::current().name());
+		}
+		self
+	}
+
+	/// Immutable access to a value, timed and accumulated under a label.
+	///
+	/// This function is identical to [`Tap::tap`], except that the execution
+	/// of the effect function is timed, and the measurement is added to a
+	/// global summary under `label`. The summaries of all labels can be
+	/// retrieved with [`profile::report`].
+	///
+	/// When profiling is disabled with [`profile::set_enabled`], the effect
+	/// function is run without being timed.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::{profile, tap::Tap};
+	///
+	/// let sum = vec![1, 2, 3]
+	///   .tap_profile("sum", |v| { v.iter().sum::<i32>(); })
+	///   .into_iter()
+	///   .sum::<i32>();
+	/// assert!(profile::report().iter().any(|e| e.label == "sum"));
+	/// ```
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	/// [`profile::report`]: ../profile/fn.report.html
+	/// [`profile::set_enabled`]: ../profile/fn.set_enabled.html
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_profile(self, label: &'static str, func: impl FnOnce(&Self)) -> Self {
+		if crate::profile::is_enabled() {
+			let start = Instant::now();
+			func(&self);
+			crate::profile::record(label, start.elapsed());
+		} else {
+			func(&self);
+		}
+		self
+	}
+
+	/// Sends a value derived from `self` into a channel.
+	///
+	/// This function passes a borrow of the value to `transform`, and sends
+	/// the result into `sender`. It is useful for sampling values out of a
+	/// processing pipeline into a monitoring thread without disturbing the
+	/// pipeline. `mpsc` sends never block, so this does not stall the
+	/// pipeline if the monitor falls behind.
+	///
+	/// If the receiving end of the channel has been dropped, the send failure
+	/// is silently ignored. Use [`Tap::tap_channel_send_or_panic`], or
+	/// [`Tap::tap_channel_send_or_log`] with the `log` feature, to observe it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::mpsc;
+	/// use tap::tap::Tap;
+	///
+	/// let (tx, rx) = mpsc::channel();
+	/// let total = (1 .. 4)
+	///   .map(|n| n.tap_channel_send(&tx, |n| n * 10))
+	///   .sum::<i32>();
+	/// assert_eq!(total, 6);
+	/// assert_eq!(rx.try_iter().collect::<Vec<_>>(), [10, 20, 30]);
+	/// ```
+	///
+	/// [`Tap::tap_channel_send_or_log`]: trait.Tap.html#method.tap_channel_send_or_log
+	/// [`Tap::tap_channel_send_or_panic`]: trait.Tap.html#method.tap_channel_send_or_panic
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_channel_send<U>(
+		self,
+		sender: &Sender<U>,
+		transform: impl FnOnce(&Self) -> U,
+	) -> Self {
+		let _ = sender.send(transform(&self));
+		self
+	}
+
+	/// Sends a value derived from `self` into a channel, panicking if the
+	/// receiver has disconnected.
+	///
+	/// This function is identical to [`Tap::tap_channel_send`], except that a
+	/// failed send panics rather than being ignored.
+	///
+	/// [`Tap::tap_channel_send`]: trait.Tap.html#method.tap_channel_send
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_channel_send_or_panic<U>(
+		self,
+		sender: &Sender<U>,
+		transform: impl FnOnce(&Self) -> U,
+	) -> Self {
+		if sender.send(transform(&self)).is_err() {
+			panic!("tap channel receiver has disconnected");
+		}
+		self
+	}
+
+	/// Sends a value derived from `self` into a channel, logging a warning if
+	/// the receiver has disconnected.
+	///
+	/// This function is identical to [`Tap::tap_channel_send`], except that a
+	/// failed send emits a `log` warning rather than being silently ignored.
+	///
+	/// [`Tap::tap_channel_send`]: trait.Tap.html#method.tap_channel_send
+	#[cfg(all(feature = "std", feature = "log"))]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_channel_send_or_log<U>(
+		self,
+		sender: &Sender<U>,
+		transform: impl FnOnce(&Self) -> U,
+	) -> Self {
+		if let Err(err) = sender.send(transform(&self)) {
+			log::warn!("tap channel send failed: {}", err);
+		}
+		self
+	}
+
+	/// Immutable access to a value, reporting when the effect function is
+	/// slow.
+	///
+	/// This function is identical to [`Tap::tap`], except that the execution
+	/// of the effect function is timed, and `on_slow` is called with the
+	/// measured duration only if it exceeds `threshold`. Stages that finish
+	/// within their budget produce no report.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::time::Duration;
+	/// use tap::tap::Tap;
+	///
+	/// let mut slow = false;
+	/// let val = 5.tap_slow_warn(
+	///   Duration::from_secs(60),
+	///   |v| assert_eq!(*v, 5),
+	///   |_| slow = true,
+	/// );
+	/// assert!(!slow);
+	/// ```
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_slow_warn(
+		self,
+		threshold: Duration,
+		func: impl FnOnce(&Self),
+		on_slow: impl FnOnce(Duration),
+	) -> Self {
+		let start = Instant::now();
+		func(&self);
+		let elapsed = start.elapsed();
+		if elapsed > threshold {
+			on_slow(elapsed);
+		}
+		self
+	}
+
+	/// Calls `.tap_slow_warn()` with a reporter that emits a `log` warning.
+	///
+	/// The warning includes both the threshold and the measured duration.
+	#[cfg(all(feature = "std", feature = "log"))]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_slow_log(
+		self,
+		threshold: Duration,
+		func: impl FnOnce(&Self),
+	) -> Self {
+		self.tap_slow_warn(threshold, func, |elapsed| {
+			log::warn!(
+				"tap took {:?}, exceeding its threshold of {:?}",
+				elapsed,
+				threshold,
+			)
+		})
+	}
+
+	/// Calls `.tap_slow_warn()` with a reporter that emits a `tracing`
+	/// warning.
+	///
+	/// The warning event records both the threshold and the measured duration
+	/// as fields.
+	#[cfg(all(feature = "std", feature = "tracing"))]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_slow_trace(
+		self,
+		threshold: Duration,
+		func: impl FnOnce(&Self),
+	) -> Self {
+		self.tap_slow_warn(threshold, func, |elapsed| {
+			tracing::warn!(?threshold, ?elapsed, "tap exceeded its threshold")
+		})
+	}
+
+	/// Immutable access to a value, at most once per `Once` cell.
+	///
+	/// This function runs the effect function only if `cell` has not
+	/// previously completed; all later calls with the same cell skip the
+	/// effect entirely and only pay for a single atomic load. It is useful for
+	/// one-shot logging on a hot path, such as reporting the first request a
+	/// server receives.
+	///
+	/// The cell is thread-safe: if multiple threads reach the tap at the same
+	/// time, exactly one of them runs the effect, and the others block until
+	/// it has finished. If the effect panics, the cell is poisoned, and every
+	/// later use of it also panics. [`Tap::tap_once_flag`] does the same with
+	/// an `AtomicBool`, and needs only 8-bit atomics rather than `std`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::Once;
+	/// use tap::tap::Tap;
+	///
+	/// static FIRST: Once = Once::new();
+	/// let mut seen = Vec::new();
+	/// for n in 0 .. 3 {
+	///   let _ = n.tap_once(&FIRST, |n| seen.push(*n));
+	/// }
+	/// assert_eq!(seen, [0]);
+	/// ```
+	///
+	/// [`Tap::tap_once_flag`]: trait.Tap.html#method.tap_once_flag
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_once(self, cell: &Once, func: impl FnOnce(&Self)) -> Self {
+		cell.call_once(|| func(&self));
+		self
+	}
+
+	/// Immutable access to a value, at most once per flag.
+	///
+	/// This function runs the effect function only if it is the first to set
+	/// `flag` from `false` to `true`, and skips it on every later call with
+	/// the same flag. Unlike [`Tap::tap_once`], it does not require `std`,
+	/// but only exists on targets with 8-bit atomic compare-and-swap
+	/// (`target_has_atomic = "8"`). Other threads that reach the tap while
+	/// the effect is running skip it rather than waiting for it to finish. A
+	/// panicking effect leaves the flag set, rather than poisoning it.
+	///
+	/// The [`tap_once!`] macro declares the flag at the call site.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::atomic::AtomicBool;
+	/// use tap::tap::Tap;
+	///
+	/// static WARNED: AtomicBool = AtomicBool::new(false);
+	/// let mut warnings = 0;
+	/// for n in 0 .. 3 {
+	///   let _ = n.tap_once_flag(&WARNED, |_| warnings += 1);
+	/// }
+	/// assert_eq!(warnings, 1);
+	/// ```
+	///
+	/// [`Tap::tap_once`]: trait.Tap.html#method.tap_once
+	/// [`tap_once!`]: ../macro.tap_once.html
+	#[cfg(target_has_atomic = "8")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_once_flag(self, flag: &AtomicBool, func: impl FnOnce(&Self)) -> Self {
+		if flag
+			.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+			.is_ok()
+		{
+			func(&self);
+		}
+		self
+	}
+
+	/// Immutable access to a value, if a rate limiter allows it.
+	///
+	/// This function takes a token from `bucket`, and runs the effect function
+	/// only if one was available. When the bucket is empty, the effect is
+	/// skipped. It is useful for keeping a noisy log line from flooding the
+	/// output.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::{Tap, TokenBucket};
+	///
+	/// static LOG_LIMIT: TokenBucket = TokenBucket::new(3, 1.0);
+	/// let mut logged = 0;
+	/// for n in 0 .. 100 {
+	///   let _ = n.tap_rate_limited(&LOG_LIMIT, |_| logged += 1);
+	/// }
+	/// assert_eq!(logged, 3);
+	/// ```
+	#[cfg(all(feature = "std", target_has_atomic = "64"))]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_rate_limited(
+		self,
+		bucket: &TokenBucket,
+		func: impl FnOnce(&Self),
+	) -> Self {
+		if bucket.try_acquire() {
+			func(&self);
+		}
+		self
+	}
+
+	/// Immutable access to a value, if a rate limiter allows it, or a
+	/// notification that it was throttled.
+	///
+	/// This function is identical to [`Tap::tap_rate_limited`], except that
+	/// when the bucket is empty, it runs `on_throttle` instead of skipping
+	/// silently.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::{Tap, TokenBucket};
+	///
+	/// let bucket = TokenBucket::new(1, 0.0);
+	/// let (mut sent, mut dropped) = (0, 0);
+	/// for n in 0 .. 4 {
+	///   let _ = n.tap_rate_limited_or(&bucket, || dropped += 1, |_| sent += 1);
+	/// }
+	/// assert_eq!((sent, dropped), (1, 3));
+	/// ```
+	///
+	/// [`Tap::tap_rate_limited`]: trait.Tap.html#method.tap_rate_limited
+	#[cfg(all(feature = "std", target_has_atomic = "64"))]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_rate_limited_or(
+		self,
+		bucket: &TokenBucket,
+		on_throttle: impl FnOnce(),
+		func: impl FnOnce(&Self),
+	) -> Self {
+		if bucket.try_acquire() {
+			func(&self);
+		} else {
+			on_throttle();
+		}
+		self
+	}
+
+	/// Immutable access to a value, through a circuit breaker.
+	///
+	/// The effect function runs only if `breaker` allows it, and reports
+	/// whether it succeeded. Once it has failed enough times in a row, the
+	/// breaker opens, and the tap becomes a no-op until the breaker’s reset
+	/// timeout passes and a trial call succeeds. This keeps a tap that talks
+	/// to an unavailable service from paying for a failure on every value.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::time::Duration;
+	/// use tap::{circuit_breaker::CircuitBreaker, tap::Tap};
+	///
+	/// let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+	/// let mut calls = 0;
+	/// for n in 0 .. 5 {
+	///   let _ = n.tap_circuit(&breaker, |_| {
+	///     calls += 1;
+	///     Err(())
+	///   });
+	/// }
+	/// assert_eq!(calls, 2);
+	/// ```
+	#[cfg(all(feature = "std", target_has_atomic = "64"))]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_circuit(
+		self,
+		breaker: &CircuitBreaker,
+		func: impl FnOnce(&Self) -> Result<(), ()>,
+	) -> Self {
+		let _ = breaker.call(|| func(&self));
+		self
+	}
+
+	/// Counts the allocations made by an effect function.
+	///
+	/// The first function receives a borrow of the value, and may return
+	/// anything; its return value is dropped only after the measurement ends,
+	/// so that returning an allocation keeps its deallocation out of the
+	/// count. The second function receives the allocator activity of the
+	/// current thread while the first ran.
+	///
+	/// This requires [`CountingAllocator`] to be installed as the global
+	/// allocator, and reports zero activity otherwise.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::alloc::System;
+	/// use tap::{alloc::CountingAllocator, tap::Tap};
+	///
+	/// #[global_allocator]
+	/// static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+	///
+	/// let text = "a,b,c".tap_alloc_count(
+	///   |s| s.split(',').count(),
+	///   |stats| assert!(!stats.allocated()),
+	/// );
+	/// ```
+	///
+	/// [`CountingAllocator`]: ../alloc/struct.CountingAllocator.html
+	#[cfg(feature = "counting-alloc")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_alloc_count<R>(
+		self,
+		func: impl FnOnce(&Self) -> R,
+		on_done: impl FnOnce(AllocStats),
+	) -> Self {
+		let before = crate::alloc::stats();
+		let out = func(&self);
+		let after = crate::alloc::stats();
+		drop(out);
+		on_done(after.since(before));
+		self
+	}
+
+	/// Asserts that an effect function does not allocate.
+	///
+	/// This panics if the effect function allocates or reallocates any memory
+	/// on the current thread. Deallocations are permitted. Like
+	/// [`.tap_alloc_count()`], it requires [`CountingAllocator`] to be
+	/// installed, and passes vacuously otherwise.
+	///
+	/// # Examples
+	///
+	/// ```rust,should_panic
+	/// use std::alloc::System;
+	/// use tap::{alloc::CountingAllocator, tap::Tap};
+	///
+	/// #[global_allocator]
+	/// static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+	///
+	/// let n = 5.tap_assert_no_alloc(|n| n.to_string());
+	/// ```
+	///
+	/// [`.tap_alloc_count()`]: trait.Tap.html#method.tap_alloc_count
+	/// [`CountingAllocator`]: ../alloc/struct.CountingAllocator.html
+	#[cfg(feature = "counting-alloc")]
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_assert_no_alloc<R>(self, func: impl FnOnce(&Self) -> R) -> Self {
+		self.tap_alloc_count(func, |stats| {
+			assert!(!stats.allocated(), "effect function allocated: {:?}", stats)
+		})
+	}
+}
+
+impl<T> Tap for T where T: Sized {}
+
+/// A value that runs an effect function when it leaves scope.
+///
+/// This is produced by [`Tap::tap_on_drop`], and dereferences to the value it
+/// holds.
+///
+/// [`Tap::tap_on_drop`]: trait.Tap.html#method.tap_on_drop
+#[cfg(feature = "alloc")]
+#[must_use = "dropping the guard immediately runs the effect and discards the value"]
+pub struct TapGuard<T> {
+	value: mem::ManuallyDrop<T>,
+	func: Option<DropEffect<T>>,
+}
+
+/// The type-erased effect function held by a [`TapGuard`].
+///
+/// [`TapGuard`]: struct.TapGuard.html
+#[cfg(feature = "alloc")]
+type DropEffect<T> = Box<dyn FnOnce(&T)>;
+
+#[cfg(feature = "alloc")]
+impl<T> TapGuard<T> {
+	/// Unwraps the value, cancelling the effect function.
+	///
+	/// This is an associated function, rather than a method, so that it does
+	/// not shadow a method of the same name on `T`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::{Tap, TapGuard};
+	///
+	/// let guard = 5.tap_on_drop(|_| unreachable!());
+	/// assert_eq!(TapGuard::into_inner(guard), 5);
+	/// ```
+	#[inline]
+	pub fn into_inner(guard: Self) -> T {
+		let mut guard = mem::ManuallyDrop::new(guard);
+		guard.func = None;
+		//  SAFETY: the guard is never used again, and its destructor is
+		//  suppressed, so the value is moved out exactly once.
+		unsafe { mem::ManuallyDrop::take(&mut guard.value) }
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Deref for TapGuard<T> {
+	type Target = T;
+
+	#[inline]
+	fn deref(&self) -> &T {
+		&self.value
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DerefMut for TapGuard<T> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.value
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Drop for TapGuard<T> {
+	fn drop(&mut self) {
+		if let Some(func) = self.func.take() {
+			func(&self.value);
+		}
+		//  SAFETY: this is the only place, other than `into_inner`, that
+		//  releases the value, and `into_inner` suppresses this destructor.
+		unsafe { mem::ManuallyDrop::drop(&mut self.value) }
+	}
+}
+
+/// Executes the target’s hardware breakpoint instruction.
+#[cfg(feature = "debugger")]
+#[inline(always)]
+fn breakpoint() {
+	#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+	unsafe {
+		core::arch::asm!("int3", options(nomem, nostack));
+	}
+	#[cfg(target_arch = "aarch64")]
+	unsafe {
+		core::arch::asm!("brk #0xf000", options(nomem, nostack));
+	}
+	#[cfg(target_arch = "arm")]
+	unsafe {
+		core::arch::asm!("bkpt #0", options(nomem, nostack));
+	}
+	#[cfg(not(any(
+		target_arch = "x86",
+		target_arch = "x86_64",
+		target_arch = "aarch64",
+		target_arch = "arm",
+	)))]
+	panic!("tap breakpoint reached on a target without a trap instruction");
+}
+
+/** Point-free modification of slice buffers.
+
+This trait is implemented on all types that mutably dereference to a slice, such
+as `Vec<T>`, `Box<[T]>`, and `&mut [T]`. It provides taps that forward to the
+slice’s bulk-copy methods, so that buffers can be initialized inside a larger
+expression.
+**/
+pub trait TapSlice<T>
+where
+	Self: Sized + DerefMut<Target = [T]>,
+{
+	/// Overwrites the slice with a copy of `src`.
+	///
+	/// This forwards to `<[T]>::copy_from_slice`, and so panics if `src` does
+	/// not have the same length as `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapSlice;
+	///
+	/// let header = [0xCA, 0xFE];
+	/// let buf = vec![0u8; 2].tap_copy_from_slice(&header);
+	/// assert_eq!(buf, header);
+	/// ```
+	#[inline(always)]
+	fn tap_copy_from_slice(mut self, src: &[T]) -> Self
+	where
+		T: Copy,
+	{
+		self.copy_from_slice(src);
+		self
+	}
+
+	/// Overwrites the slice with clones of the elements of `src`.
+	///
+	/// This forwards to `<[T]>::clone_from_slice`, and so panics if `src` does
+	/// not have the same length as `self`.
+	#[inline(always)]
+	fn tap_clone_from_slice(mut self, src: &[T]) -> Self
+	where
+		T: Clone,
+	{
+		self.clone_from_slice(src);
+		self
+	}
+
+	/// Copies a range of the slice to another position within itself.
+	///
+	/// This forwards to `<[T]>::copy_within`, and so panics if either the
+	/// source range or the destination range falls outside the slice.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapSlice;
+	///
+	/// let buf = vec![1, 2, 3, 0, 0].tap_copy_within(.. 2, 3);
+	/// assert_eq!(buf, [1, 2, 3, 1, 2]);
+	/// ```
+	#[inline(always)]
+	fn tap_copy_within<R>(mut self, src: R, dest: usize) -> Self
+	where
+		R: RangeBounds<usize>,
+		T: Copy,
+	{
+		self.copy_within(src, dest);
+		self
+	}
+}
+
+impl<S, T> TapSlice<T> for S where S: DerefMut<Target = [T]> {}
+
+/** Inline checks of slice invariants.
+
+This trait is implemented on all types that dereference to a slice, such as
+`Vec<T>`, `Box<[T]>`, and `&[T]`. Its methods assert a postcondition of the
+operation that produced the slice, such as a sort or a deduplication, and
+panic with the first element that breaks it. They are intended for tests and
+debugging, where a failed check should stop the program right at the
+expression that produced the bad data.
+**/
+pub trait TapSliceAssert<T>
+where
+	Self: Sized + Deref<Target = [T]>,
+{
+	/// Asserts that the slice is sorted in ascending order.
+	///
+	/// Equal neighbors are permitted. The panic message names the first pair
+	/// of neighbors that is out of order.
+	///
+	/// # Examples
+	///
+	/// ```rust,should_panic
+	/// use tap::tap::{Tap, TapSliceAssert};
+	///
+	/// let ranks = vec![1, 2, 2, 5]
+	///   .tap_assert_sorted()
+	///   .tap_mut(|v| v.push(3))
+	///   .tap_assert_sorted();
+	/// ```
+	#[inline(always)]
+	#[track_caller]
+	fn tap_assert_sorted(self) -> Self
+	where
+		T: Ord + Debug,
+	{
+		self.tap_assert_sorted_by(Ord::cmp)
+	}
+
+	/// Asserts that the slice is sorted according to a comparator.
+	///
+	/// This is the invariant that `<[T]>::sort_by` with the same comparator
+	/// establishes: no element compares `Greater` than the element after it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapSliceAssert;
+	///
+	/// let mut by_len = vec!["ccc", "a", "bb"];
+	/// by_len.sort_by(|a, b| a.len().cmp(&b.len()).reverse());
+	/// let by_len =
+	///   by_len.tap_assert_sorted_by(|a, b| b.len().cmp(&a.len()));
+	/// ```
+	#[inline(always)]
+	#[track_caller]
+	fn tap_assert_sorted_by(
+		self,
+		mut compare: impl FnMut(&T, &T) -> cmp::Ordering,
+	) -> Self
+	where
+		T: Debug,
+	{
+		let mut pairs = self.windows(2).enumerate();
+		if let Some((idx, pair)) =
+			pairs.find(|(_, p)| compare(&p[0], &p[1]) == cmp::Ordering::Greater)
+		{
+			panic!(
+				"slice is not sorted: element {} ({:?}) is greater than element \
+				 {} ({:?})",
+				idx,
+				pair[0],
+				idx + 1,
+				pair[1],
+			);
+		}
+		self
+	}
+
+	/// Calls `.tap_assert_sorted()` only in debug builds, and is erased in
+	/// release builds.
+	#[inline(always)]
+	#[track_caller]
+	fn tap_assert_sorted_dbg(self) -> Self
+	where
+		T: Ord + Debug,
+	{
+		if cfg!(debug_assertions) {
+			return self.tap_assert_sorted();
+		}
+		self
+	}
+
+	/// Asserts that the slice contains no duplicate elements.
+	///
+	/// The panic message names the first element that repeats an earlier one.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapSliceAssert;
+	///
+	/// let mut ids = vec![3, 1, 3, 2];
+	/// ids.sort();
+	/// ids.dedup();
+	/// let ids = ids.tap_assert_unique();
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[track_caller]
+	fn tap_assert_unique(self) -> Self
+	where
+		T: Eq + Hash + Debug,
+	{
+		let mut seen = HashSet::with_capacity(self.len());
+		if let Some((idx, dup)) = self
+			.iter()
+			.enumerate()
+			.find(|(_, item)| !seen.insert(*item))
+		{
+			panic!(
+				"slice is not unique: element {} ({:?}) is repeated",
+				idx, dup
+			);
+		}
+		self
+	}
+
+	/// Asserts that no two elements of the slice share a key.
+	///
+	/// The panic message names the first element whose key repeats an earlier
+	/// element’s key, and the key itself.
+	///
+	/// # Examples
+	///
+	/// ```rust,should_panic
+	/// use tap::tap::TapSliceAssert;
+	///
+	/// let users = vec![("ann", 1), ("bob", 2), ("ann", 3)]
+	///   .tap_assert_unique_by_key(|(name, _)| *name);
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline(always)]
+	#[track_caller]
+	fn tap_assert_unique_by_key<K>(self, mut key: impl FnMut(&T) -> K) -> Self
+	where
+		K: Eq + Hash + Debug,
+		T: Debug,
+	{
+		let mut seen = HashSet::with_capacity(self.len());
+		for (idx, item) in self.iter().enumerate() {
+			let k = key(item);
+			if seen.contains(&k) {
+				panic!(
+					"slice is not unique: element {} ({:?}) repeats the key {:?}",
+					idx, item, k,
+				);
+			}
+			seen.insert(k);
+		}
+		self
+	}
+}
+
+impl<S, T> TapSliceAssert<T> for S where S: Deref<Target = [T]> {}
+
+/** Point-free access to the text of owned strings.
+
+This trait is implemented on all types that mutably dereference to `str`, such
+as `String`, `Box<str>`, and `&mut str`. It is the string counterpart of
+[`TapSlice`]: where `String::tap_mut` exposes the `String` itself, these taps
+expose the `str` it dereferences to, so that in-place `str` methods can be
+passed by path without a turbofish or a manual dereference.
+
+[`TapSlice`]: trait.TapSlice.html
+**/
+pub trait TapStr
+where
+	Self: Sized + DerefMut<Target = str>,
+{
+	/// Immutable access to the text of a string.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapStr;
+	///
+	/// let greeting = String::from("hello")
+	///   .tap_str(|s| assert!(s.is_ascii()));
+	/// ```
+	#[inline(always)]
+	fn tap_str(self, func: impl FnOnce(&str)) -> Self {
+		func(&self);
+		self
+	}
+
+	/// Mutable access to the text of a string.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapStr;
+	///
+	/// let shout = String::from("hello")
+	///   .tap_str_mut(str::make_ascii_uppercase);
+	/// assert_eq!(shout, "HELLO");
+	/// ```
+	#[inline(always)]
+	fn tap_str_mut(mut self, func: impl FnOnce(&mut str)) -> Self {
+		func(&mut self);
+		self
+	}
+}
+
+impl<S> TapStr for S where S: DerefMut<Target = str> {}
+
+/** Point-free inspection through a reference, of the value it refers to.
+
+[`Tap`] is implemented for every `Sized` type, and references are `Sized`, so a
+`&[T]` or `&str` binding can already be tapped. The effect function then
+receives a reference to the *reference*, `&&[T]`, which generic helpers written
+against `&[T]` do not accept without an explicit dereference. This trait is
+implemented on `&T` and `&mut T`, including for unsized `T`, and hands the
+effect function the referent directly.
+
+Its method has a name distinct from every `Tap` method, so importing both
+traits never makes a call ambiguous. Note that calling it on an owned value
+auto-borrows that value, and so returns the borrow rather than the value; use
+[`Tap::tap`] when you have ownership.
+
+[`Tap`]: trait.Tap.html
+[`Tap::tap`]: trait.Tap.html#method.tap
+**/
+pub trait TapRef
+where
+	Self: Sized,
+{
+	/// The type that this reference refers to.
+	type Referent: ?Sized;
+
+	/// Immutable access to the value behind a reference.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapRef;
+	///
+	/// fn head(bytes: &[u8]) -> &[u8] {
+	///   bytes.tap_ref_self(|b| assert!(!b.is_empty()))
+	/// }
+	///
+	/// assert_eq!(head(b"tap"), b"tap");
+	/// ```
+	fn tap_ref_self(self, func: impl FnOnce(&Self::Referent)) -> Self;
+}
+
+impl<T> TapRef for &T
+where
+	T: ?Sized,
+{
+	type Referent = T;
+
+	#[inline(always)]
+	fn tap_ref_self(self, func: impl FnOnce(&T)) -> Self {
+		func(self);
+		self
+	}
+}
+
+impl<T> TapRef for &mut T
+where
+	T: ?Sized,
+{
+	type Referent = T;
+
+	#[inline(always)]
+	fn tap_ref_self(self, func: impl FnOnce(&T)) -> Self {
+		func(self);
+		self
+	}
+}
+
+/// Renders bytes as space-separated, two-digit, lowercase hexadecimal.
+#[cfg(all(feature = "alloc", feature = "bytemuck"))]
+fn hex_dump(bytes: &[u8]) -> liballoc::string::String {
+	HexBytes(bytes).to_string()
+}
+
+/// Displays bytes as space-separated, two-digit, lowercase hexadecimal.
+#[cfg(feature = "bytemuck")]
+struct HexBytes<'a>(&'a [u8]);
+
+#[cfg(feature = "bytemuck")]
+impl Display for HexBytes<'_> {
+	fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+		for (idx, byte) in self.0.iter().enumerate() {
+			if idx > 0 {
+				fmt.write_str(" ")?;
+			}
+			write!(fmt, "{:02x}", byte)?;
+		}
+		Ok(())
+	}
+}
+
+/// Displays a row of `^^` markers under the bytes where two patterns differ.
+///
+/// The markers line up with two [`HexBytes`] rows printed above them. Bytes
+/// present in only one of the patterns count as differing.
+#[cfg(feature = "bytemuck")]
+struct HexDiff<'a>(&'a [u8], &'a [u8]);
+
+#[cfg(feature = "bytemuck")]
+impl Display for HexDiff<'_> {
+	fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+		let len = cmp::max(self.0.len(), self.1.len());
+		let marks = (0..len).map(|idx| self.0.get(idx) != self.1.get(idx));
+		let last = marks.clone().rposition(|differs| differs);
+		for (idx, differs) in marks.enumerate().take(last.map_or(0, |l| l + 1)) {
+			if idx > 0 {
+				fmt.write_str(" ")?;
+			}
+			fmt.write_str(if differs { "^^" } else { "  " })?;
+		}
+		Ok(())
+	}
+}
+
+/** Fallible tapping, conditional on the optional success of an expression.
+
+This trait is intended for use on types that express the concept of “fallible
+presence”, primarily the [`Result`] monad. It provides taps that inspect the
+container to determine if the effect function should execute or not.
+
+> Note: This trait would ideally be implemented as a blanket over all
+> [`std::ops::Try`] implementors. When `Try` stabilizes, this crate can be
+> updated to do so.
+
+[`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+[`std::ops::Try`]: https://doc.rust-lang.org/std/ops/trait.Try.html
+**/
+pub trait TapFallible
+where
+	Self: Sized + Try,
+{
+	/// Immutably accesses an interior success value.
+	///
+	/// This function is identical to [`Tap::tap`], except that it is required
+	/// to check the implementing container for value success before running.
+	/// Implementors must not run the effect function if the container is marked
+	/// as being a failure.
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_continue(self, func: impl FnOnce(&Self::Output)) -> Self;
+
+	/// Mutably accesses an interior success value.
+	///
+	/// This function is identical to [`Tap::tap_mut`], except that it is
+	/// required to check the implementing container for value success before
+	/// running. Implementors must not run the effect function if the container
+	/// is marked as being a failure.
+	///
+	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_continue_mut(self, func: impl FnOnce(&mut Self::Output)) -> Self;
+
+	/// Immutably accesses an interior failure value.
+	///
+	/// This function is identical to [`Tap::tap`], except that it is required
+	/// to check the implementing container for value failure before running.
+	/// Implementors must not run the effect function if the container is marked
+	/// as being a success.
+	///
+	/// [`Tap::tap`]: trait.Tap.html#method.tap
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_break(self, func: impl FnOnce(&Self::Residual)) -> Self;
+
+	/// Mutably accesses an interior failure value.
+	///
+	/// This function is identical to [`Tap::tap_mut`], except that it is
+	/// required to check the implementing container for value failure before
+	/// running. Implementors must not run the effect function if the container
+	/// is marked as being a success.
+	///
+	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_break_mut(self, func: impl FnOnce(&mut Self::Residual)) -> Self;
+
+	/// Calls `.tap_continue()` only in debug builds, and is erased in release builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_continue_dbg(self, func: impl FnOnce(&Self::Output)) -> Self {
+		if cfg!(debug_assertions) {
+			self.tap_continue(func)
+		} else {
+			self
+		}
+	}
+
+	/// Calls `.tap_continue_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_continue_mut_dbg(self, func: impl FnOnce(&mut Self::Output)) -> Self {
+		if cfg!(debug_assertions) {
+			self.tap_continue_mut(func)
+		} else {
+			self
+		}
+	}
+
+	/// Calls `.tap_break()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_break_dbg(self, func: impl FnOnce(&Self::Residual)) -> Self {
+		if cfg!(debug_assertions) {
+			self.tap_break(func)
+		} else {
+			self
+		}
+	}
+
+	/// Calls `.tap_break_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_break_mut_dbg(self, func: impl FnOnce(&mut Self::Residual)) -> Self {
+		if cfg!(debug_assertions) {
+			self.tap_break_mut(func)
+		} else {
+			self
+		}
+	}
+}
+
+impl<T> TapFallible for T
+where
+	T: Try,
+{
+	#[inline(always)]
+	#[track_caller]
+	fn tap_continue(self, func: impl FnOnce(&Self::Output)) -> Self {
+		match self.branch() {
+			ControlFlow::Continue(output) => {
+				func(&output);
+				Self::from_output(output)
+			}
+			ControlFlow::Break(residual) => Self::from_residual(residual),
+		}
+	}
+
+	#[inline(always)]
+	#[track_caller]
+	fn tap_continue_mut(self, func: impl FnOnce(&mut Self::Output)) -> Self {
+		match self.branch() {
+			ControlFlow::Continue(mut output) => {
+				func(&mut output);
+				Self::from_output(output)
+			}
+			ControlFlow::Break(residual) => Self::from_residual(residual),
+		}
+	}
+
+	#[inline(always)]
+	#[track_caller]
+	fn tap_break(self, func: impl FnOnce(&Self::Residual)) -> Self {
+		match self.branch() {
+			ControlFlow::Continue(output) => Self::from_output(output),
+			ControlFlow::Break(residual) => {
+				func(&residual);
+				Self::from_residual(residual)
+			}
+		}
+	}
+
+	#[inline(always)]
+	#[track_caller]
+	fn tap_break_mut(self, func: impl FnOnce(&mut Self::Residual)) -> Self {
+		match self.branch() {
+			ControlFlow::Continue(output) => Self::from_output(output),
+			ControlFlow::Break(mut residual) => {
+				func(&mut residual);
+				Self::from_residual(residual)
+			}
+		}
+	}
+}
+
+/** Reference-count inspection of weak pointers.
+
+This trait is implemented on the weak handles of both `Rc` and `Arc`. It
+provides taps that look through the weak handle at the shared allocation it
+refers to, without consuming the handle or permanently keeping the allocation
+alive.
+
+This is primarily a debugging aid for tracking down weak references that are
+unexpectedly dangling, or strong references that are unexpectedly kept alive.
+**/
+#[cfg(feature = "alloc")]
+pub trait TapWeak
+where
+	Self: Sized,
+{
+	/// The strong pointer type that this weak pointer upgrades into.
+	type Strong;
+
+	/// Immutable access to a temporary upgrade of a weak pointer.
+	///
+	/// This function attempts to upgrade the weak pointer, and passes the
+	/// result of the attempt to the effect function. `None` indicates that
+	/// the allocation has already been destroyed. The temporary strong pointer
+	/// is dropped as soon as the effect function returns, so the tap does not
+	/// extend the lifetime of the allocation.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::Arc;
+	/// use tap::tap::TapWeak;
+	///
+	/// let strong = Arc::new(5);
+	/// let weak = Arc::downgrade(&strong)
+	///   .tap_upgrade_inspect(|arc| assert_eq!(arc.map(|a| **a), Some(5)));
+	///
+	/// drop(strong);
+	/// weak.tap_upgrade_inspect(|arc| assert!(arc.is_none()));
+	/// ```
+	fn tap_upgrade_inspect(
+		self,
+		func: impl FnOnce(Option<&Self::Strong>),
+	) -> Self;
+
+	/// Immutable access to the strong count of a weak pointer.
+	///
+	/// This function passes the number of strong pointers currently keeping
+	/// the allocation alive to the effect function. A count of zero indicates
+	/// that the weak pointer is dangling.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::rc::Rc;
+	/// use tap::tap::TapWeak;
+	///
+	/// let strong = Rc::new("hello");
+	/// let _also = Rc::clone(&strong);
+	/// Rc::downgrade(&strong)
+	///   .tap_strong_count_weak(|count| assert_eq!(count, 2));
+	/// ```
+	fn tap_strong_count_weak(self, func: impl FnOnce(usize)) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapWeak for sync::Weak<T>
+where
+	T: ?Sized,
+{
+	type Strong = sync::Arc<T>;
+
+	#[inline(always)]
+	fn tap_upgrade_inspect(
+		self,
+		func: impl FnOnce(Option<&Self::Strong>),
+	) -> Self {
+		func(self.upgrade().as_ref());
+		self
+	}
+
+	#[inline(always)]
+	fn tap_strong_count_weak(self, func: impl FnOnce(usize)) -> Self {
+		func(self.strong_count());
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapWeak for rc::Weak<T>
+where
+	T: ?Sized,
+{
+	type Strong = rc::Rc<T>;
+
+	#[inline(always)]
+	fn tap_upgrade_inspect(
+		self,
+		func: impl FnOnce(Option<&Self::Strong>),
+	) -> Self {
+		func(self.upgrade().as_ref());
+		self
+	}
+
+	#[inline(always)]
+	fn tap_strong_count_weak(self, func: impl FnOnce(usize)) -> Self {
+		func(self.strong_count());
+		self
+	}
+}
+
+/** Point-free access to the value guarded by a mutex.
+
+This trait is implemented on `Mutex<T>` and on `&Mutex<T>`. Its taps acquire
+the lock, run the effect function on the guarded value, and release the lock
+before returning the mutex (or the reference to it) unchanged. An owned mutex
+needs no locking at all, as ownership already proves exclusive access, so it is
+reached through `Mutex::get_mut` instead.
+
+# Poisoning
+
+A mutex is poisoned when a thread panics while holding its lock. These taps
+treat a poisoned mutex as usable: they recover the guard from the
+`PoisonError` and run the effect function on the value anyway. The poison flag
+itself is left untouched, so later calls to `.lock()` still observe it. If your
+code must not look at possibly-inconsistent data, check `.is_poisoned()` first.
+**/
+#[cfg(feature = "std")]
+pub trait TapLock
+where
+	Self: Sized,
+{
+	/// The type of the value guarded by the mutex.
+	type Inner: ?Sized;
+
+	/// Immutable access to the value guarded by a mutex.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::Mutex;
+	/// use tap::tap::TapLock;
+	///
+	/// let queue = Mutex::new(vec![1, 2, 3])
+	///   .tap_lock(|q| assert_eq!(q.len(), 3));
+	/// ```
+	fn tap_lock(self, func: impl FnOnce(&Self::Inner)) -> Self;
+
+	/// Mutable access to the value guarded by a mutex.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::Mutex;
+	/// use tap::tap::TapLock;
+	///
+	/// let queue = Mutex::new(vec![1, 2, 3]);
+	/// (&queue).tap_lock_mut(|q| q.push(4));
+	/// assert_eq!(*queue.lock().unwrap(), [1, 2, 3, 4]);
+	/// ```
+	fn tap_lock_mut(self, func: impl FnOnce(&mut Self::Inner)) -> Self;
+}
+
+#[cfg(feature = "std")]
+impl<T> TapLock for Mutex<T> {
+	type Inner = T;
+
+	#[inline(always)]
+	fn tap_lock(mut self, func: impl FnOnce(&T)) -> Self {
+		func(self.get_mut().unwrap_or_else(PoisonError::into_inner));
+		self
+	}
+
+	#[inline(always)]
+	fn tap_lock_mut(mut self, func: impl FnOnce(&mut T)) -> Self {
+		func(self.get_mut().unwrap_or_else(PoisonError::into_inner));
+		self
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T> TapLock for &Mutex<T>
+where
+	T: ?Sized,
+{
+	type Inner = T;
+
+	#[inline(always)]
+	fn tap_lock(self, func: impl FnOnce(&T)) -> Self {
+		func(&self.lock().unwrap_or_else(PoisonError::into_inner));
+		self
+	}
+
+	#[inline(always)]
+	fn tap_lock_mut(self, func: impl FnOnce(&mut T)) -> Self {
+		func(&mut self.lock().unwrap_or_else(PoisonError::into_inner));
+		self
+	}
+}
+
+/** Uniform access to the value inside an optional container.
+
+This trait is implemented on `Option<T>` and `Result<T, E>`, and presents “the
+value, if present” through a single interface. It allows generic code to
+instrument either container without knowing which one it has been given.
+
+Unlike [`TapFallible`], which can be implemented by any `Try` type and reports
+its success value through `Try::Output`, this trait is deliberately minimal, and
+only requires a way to borrow the inner value.
+
+[`TapFallible`]: trait.TapFallible.html
+**/
+pub trait HasInner
+where
+	Self: Sized,
+{
+	/// The type of the value held inside the container.
+	type Inner;
+
+	/// Borrows the inner value, if it is present.
+	fn as_inner(&self) -> Option<&Self::Inner>;
+
+	/// Mutably borrows the inner value, if it is present.
+	fn as_inner_mut(&mut self) -> Option<&mut Self::Inner>;
+
+	/// Immutable access to the inner value, if it is present.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::HasInner;
+	///
+	/// fn log_inner<C: HasInner>(c: C) -> C
+	/// where C::Inner: std::fmt::Debug {
+	///   c.tap_inner(|v| println!("{:?}", v))
+	/// }
+	///
+	/// let _ = log_inner(Some(5));
+	/// let _ = log_inner(Ok::<_, ()>("text"));
+	/// ```
+	#[inline(always)]
+	fn tap_inner(self, func: impl FnOnce(&Self::Inner)) -> Self {
+		if let Some(inner) = self.as_inner() {
+			func(inner);
+		}
+		self
+	}
+
+	/// Mutable access to the inner value, if it is present.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::HasInner;
+	///
+	/// assert_eq!(Some(5).tap_inner_mut(|v| *v += 1), Some(6));
+	/// assert_eq!(Err::<i32, _>(5).tap_inner_mut(|v| *v += 1), Err(5));
+	/// ```
+	#[inline(always)]
+	fn tap_inner_mut(mut self, func: impl FnOnce(&mut Self::Inner)) -> Self {
+		if let Some(inner) = self.as_inner_mut() {
+			func(inner);
+		}
+		self
+	}
+
+	/// Calls `.tap_inner()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	fn tap_inner_dbg(self, func: impl FnOnce(&Self::Inner)) -> Self {
+		if cfg!(debug_assertions) {
+			self.tap_inner(func)
+		} else {
+			self
+		}
+	}
+
+	/// Calls `.tap_inner_mut()` only in debug builds, and is erased in release
+	/// builds.
+	#[inline(always)]
+	fn tap_inner_mut_dbg(self, func: impl FnOnce(&mut Self::Inner)) -> Self {
+		if cfg!(debug_assertions) {
+			self.tap_inner_mut(func)
+		} else {
+			self
+		}
+	}
+}
+
+impl<T> HasInner for Option<T> {
+	type Inner = T;
+
+	#[inline(always)]
+	fn as_inner(&self) -> Option<&T> {
+		self.as_ref()
+	}
+
+	#[inline(always)]
+	fn as_inner_mut(&mut self) -> Option<&mut T> {
+		self.as_mut()
+	}
+}
+
+impl<T, E> HasInner for Result<T, E> {
+	type Inner = T;
+
+	#[inline(always)]
+	fn as_inner(&self) -> Option<&T> {
+		self.as_ref().ok()
+	}
+
+	#[inline(always)]
+	fn as_inner_mut(&mut self) -> Option<&mut T> {
+		self.as_mut().ok()
+	}
+}
+
+/** Uniform access to the length of a collection.
+
+The standard collections each have an inherent `len` method, but no trait
+unites them. This trait does, so that [`Tap::tap_deref_len`] can accept any of
+them behind a smart pointer.
+
+It is not implemented on arrays. Arrays have no inherent `len`, and borrow the
+one on slices, so an implementation here would take priority over the slice
+method wherever this trait is in scope, including in `const` code.
+
+[`Tap::tap_deref_len`]: trait.Tap.html#method.tap_deref_len
+**/
+pub trait HasLen {
+	/// Counts the elements of the collection.
+	///
+	/// For `str` and `String`, this counts bytes, as their inherent `len`
+	/// methods do.
+	fn len(&self) -> usize;
+
+	/// Tests whether the collection has no elements.
+	#[inline]
+	fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl<T> HasLen for [T] {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		<[T]>::len(self)
+	}
+}
+
+impl HasLen for str {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		str::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HasLen for Vec<T> {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		Vec::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HasLen for VecDeque<T> {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		VecDeque::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl HasLen for String {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		String::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> HasLen for BTreeMap<K, V> {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		BTreeMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HasLen for BTreeSet<T> {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		BTreeSet::len(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> HasLen for HashMap<K, V, S> {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		HashMap::len(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T, S> HasLen for HashSet<T, S> {
+	#[inline(always)]
+	fn len(&self) -> usize {
+		HashSet::len(self)
+	}
+}
+
+/** Inspection through nested containers.
+
+Taps such as [`Tap::tap_deref`] peel exactly one layer of indirection. This
+trait peels a fixed combination of layers at once, to reach the innermost value
+of a commonly composed type. Each implementation peels as follows:
+
+| Container          | `.tap_flatten()` sees          | Effect runs when            |
+|--------------------|--------------------------------|-----------------------------|
+| `Option<Box<T>>`   | `&T`, through `as_deref`       | the option is `Some`        |
+| `Box<Option<T>>`   | `&T`, through `as_ref`         | the option is `Some`        |
+| `Rc<RefCell<T>>`   | `&T`, through `RefCell::borrow`| always                      |
+
+The mutable forms peel the same layers with `as_deref_mut`, `as_mut`, and
+`RefCell::borrow_mut`. The `RefCell` forms panic if the cell is already borrowed
+incompatibly, exactly as the `RefCell` methods do; the borrow is released before
+the tap returns.
+
+[`Tap::tap_deref`]: trait.Tap.html#method.tap_deref
+**/
+#[cfg(feature = "alloc")]
+pub trait TapFlatten
+where
+	Self: Sized,
+{
+	/// The innermost value reached by peeling the container.
+	type Innermost: ?Sized;
+
+	/// Immutable access to the innermost value, if present.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::{cell::RefCell, rc::Rc};
+	/// use tap::tap::TapFlatten;
+	///
+	/// let shared = Rc::new(RefCell::new(vec![1, 2]))
+	///   .tap_flatten_mut(|v| v.push(3))
+	///   .tap_flatten(|v| assert_eq!(v.len(), 3));
+	/// ```
+	fn tap_flatten(self, func: impl FnOnce(&Self::Innermost)) -> Self;
+
+	/// Mutable access to the innermost value, if present.
+	fn tap_flatten_mut(self, func: impl FnOnce(&mut Self::Innermost)) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapFlatten for Option<Box<T>>
+where
+	T: ?Sized,
+{
+	type Innermost = T;
+
+	#[inline(always)]
+	fn tap_flatten(self, func: impl FnOnce(&T)) -> Self {
+		if let Some(val) = self.as_deref() {
+			func(val);
+		}
+		self
+	}
+
+	#[inline(always)]
+	fn tap_flatten_mut(mut self, func: impl FnOnce(&mut T)) -> Self {
+		if let Some(val) = self.as_deref_mut() {
+			func(val);
+		}
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapFlatten for Box<Option<T>> {
+	type Innermost = T;
+
+	#[inline(always)]
+	fn tap_flatten(self, func: impl FnOnce(&T)) -> Self {
+		if let Some(val) = self.as_ref() {
+			func(val);
+		}
+		self
+	}
+
+	#[inline(always)]
+	fn tap_flatten_mut(mut self, func: impl FnOnce(&mut T)) -> Self {
+		if let Some(val) = self.as_mut() {
+			func(val);
+		}
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapFlatten for rc::Rc<core::cell::RefCell<T>>
+where
+	T: ?Sized,
+{
+	type Innermost = T;
+
+	#[inline(always)]
+	fn tap_flatten(self, func: impl FnOnce(&T)) -> Self {
+		func(&core::cell::RefCell::borrow(&self));
+		self
+	}
+
+	#[inline(always)]
+	fn tap_flatten_mut(self, func: impl FnOnce(&mut T)) -> Self {
+		func(&mut core::cell::RefCell::borrow_mut(&self));
+		self
+	}
+}
+
+/** Inspection of optional boxed values.
+
+This is the most common case of [`TapFlatten`], given its own name so that the
+container shape is visible at the call site.
+
+[`TapFlatten`]: trait.TapFlatten.html
+**/
+#[cfg(feature = "alloc")]
+pub trait TapOptBox<T>
+where
+	T: ?Sized,
+{
+	/// Immutable access to the boxed value, if present.
+	///
+	/// The effect function runs only on `Some`, and receives a borrow of the
+	/// value inside the box rather than of the box itself.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapOptBox;
+	///
+	/// let mut seen = None;
+	/// let node = Some(Box::new(5)).tap_opt_box(|n| seen = Some(*n));
+	/// assert_eq!(seen, Some(5));
+	/// let none = None::<Box<i32>>.tap_opt_box(|_| unreachable!());
+	/// ```
+	fn tap_opt_box(self, func: impl FnOnce(&T)) -> Self;
+
+	/// Mutable access to the boxed value, if present.
+	fn tap_opt_box_mut(self, func: impl FnOnce(&mut T)) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapOptBox<T> for Option<Box<T>>
+where
+	T: ?Sized,
+{
+	#[inline(always)]
+	fn tap_opt_box(self, func: impl FnOnce(&T)) -> Self {
+		self.tap_flatten(func)
+	}
+
+	#[inline(always)]
+	fn tap_opt_box_mut(self, func: impl FnOnce(&mut T)) -> Self {
+		self.tap_flatten_mut(func)
+	}
+}
+
+/** Tapping of the payloads inside a `Result`.
+
+[`TapFallible`] works on every `Try` implementor, and so can only present the
+failure arm as the opaque `Try::Residual` type. This trait is implemented only
+on `Result`, and presents the `Ok` and `Err` payloads directly.
+
+[`TapFallible`]: trait.TapFallible.html
+**/
+pub trait TapResult<T, E>
+where
+	Self: Sized,
+{
+	/// Counts the value if it is `Ok`.
+	///
+	/// This function increments `counter` by one, with relaxed ordering, only
+	/// on the `Ok` arm.
+	#[cfg(target_has_atomic = "ptr")]
+	fn tap_ok_count(self, counter: &AtomicUsize) -> Self;
+
+	/// Counts the value if it is `Err`.
+	///
+	/// This function increments `counter` by one, with relaxed ordering, only
+	/// on the `Err` arm. Together with [`TapResult::tap_ok_count`], it can
+	/// track the error rate of a fallible operation inline.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::sync::atomic::AtomicUsize;
+	/// use tap::tap::TapResult;
+	///
+	/// let (oks, errs) = (AtomicUsize::new(0), AtomicUsize::new(0));
+	/// for s in &["1", "x", "3"] {
+	///   let _ = s.parse::<i32>()
+	///     .tap_ok_count(&oks)
+	///     .tap_err_count(&errs);
+	/// }
+	/// assert_eq!(oks.into_inner(), 2);
+	/// assert_eq!(errs.into_inner(), 1);
+	/// ```
+	///
+	/// [`TapResult::tap_ok_count`]: trait.TapResult.html#method.tap_ok_count
+	#[cfg(target_has_atomic = "ptr")]
+	fn tap_err_count(self, counter: &AtomicUsize) -> Self;
+
+	/// Immutable access to the `Display` rendering of an `Err` payload.
+	///
+	/// On the `Err` arm, this function formats the error once with its
+	/// `Display` implementation, and passes the resulting text to the effect
+	/// function. This covers the common case of logging an error message
+	/// without having to format inside every effect function. The `Ok` arm
+	/// does not allocate.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapResult;
+	///
+	/// let mut msg = String::new();
+	/// let res = "x".parse::<i32>()
+	///   .tap_err_display(|e| msg.push_str(e));
+	/// assert_eq!(msg, "invalid digit found in string");
+	/// ```
+	#[cfg(feature = "alloc")]
+	fn tap_err_display(self, func: impl FnOnce(&str)) -> Self
+	where
+		E: Display;
+
+	/// Immutable access to the `Debug` rendering of an `Err` payload.
+	///
+	/// This function is identical to [`TapResult::tap_err_display`], except
+	/// that it formats the error with its `Debug` implementation.
+	///
+	/// [`TapResult::tap_err_display`]: trait.TapResult.html#method.tap_err_display
+	#[cfg(feature = "alloc")]
+	fn tap_err_debug(self, func: impl FnOnce(&str)) -> Self
+	where
+		E: Debug;
+
+	/// Increments a `metrics` counter if the value is `Ok`.
+	///
+	/// This emits `metrics::counter!(key).increment(1)` only on the `Ok` arm.
+	/// It is built against version 0.24 of the [`metrics`] facade, and the
+	/// count goes to whichever recorder the program has installed with that
+	/// version.
+	///
+	/// [`metrics`]: https://docs.rs/metrics/0.24
+	#[cfg(feature = "metrics")]
+	fn tap_ok_metric(self, key: &'static str) -> Self;
+
+	/// Increments a `metrics` counter if the value is `Err`.
+	///
+	/// This is the `Err` counterpart of [`TapResult::tap_ok_metric`], and the
+	/// [`metrics`] counterpart of [`TapResult::tap_err_count`]: it emits to a
+	/// named counter in the installed recorder, rather than to an atomic owned
+	/// by the caller.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # extern crate metrics;
+	/// # extern crate tap;
+	/// use tap::tap::TapResult;
+	///
+	/// fn handle(req: &str) -> Result<u16, String> {
+	///   req.parse::<u16>().map_err(|e| e.to_string())
+	/// }
+	///
+	/// let status = handle("200").tap_err_metric("request_errors");
+	/// ```
+	///
+	/// [`TapResult::tap_err_count`]: trait.TapResult.html#method.tap_err_count
+	/// [`TapResult::tap_ok_metric`]: trait.TapResult.html#method.tap_ok_metric
+	/// [`metrics`]: https://docs.rs/metrics/0.24
+	#[cfg(feature = "metrics")]
+	fn tap_err_metric(self, key: &'static str) -> Self;
+
+	/// Immutable access to an `Err` payload, with a backtrace of the tap.
+	///
+	/// On the `Err` arm, this function captures a [`Backtrace`] at the tap
+	/// point and passes it to the effect function together with the error, so
+	/// that the origin of an error can be logged without storing a backtrace
+	/// in the error type. The `Ok` arm captures nothing, and so costs nothing.
+	///
+	/// The capture uses `Backtrace::capture`, and therefore respects the
+	/// `RUST_BACKTRACE` and `RUST_LIB_BACKTRACE` environment variables: when
+	/// they disable backtraces, the effect function still runs, but receives
+	/// a backtrace whose status is `Disabled`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapResult;
+	///
+	/// let mut log = String::new();
+	/// let port = "http".parse::<u16>().tap_err_backtrace(|err, trace| {
+	///   log = format!("{} at\n{}", err, trace);
+	/// });
+	/// assert!(log.starts_with("invalid digit found in string at"));
+	/// ```
+	///
+	/// [`Backtrace`]: https://doc.rust-lang.org/std/backtrace/struct.Backtrace.html
+	#[cfg(feature = "backtrace")]
+	fn tap_err_backtrace(self, func: impl FnOnce(&E, &Backtrace)) -> Self;
+}
+
+impl<T, E> TapResult<T, E> for Result<T, E> {
+	#[cfg(target_has_atomic = "ptr")]
+	#[inline(always)]
+	fn tap_ok_count(self, counter: &AtomicUsize) -> Self {
+		if self.is_ok() {
+			counter.fetch_add(1, Ordering::Relaxed);
+		}
+		self
+	}
+
+	#[cfg(target_has_atomic = "ptr")]
+	#[inline(always)]
+	fn tap_err_count(self, counter: &AtomicUsize) -> Self {
+		if self.is_err() {
+			counter.fetch_add(1, Ordering::Relaxed);
+		}
+		self
+	}
+
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	fn tap_err_display(self, func: impl FnOnce(&str)) -> Self
+	where
+		E: Display,
+	{
+		if let Err(err) = &self {
+			func(&err.to_string());
+		}
+		self
+	}
+
+	#[cfg(feature = "alloc")]
+	#[inline(always)]
+	fn tap_err_debug(self, func: impl FnOnce(&str)) -> Self
+	where
+		E: Debug,
+	{
+		if let Err(err) = &self {
+			func(&format!("{:?}", err));
+		}
+		self
+	}
+
+	#[cfg(feature = "metrics")]
+	#[inline(always)]
+	fn tap_ok_metric(self, key: &'static str) -> Self {
+		if self.is_ok() {
+			metrics::counter!(key).increment(1);
+		}
+		self
+	}
+
+	#[cfg(feature = "metrics")]
+	#[inline(always)]
+	fn tap_err_metric(self, key: &'static str) -> Self {
+		if self.is_err() {
+			metrics::counter!(key).increment(1);
+		}
+		self
+	}
+
+	#[cfg(feature = "backtrace")]
+	#[inline(always)]
+	fn tap_err_backtrace(self, func: impl FnOnce(&E, &Backtrace)) -> Self {
+		if let Err(err) = &self {
+			func(err, &Backtrace::capture());
+		}
+		self
+	}
+}
+
+/** Tapping of the error inside an `anyhow::Result`.
+
+[`TapFallible::tap_break`] works on `anyhow::Result<T>`, but presents the
+failure as the `Try::Residual` type, `Result<Infallible, anyhow::Error>`. This
+trait is implemented only on `anyhow::Result<T>`, and presents the
+[`anyhow::Error`] directly, so that its methods, such as `chain` and
+`root_cause`, are available to the effect function.
+
+This trait requires the `anyhow` feature, which adds a dependency on the
+`anyhow` crate, and does not exist without it.
+
+[`TapFallible::tap_break`]: trait.TapFallible.html#method.tap_break
+[`anyhow::Error`]: https://docs.rs/anyhow/1/anyhow/struct.Error.html
+**/
+#[cfg(feature = "anyhow")]
+pub trait TapAnyhow<T>
+where
+	Self: Sized,
+{
+	/// Immutable access to the error, including its chain of causes, on the
+	/// `Err` arm.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # extern crate anyhow;
+	/// # extern crate tap;
+	/// use anyhow::Context;
+	/// use tap::tap::TapAnyhow;
+	///
+	/// let mut causes = vec![];
+	/// let port = "http"
+	///   .parse::<u16>()
+	///   .context("reading the port")
+	///   .tap_anyhow_err(|e| causes.extend(e.chain().map(|c| c.to_string())));
+	/// assert_eq!(causes, ["reading the port", "invalid digit found in string"]);
+	/// ```
+	fn tap_anyhow_err(self, func: impl FnOnce(&anyhow::Error)) -> Self;
+
+	/// Attaches context to the error on the `Err` arm, then passes the error
+	/// to an effect function.
+	///
+	/// This is `.context(context)` followed by [`.tap_anyhow_err(func)`], so
+	/// the effect function sees the error with the new context outermost.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # extern crate anyhow;
+	/// # extern crate tap;
+	/// use tap::tap::TapAnyhow;
+	///
+	/// let mut seen = String::new();
+	/// let res = anyhow::Result::<()>::Err(anyhow::anyhow!("disk full"))
+	///   .tap_err_context("saving the report", |e| seen = format!("{:#}", e));
+	/// assert_eq!(seen, "saving the report: disk full");
+	/// assert_eq!(res.unwrap_err().to_string(), "saving the report");
+	/// ```
+	///
+	/// [`.tap_anyhow_err(func)`]: trait.TapAnyhow.html#tymethod.tap_anyhow_err
+	fn tap_err_context<C>(
+		self,
+		context: C,
+		func: impl FnOnce(&anyhow::Error),
+	) -> Self
+	where
+		C: Display + Send + Sync + 'static;
+}
+
+#[cfg(feature = "anyhow")]
+impl<T> TapAnyhow<T> for anyhow::Result<T> {
+	#[inline(always)]
+	fn tap_anyhow_err(self, func: impl FnOnce(&anyhow::Error)) -> Self {
+		if let Err(err) = &self {
+			func(err);
+		}
+		self
+	}
+
+	#[inline(always)]
+	fn tap_err_context<C>(
+		self,
+		context: C,
+		func: impl FnOnce(&anyhow::Error),
+	) -> Self
+	where
+		C: Display + Send + Sync + 'static,
+	{
+		anyhow::Context::context(self, context).tap_anyhow_err(func)
+	}
+}
+
+/** Recording of values on the active OpenTelemetry span.
+
+These methods derive data from a value and attach it to the span that is active
+in the current OpenTelemetry context, then return the value unchanged. This
+connects a tap pipeline to distributed tracing without interrupting it to look
+up the span.
+
+The effect functions run only when the active span is recording. Outside of any
+span, or when the span was not sampled, the data is never computed, and the tap
+costs only the context lookup.
+
+This trait requires the `opentelemetry` feature, which adds a dependency on the
+`opentelemetry` crate, and does not exist without it.
+**/
+#[cfg(feature = "opentelemetry")]
+pub trait TapOtel
+where
+	Self: Sized,
+{
+	/// Sets an attribute, derived from the value, on the active span.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # extern crate opentelemetry;
+	/// # extern crate tap;
+	/// use opentelemetry::Value;
+	/// use tap::tap::TapOtel;
+	///
+	/// let body = "hello, world"
+	///   .tap_otel_attribute("body.len", |s| Value::I64(s.len() as i64));
+	/// assert_eq!(body, "hello, world");
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_otel_attribute<K>(
+		self,
+		key: K,
+		value_fn: impl FnOnce(&Self) -> Value,
+	) -> Self
+	where
+		K: Into<Key>,
+	{
+		get_active_span(|span| {
+			if span.is_recording() {
+				span.set_attribute(KeyValue::new(key, value_fn(&self)));
+			}
+		});
+		self
+	}
+
+	/// Adds an event, with attributes derived from the value, to the active
+	/// span.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # extern crate opentelemetry;
+	/// # extern crate tap;
+	/// use opentelemetry::KeyValue;
+	/// use tap::tap::TapOtel;
+	///
+	/// let batch = vec![1, 2, 3].tap_otel_event("batch.ready", |b| {
+	///   vec![KeyValue::new("size", b.len() as i64)]
+	/// });
+	/// assert_eq!(batch.len(), 3);
+	/// ```
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_otel_event(
+		self,
+		name: &'static str,
+		attributes: impl FnOnce(&Self) -> Vec<KeyValue>,
+	) -> Self {
+		get_active_span(|span| {
+			if span.is_recording() {
+				span.add_event(name, attributes(&self));
+			}
+		});
+		self
+	}
+
+	/// Calls `.tap_otel_attribute()` only in debug builds, and is erased in
+	/// release builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_otel_attribute_dbg<K>(
+		self,
+		key: K,
+		value_fn: impl FnOnce(&Self) -> Value,
+	) -> Self
+	where
+		K: Into<Key>,
 	{
 		if cfg!(debug_assertions) {
-			func(DerefMut::deref_mut(&mut self));
+			return self.tap_otel_attribute(key, value_fn);
+		}
+		self
+	}
+
+	/// Calls `.tap_otel_event()` only in debug builds, and is erased in
+	/// release builds.
+	#[inline(always)]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	#[track_caller]
+	fn tap_otel_event_dbg(
+		self,
+		name: &'static str,
+		attributes: impl FnOnce(&Self) -> Vec<KeyValue>,
+	) -> Self {
+		if cfg!(debug_assertions) {
+			return self.tap_otel_event(name, attributes);
+		}
+		self
+	}
+}
+
+#[cfg(feature = "opentelemetry")]
+impl<T> TapOtel for T where T: Sized {}
+
+/** Inspection of manually-initialized values.
+
+This trait is implemented on `MaybeUninit<T>`, and allows code that initializes
+a value piecemeal to view the value as if it were complete, for debugging the
+initialization, without consuming the `MaybeUninit` wrapper.
+**/
+pub trait TapMaybeUninit<T> {
+	/// Immutable access to the assumed-initialized value.
+	///
+	/// This function passes a reference to the interior value to the effect
+	/// function, and then returns the wrapper so that initialization may
+	/// continue.
+	///
+	/// # Safety
+	///
+	/// The caller must guarantee that the `MaybeUninit<T>` is fully
+	/// initialized at the time of the call. This has the same requirements as
+	/// [`MaybeUninit::assume_init_ref`]: calling it on an uninitialized or
+	/// partially-initialized value is immediate undefined behavior, even if the
+	/// effect function does not read the value.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::mem::MaybeUninit;
+	/// use tap::tap::TapMaybeUninit;
+	///
+	/// let mut slot = MaybeUninit::<[u8; 4]>::uninit();
+	/// slot.write([1, 2, 3, 4]);
+	/// unsafe {
+	///   slot.tap_assume_init_ref(|arr| assert_eq!(arr, &[1, 2, 3, 4]));
+	/// }
+	/// ```
+	///
+	/// [`MaybeUninit::assume_init_ref`]: https://doc.rust-lang.org/core/mem/union.MaybeUninit.html#method.assume_init_ref
+	unsafe fn tap_assume_init_ref(&self, func: impl FnOnce(&T)) -> &Self;
+}
+
+impl<T> TapMaybeUninit<T> for MaybeUninit<T> {
+	#[inline(always)]
+	unsafe fn tap_assume_init_ref(&self, func: impl FnOnce(&T)) -> &Self {
+		func(self.assume_init_ref());
+		self
+	}
+}
+
+/** Taps a value with the time elapsed since the previous tap at this site.
+
+This macro expands to a tap whose effect function receives a borrow of the value
+and an `Option<Duration>` measuring the time since this same macro invocation
+last ran. The first execution of each call site receives `None`.
+
+Each expansion of the macro has its own hidden timestamp, so separate call sites
+do not interfere with each other. The timestamp is shared among all threads that
+execute the call site.
+
+# Examples
+
+```rust
+use tap::tap_elapsed_since_last;
+
+for n in 0 .. 3 {
+  tap_elapsed_since_last!(n, |n, lap| println!("{}: {:?}", n, lap));
+}
+// prints
+// 0: None
+// 1: Some(…)
+// 2: Some(…)
+```
+**/
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! tap_elapsed_since_last {
+	($val:expr, $func:expr $(,)?) => {{
+		static LAST: $crate::tap::SinceLast = $crate::tap::SinceLast::new();
+		$crate::tap::SinceLast::record(&LAST, $val, $func)
+	}};
+}
+
+/** Runs a tap at most once per call site.
+
+This macro expands to [`Tap::tap_once_flag`] with a hidden `static AtomicBool`,
+so each call site of the macro runs its effect function the first time it is
+reached, and never again for the rest of the program. It evaluates to the
+value, unchanged.
+
+# Examples
+
+```rust
+use tap::tap_once;
+
+let mut firsts = vec![];
+for n in 0 .. 3 {
+  let _ = tap_once!(n, |n| firsts.push(*n));
+  let _ = tap_once!(n * 10, |n| firsts.push(*n));
+}
+assert_eq!(firsts, [0, 0]);
+```
+
+[`Tap::tap_once_flag`]: tap/trait.Tap.html#method.tap_once_flag
+**/
+#[cfg(target_has_atomic = "8")]
+#[macro_export]
+macro_rules! tap_once {
+	($val:expr, $func:expr $(,)?) => {{
+		static FIRED: $crate::tap::OnceFlag = $crate::tap::OnceFlag::new(false);
+		$crate::tap::Tap::tap_once_flag($val, &FIRED, $func)
+	}};
+}
+
+/** Runs a tap inside a profiler scope.
+
+This macro passes a borrow of the value to the effect function inside a
+`profiling::scope!` named by its second argument, and evaluates to the value,
+unchanged. The scope is reported to whichever backend the `profiling` crate is
+configured for: puffin, optick, superluminal, tracing, or tracy.
+
+This is a macro rather than a `Tap` method because several of those backends
+register each scope in a `static`, and so require its name at the call site.
+When this crate’s `profiling` feature is disabled, the scope is omitted, and
+the macro only runs the effect function.
+
+# Examples
+
+```rust
+use tap::tap_profile_scope;
+
+let parsed = tap_profile_scope!("42", "parse", |s| assert!(!s.is_empty()))
+  .parse::<i32>();
+assert_eq!(parsed, Ok(42));
+```
+**/
+#[macro_export]
+macro_rules! tap_profile_scope {
+	($val:expr, $name:expr, $func:expr $(,)?) => {{
+		let val = $val;
+		$crate::__tap_profile_scope!($name);
+		$crate::tap::Tap::tap(val, $func)
+	}};
+}
+
+/** Runs [`tap_profile_scope!`] only in debug builds.
+
+In release builds, neither the scope nor the effect function runs, and the
+macro evaluates to the value, unchanged.
+
+[`tap_profile_scope!`]: macro.tap_profile_scope.html
+**/
+#[macro_export]
+macro_rules! tap_profile_scope_dbg {
+	($val:expr, $name:expr, $func:expr $(,)?) => {{
+		let val = $val;
+		if cfg!(debug_assertions) {
+			$crate::__tap_profile_scope!($name);
+			$crate::tap::Tap::tap(val, $func)
+		} else {
+			val
+		}
+	}};
+}
+
+/// Implementation of [`tap_profile_scope!`]; not part of the public API.
+///
+/// [`tap_profile_scope!`]: macro.tap_profile_scope.html
+#[cfg(feature = "profiling")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tap_profile_scope {
+	($name:expr) => {
+		$crate::profiling::scope!($name);
+	};
+}
+
+/// Implementation of [`tap_profile_scope!`]; not part of the public API.
+///
+/// [`tap_profile_scope!`]: macro.tap_profile_scope.html
+#[cfg(not(feature = "profiling"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tap_profile_scope {
+	($name:expr) => {
+		let _: &'static str = $name;
+	};
+}
+
+/// Call-site storage for [`tap_once!`].
+///
+/// This alias is an implementation detail of the macro, which cannot name
+/// `core` reliably from every edition, and is not part of the public API.
+///
+/// [`tap_once!`]: ../macro.tap_once.html
+#[cfg(target_has_atomic = "8")]
+#[doc(hidden)]
+pub type OnceFlag = AtomicBool;
+
+/// Call-site storage for [`tap_elapsed_since_last!`].
+///
+/// This type is an implementation detail of the macro, and is not part of the
+/// public API.
+///
+/// [`tap_elapsed_since_last!`]: ../macro.tap_elapsed_since_last.html
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub struct SinceLast {
+	last: Mutex<Option<Instant>>,
+}
+
+#[cfg(feature = "std")]
+impl SinceLast {
+	#[doc(hidden)]
+	#[inline]
+	pub const fn new() -> Self {
+		Self {
+			last: Mutex::new(None),
+		}
+	}
+
+	#[doc(hidden)]
+	#[inline]
+	pub fn record<T>(
+		&self,
+		value: T,
+		func: impl FnOnce(&T, Option<Duration>),
+	) -> T {
+		let lap = {
+			let mut last =
+				self.last.lock().unwrap_or_else(PoisonError::into_inner);
+			let now = Instant::now();
+			last.replace(now).map(|then| now.duration_since(then))
+		};
+		func(&value, lap);
+		value
+	}
+}
+
+#[cfg(feature = "std")]
+impl Default for SinceLast {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A thread-safe rate limiter for [`Tap::tap_rate_limited`].
+///
+/// The bucket holds up to `capacity` tokens, starts full, and refills at
+/// `tokens_per_second`. Each tap that runs takes one token. Bursts of up to
+/// `capacity` taps run at once; after that, taps run at the refill rate.
+///
+/// The bucket is a single atomic timestamp, rather than a token count and a
+/// refill time, so that concurrent taps never observe the two out of step.
+/// It is tracked as the time at which the bucket will next be full, which
+/// is the “generic cell rate” formulation of a token bucket. The clock
+/// starts at the first tap, so a bucket can be built in a `static`.
+///
+/// [`Tap::tap_rate_limited`]: trait.Tap.html#method.tap_rate_limited
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+pub struct TokenBucket {
+	capacity: u64,
+	/// The nanoseconds it takes to refill one token, or `None` if the bucket
+	/// never refills.
+	interval: Option<u64>,
+	/// The nanoseconds, after `origin`, at which the bucket is next full.
+	///
+	/// A bucket that never refills counts the tokens taken here instead.
+	full_at: AtomicU64,
+	origin: OnceLock<Instant>,
+}
+
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+impl TokenBucket {
+	/// Creates a full bucket of `capacity` tokens, which refills at
+	/// `tokens_per_second`.
+	///
+	/// A rate of zero, or less, never refills the bucket. Neither does a rate
+	/// so low that a full refill would take longer than the roughly 584 years
+	/// a `u64` of nanoseconds can measure.
+	///
+	/// # Panics
+	///
+	/// This panics if `capacity` is zero.
+	#[inline]
+	pub const fn new(capacity: u64, tokens_per_second: f64) -> Self {
+		assert!(capacity != 0, "token bucket capacity must be nonzero");
+		let interval = if tokens_per_second > 0.0 {
+			let interval = (1e9 / tokens_per_second) as u64;
+			match interval.checked_mul(capacity) {
+				Some(_) => Some(interval),
+				None => None,
+			}
+		} else {
+			None
+		};
+		Self {
+			capacity,
+			interval,
+			full_at: AtomicU64::new(0),
+			origin: OnceLock::new(),
+		}
+	}
+
+	/// Takes a token, if one is available.
+	pub fn try_acquire(&self) -> bool {
+		let interval = match self.interval {
+			Some(interval) => interval,
+			None => {
+				return self
+					.full_at
+					.fetch_update(Ordering::AcqRel, Ordering::Acquire, |taken| {
+						(taken < self.capacity).then(|| taken + 1)
+					})
+					.is_ok();
+			}
+		};
+		let burst = interval * (self.capacity - 1);
+		let origin = self.origin.get_or_init(Instant::now);
+		let now = origin.elapsed().as_nanos() as u64;
+		self.full_at
+			.fetch_update(Ordering::AcqRel, Ordering::Acquire, |full_at| {
+				let start = cmp::max(full_at, now);
+				if start - now > burst {
+					return None;
+				}
+				Some(start.saturating_add(interval))
+			})
+			.is_ok()
+	}
+}
+
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+impl Debug for TokenBucket {
+	fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+		fmt.debug_struct("TokenBucket")
+			.field("capacity", &self.capacity)
+			.field("interval", &self.interval.map(Duration::from_nanos))
+			.finish()
+	}
+}
+
+/// Tracks a value across taps, to show each new value alongside the previous
+/// one.
+///
+/// A `TapDiff` remembers a copy of the last value passed through it. Each call
+/// to [`tap_diff`] or [`tap_if_changed`] shows the effect function the new value and
+/// the remembered one, and then remembers the new value. This suits streaming
+/// pipelines, where a value changes over time and only the changes are of
+/// interest.
+///
+/// # Examples
+///
+/// ```rust
+/// use tap::tap::TapDiff;
+///
+/// let mut diff = TapDiff::new();
+/// let mut changes = vec![];
+/// for reading in vec![20, 20, 21, 21, 19] {
+///   let _ =
+///     diff.tap_if_changed(reading, |new, old| changes.push((*old, *new)));
+/// }
+/// assert_eq!(changes, [(20, 21), (21, 19)]);
+/// ```
+///
+/// [`tap_diff`]: #method.tap_diff
+/// [`tap_if_changed`]: #method.tap_if_changed
+#[derive(Clone, Debug)]
+pub struct TapDiff<T> {
+	last: Option<T>,
+}
+
+impl<T> TapDiff<T> {
+	/// Creates a tracker that has not yet seen a value.
+	#[inline]
+	pub const fn new() -> Self {
+		Self { last: None }
+	}
+
+	/// Borrows the most recently tapped value, if any.
+	#[inline]
+	pub fn last(&self) -> Option<&T> {
+		self.last.as_ref()
+	}
+}
+
+impl<T> TapDiff<T>
+where
+	T: Clone,
+{
+	/// Immutable access to a value and to the value tapped before it.
+	///
+	/// The first call passes `None` as the previous value.
+	///
+	/// This is not named `tap`, because `Tap::tap` takes its receiver by
+	/// value, and so would be chosen over it wherever `Tap` is in scope.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapDiff;
+	///
+	/// let mut diff = TapDiff::new();
+	/// let mut deltas = vec![];
+	/// for total in vec![3, 5, 9] {
+	///   let _ =
+	///     diff.tap_diff(total, |new, old| deltas.push(new - old.unwrap_or(&0)));
+	/// }
+	/// assert_eq!(deltas, [3, 2, 4]);
+	/// ```
+	#[inline]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	pub fn tap_diff(
+		&mut self,
+		value: T,
+		func: impl FnOnce(&T, Option<&T>),
+	) -> T {
+		func(&value, self.last.as_ref());
+		self.last = Some(value.clone());
+		value
+	}
+
+	/// Immutable access to a value and to the value tapped before it, only
+	/// when the two differ.
+	///
+	/// The effect function does not run on the first call, since there is no
+	/// previous value to compare against.
+	#[inline]
+	#[must_use = "taps return the tapped value; dropping it discards your data"]
+	pub fn tap_if_changed(&mut self, value: T, func: impl FnOnce(&T, &T)) -> T
+	where
+		T: PartialEq,
+	{
+		if let Some(last) = self.last.as_ref() {
+			if *last != value {
+				func(&value, last);
+			}
 		}
-		self
+		self.last = Some(value.clone());
+		value
 	}
 }
 
-impl<T> Tap for T where T: Sized {}
+impl<T> Default for TapDiff<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
 
-/** Fallible tapping, conditional on the optional success of an expression.
+/** Asserts, at compile time, that a value has a specific size in bytes.
 
-This trait is intended for use on types that express the concept of “fallible
-presence”, primarily the [`Result`] monad. It provides taps that inspect the
-container to determine if the effect function should execute or not.
+This macro evaluates to its first argument, unchanged. If `size_of` the value’s
+type is not equal to the second argument, compilation fails. This is useful for
+pinning the layout of types that must fit a cache line, a hardware register, or
+a wire format, so that an accidentally-added field is caught where the value is
+used.
 
-> Note: This trait would ideally be implemented as a blanket over all
-> [`std::ops::Try`] implementors. When `Try` stabilizes, this crate can be
-> updated to do so.
+The value’s type is inferred, and cannot be named by a `const` item inside a
+closure, so the check is carried by an associated constant of a generic helper
+type instead. The error is therefore raised when the expression is
+monomorphized, which `cargo build` and `cargo test` perform but `cargo check`
+does not.
 
-[`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
-[`std::ops::Try`]: https://doc.rust-lang.org/std/ops/trait.Try.html
+# Examples
+
+```rust
+use tap::tap_ensure_size;
+
+let word = tap_ensure_size!(5u64, 8);
+let pair = tap_ensure_size!((1u32, 2u32), 8);
+```
+
+```rust,compile_fail
+use tap::tap_ensure_size;
+
+let word = tap_ensure_size!(5u64, 4);
+```
 **/
-pub trait TapFallible
+#[macro_export]
+macro_rules! tap_ensure_size {
+	($val:expr, $size:expr $(,)?) => {
+		$crate::tap::ensure_size::<_, { $size }>($val)
+	};
+}
+
+/** Asserts, at compile time, that a value has a specific alignment in bytes.
+
+This macro is the alignment counterpart of [`tap_ensure_size!`], and carries the
+same caveat about `cargo check`.
+
+# Examples
+
+```rust
+use tap::tap_ensure_align;
+
+#[repr(align(64))]
+struct CacheLine([u8; 64]);
+
+let line = tap_ensure_align!(CacheLine([0; 64]), 64);
+```
+
+```rust,compile_fail
+use tap::tap_ensure_align;
+
+let byte = tap_ensure_align!(5u8, 2);
+```
+
+[`tap_ensure_size!`]: macro.tap_ensure_size.html
+**/
+#[macro_export]
+macro_rules! tap_ensure_align {
+	($val:expr, $align:expr $(,)?) => {
+		$crate::tap::ensure_align::<_, { $align }>($val)
+	};
+}
+
+/// Compile-time layout assertions for [`tap_ensure_size!`] and
+/// [`tap_ensure_align!`].
+///
+/// [`tap_ensure_size!`]: ../macro.tap_ensure_size.html
+/// [`tap_ensure_align!`]: ../macro.tap_ensure_align.html
+struct LayoutCheck<T, const N: usize>(core::marker::PhantomData<T>);
+
+impl<T, const N: usize> LayoutCheck<T, N> {
+	const SIZE: () = assert!(mem::size_of::<T>() == N, "size mismatch");
+	const ALIGN: () = assert!(mem::align_of::<T>() == N, "alignment mismatch");
+}
+
+/// Implementation of [`tap_ensure_size!`]; not part of the public API.
+///
+/// [`tap_ensure_size!`]: ../macro.tap_ensure_size.html
+#[doc(hidden)]
+#[inline(always)]
+pub fn ensure_size<T, const N: usize>(value: T) -> T {
+	#[allow(clippy::let_unit_value)]
+	let () = LayoutCheck::<T, N>::SIZE;
+	value
+}
+
+/// Implementation of [`tap_ensure_align!`]; not part of the public API.
+///
+/// [`tap_ensure_align!`]: ../macro.tap_ensure_align.html
+#[doc(hidden)]
+#[inline(always)]
+pub fn ensure_align<T, const N: usize>(value: T) -> T {
+	#[allow(clippy::let_unit_value)]
+	let () = LayoutCheck::<T, N>::ALIGN;
+	value
+}
+
+/** Point-free inspection of the contents of a map.
+
+This trait is implemented on the standard library’s map collections. It provides
+taps that visit every key, value, or entry of the map, and then return the map
+unchanged.
+
+The visitation order is the iteration order of the underlying map. For
+`BTreeMap` this is sorted by key, and is deterministic. For `HashMap` it is
+unspecified, and with the default hasher it differs between program runs, so
+effect functions that log or collect what they see will produce different
+output each time. Sort the collected output, or use a `BTreeMap`, where a stable
+order matters.
+
+The trait is implemented through a sealed abstraction over “a map that can be
+iterated by entry and by mutable value”, and cannot be implemented outside this
+crate.
+**/
+#[cfg(feature = "alloc")]
+pub trait TapMap<K, V>
 where
-	Self: Sized + Try,
+	Self: Sized + map::Map<K, V>,
 {
-	/// Immutably accesses an interior success value.
-	///
-	/// This function is identical to [`Tap::tap`], except that it is required
-	/// to check the implementing container for value success before running.
-	/// Implementors must not run the effect function if the container is marked
-	/// as being a failure.
-	///
-	/// [`Tap::tap`]: trait.Tap.html#method.tap
-	fn tap_continue(self, func: impl FnOnce(&Self::Output)) -> Self;
+	/// Immutable access to each key of a map.
+	#[inline(always)]
+	fn tap_keys(self, mut func: impl FnMut(&K)) -> Self {
+		self.entries().for_each(|(k, _)| func(k));
+		self
+	}
 
-	/// Mutably accesses an interior success value.
+	/// Immutable access to each value of a map.
 	///
-	/// This function is identical to [`Tap::tap_mut`], except that it is
-	/// required to check the implementing container for value success before
-	/// running. Implementors must not run the effect function if the container
-	/// is marked as being a failure.
+	/// # Examples
 	///
-	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
-	fn tap_continue_mut(self, func: impl FnOnce(&mut Self::Output)) -> Self;
+	/// ```rust
+	/// use std::collections::BTreeMap;
+	/// use tap::tap::TapMap;
+	///
+	/// let config = vec![("port", 8080), ("workers", 4)]
+	///   .into_iter()
+	///   .collect::<BTreeMap<_, _>>()
+	///   .tap_values(|v| assert!(*v > 0));
+	/// ```
+	#[inline(always)]
+	fn tap_values(self, mut func: impl FnMut(&V)) -> Self {
+		self.entries().for_each(|(_, v)| func(v));
+		self
+	}
 
-	/// Immutably accesses an interior failure value.
+	/// Mutable access to each value of a map.
 	///
-	/// This function is identical to [`Tap::tap`], except that it is required
-	/// to check the implementing container for value failure before running.
-	/// Implementors must not run the effect function if the container is marked
-	/// as being a success.
+	/// Keys cannot be modified in place, as that could break the map’s
+	/// ordering or hashing invariants.
 	///
-	/// [`Tap::tap`]: trait.Tap.html#method.tap
-	fn tap_break(self, func: impl FnOnce(&Self::Residual)) -> Self;
-
-	/// Mutably accesses an interior failure value.
+	/// # Examples
 	///
-	/// This function is identical to [`Tap::tap_mut`], except that it is
-	/// required to check the implementing container for value failure before
-	/// running. Implementors must not run the effect function if the container
-	/// is marked as being a success.
+	/// ```rust
+	/// use std::collections::HashMap;
+	/// use tap::tap::TapMap;
 	///
-	/// [`Tap::tap_mut`]: trait.Tap.html#method.tap_mut
-	fn tap_break_mut(self, func: impl FnOnce(&mut Self::Residual)) -> Self;
+	/// let scores = vec![("ana", 1), ("bo", 2)]
+	///   .into_iter()
+	///   .collect::<HashMap<_, _>>()
+	///   .tap_values_mut(|v| *v *= 100);
+	/// assert_eq!(scores["bo"], 200);
+	/// ```
+	#[inline(always)]
+	fn tap_values_mut(mut self, func: impl FnMut(&mut V)) -> Self {
+		self.values_mut().for_each(func);
+		self
+	}
 
-	/// Calls `.tap_continue()` only in debug builds, and is erased in release builds.
+	/// Immutable access to each key/value pair of a map.
 	#[inline(always)]
-	fn tap_continue_dbg(self, func: impl FnOnce(&Self::Output)) -> Self {
-		if cfg!(debug_assertions) {
-			self.tap_continue(func)
-		} else {
-			self
+	fn tap_entries(self, mut func: impl FnMut(&K, &V)) -> Self {
+		self.entries().for_each(|(k, v)| func(k, v));
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<M, K, V> TapMap<K, V> for M where M: map::Map<K, V> {}
+
+/// The sealed map abstraction behind [`TapMap`].
+///
+/// [`TapMap`]: trait.TapMap.html
+#[cfg(feature = "alloc")]
+mod map {
+	#[cfg(feature = "alloc")]
+	use liballoc::collections::BTreeMap;
+
+	#[cfg(feature = "std")]
+	use std::{collections::HashMap, hash::BuildHasher};
+
+	/// A map that can be iterated by entry and by mutable value.
+	pub trait Map<K, V> {
+		/// Iterates over the entries of the map, in its natural order.
+		fn entries<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+		where
+			K: 'a,
+			V: 'a;
+
+		/// Iterates over the values of the map, in its natural order.
+		fn values_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut V>
+		where
+			V: 'a;
+	}
+
+	impl<K, V> Map<K, V> for BTreeMap<K, V> {
+		#[inline(always)]
+		fn entries<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+		where
+			K: 'a,
+			V: 'a,
+		{
+			self.iter()
+		}
+
+		#[inline(always)]
+		fn values_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut V>
+		where
+			V: 'a,
+		{
+			BTreeMap::values_mut(self)
 		}
 	}
 
-	/// Calls `.tap_continue_mut()` only in debug builds, and is erased in release
-	/// builds.
-	#[inline(always)]
-	fn tap_continue_mut_dbg(self, func: impl FnOnce(&mut Self::Output)) -> Self {
-		if cfg!(debug_assertions) {
-			self.tap_continue_mut(func)
-		} else {
-			self
+	#[cfg(feature = "std")]
+	impl<K, V, S> Map<K, V> for HashMap<K, V, S>
+	where
+		S: BuildHasher,
+	{
+		#[inline(always)]
+		fn entries<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+		where
+			K: 'a,
+			V: 'a,
+		{
+			self.iter()
+		}
+
+		#[inline(always)]
+		fn values_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut V>
+		where
+			V: 'a,
+		{
+			HashMap::values_mut(self)
 		}
 	}
+}
 
-	/// Calls `.tap_break()` only in debug builds, and is erased in release
-	/// builds.
+/** Non-panicking lookup taps on keyed and indexed collections.
+
+This trait is the checked counterpart of [`Tap::tap_index`]: rather than
+panicking when a key or index is absent, `.tap_get()` skips the effect function.
+It is implemented for slices, arrays, and `Vec` (by `usize`), and for `BTreeMap`
+and `HashMap` (by a borrow of the key). For other collections, use
+[`Tap::tap_lookup`] with the collection’s own `Option`-returning getter.
+
+[`Tap::tap_index`]: trait.Tap.html#method.tap_index
+[`Tap::tap_lookup`]: trait.Tap.html#method.tap_lookup
+**/
+pub trait TapGet<Q>
+where
+	Self: Sized,
+{
+	/// The type of the element found by a lookup.
+	type Value: ?Sized;
+
+	/// Finds the element for `key`, if present.
+	fn lookup(&self, key: Q) -> Option<&Self::Value>;
+
+	/// Immutable access to the element for `key`, if present.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tap::tap::TapGet;
+	///
+	/// let mut hits = 0;
+	/// let v = vec![10, 20, 30]
+	///   .tap_get(1, |n| { assert_eq!(*n, 20); hits += 1; })
+	///   .tap_get(7, |_| unreachable!());
+	/// assert_eq!(hits, 1);
+	/// ```
 	#[inline(always)]
-	fn tap_break_dbg(self, func: impl FnOnce(&Self::Residual)) -> Self {
-		if cfg!(debug_assertions) {
-			self.tap_break(func)
-		} else {
-			self
+	fn tap_get(self, key: Q, func: impl FnOnce(&Self::Value)) -> Self {
+		if let Some(val) = self.lookup(key) {
+			func(val);
 		}
+		self
 	}
+}
 
-	/// Calls `.tap_break_mut()` only in debug builds, and is erased in release
-	/// builds.
+/** Non-panicking mutable lookup taps on keyed and indexed collections.
+
+This trait is the mutable counterpart of [`TapGet`], and is implemented on the
+same collections, except shared slice references.
+
+[`TapGet`]: trait.TapGet.html
+**/
+pub trait TapGetMut<Q>
+where
+	Self: TapGet<Q>,
+{
+	/// Finds the element for `key` for modification, if present.
+	fn lookup_mut(&mut self, key: Q) -> Option<&mut Self::Value>;
+
+	/// Mutable access to the element for `key`, if present.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use std::collections::BTreeMap;
+	/// use tap::tap::TapGetMut;
+	///
+	/// # #[cfg(feature = "alloc")] {
+	/// let scores = vec![("ana", 1), ("bo", 2)]
+	///   .into_iter()
+	///   .collect::<BTreeMap<_, _>>()
+	///   .tap_get_mut("bo", |s| *s += 10)
+	///   .tap_get_mut("cy", |s| *s += 10);
+	/// assert_eq!(scores["bo"], 12);
+	/// assert_eq!(scores.len(), 2);
+	/// # }
+	/// ```
 	#[inline(always)]
-	fn tap_break_mut_dbg(self, func: impl FnOnce(&mut Self::Residual)) -> Self {
-		if cfg!(debug_assertions) {
-			self.tap_break_mut(func)
-		} else {
-			self
+	fn tap_get_mut(
+		mut self,
+		key: Q,
+		func: impl FnOnce(&mut Self::Value),
+	) -> Self {
+		if let Some(val) = self.lookup_mut(key) {
+			func(val);
 		}
+		self
 	}
 }
 
-impl<T> TapFallible for T
+impl<T> TapGet<usize> for &[T] {
+	type Value = T;
+
+	#[inline(always)]
+	fn lookup(&self, key: usize) -> Option<&T> {
+		self.get(key)
+	}
+}
+
+impl<T> TapGet<usize> for &mut [T] {
+	type Value = T;
+
+	#[inline(always)]
+	fn lookup(&self, key: usize) -> Option<&T> {
+		self.get(key)
+	}
+}
+
+impl<T> TapGetMut<usize> for &mut [T] {
+	#[inline(always)]
+	fn lookup_mut(&mut self, key: usize) -> Option<&mut T> {
+		self.get_mut(key)
+	}
+}
+
+impl<T, const N: usize> TapGet<usize> for [T; N] {
+	type Value = T;
+
+	#[inline(always)]
+	fn lookup(&self, key: usize) -> Option<&T> {
+		self.get(key)
+	}
+}
+
+impl<T, const N: usize> TapGetMut<usize> for [T; N] {
+	#[inline(always)]
+	fn lookup_mut(&mut self, key: usize) -> Option<&mut T> {
+		self.get_mut(key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapGet<usize> for Vec<T> {
+	type Value = T;
+
+	#[inline(always)]
+	fn lookup(&self, key: usize) -> Option<&T> {
+		self.get(key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TapGetMut<usize> for Vec<T> {
+	#[inline(always)]
+	fn lookup_mut(&mut self, key: usize) -> Option<&mut T> {
+		self.get_mut(key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'q, K, V, Q> TapGet<&'q Q> for BTreeMap<K, V>
 where
-	T: Try,
+	K: Borrow<Q> + Ord,
+	Q: ?Sized + Ord,
 {
+	type Value = V;
+
 	#[inline(always)]
-	fn tap_continue(self, func: impl FnOnce(&Self::Output)) -> Self {
-		match self.branch() {
-			ControlFlow::Continue(output) => {
-				func(&output);
-				Self::from_output(output)
-			}
-			ControlFlow::Break(residual) => Self::from_residual(residual),
-		}
+	fn lookup(&self, key: &'q Q) -> Option<&V> {
+		self.get(key)
 	}
+}
 
+#[cfg(feature = "alloc")]
+impl<'q, K, V, Q> TapGetMut<&'q Q> for BTreeMap<K, V>
+where
+	K: Borrow<Q> + Ord,
+	Q: ?Sized + Ord,
+{
 	#[inline(always)]
-	fn tap_continue_mut(self, func: impl FnOnce(&mut Self::Output)) -> Self {
-		match self.branch() {
-			ControlFlow::Continue(mut output) => {
-				func(&mut output);
-				Self::from_output(output)
-			}
-			ControlFlow::Break(residual) => Self::from_residual(residual),
-		}
+	fn lookup_mut(&mut self, key: &'q Q) -> Option<&mut V> {
+		self.get_mut(key)
 	}
+}
+
+#[cfg(feature = "std")]
+impl<'q, K, V, S, Q> TapGet<&'q Q> for HashMap<K, V, S>
+where
+	K: Borrow<Q> + Eq + Hash,
+	Q: ?Sized + Eq + Hash,
+	S: BuildHasher,
+{
+	type Value = V;
 
 	#[inline(always)]
-	fn tap_break(self, func: impl FnOnce(&Self::Residual)) -> Self {
-		match self.branch() {
-			ControlFlow::Continue(output) => Self::from_output(output),
-			ControlFlow::Break(residual) => {
-				func(&residual);
-				Self::from_residual(residual)
-			}
-		}
+	fn lookup(&self, key: &'q Q) -> Option<&V> {
+		self.get(key)
 	}
+}
 
+#[cfg(feature = "std")]
+impl<'q, K, V, S, Q> TapGetMut<&'q Q> for HashMap<K, V, S>
+where
+	K: Borrow<Q> + Eq + Hash,
+	Q: ?Sized + Eq + Hash,
+	S: BuildHasher,
+{
 	#[inline(always)]
-	fn tap_break_mut(self, func: impl FnOnce(&mut Self::Residual)) -> Self {
-		match self.branch() {
-			ControlFlow::Continue(output) => Self::from_output(output),
-			ControlFlow::Break(mut residual) => {
-				func(&mut residual);
-				Self::from_residual(residual)
-			}
-		}
+	fn lookup_mut(&mut self, key: &'q Q) -> Option<&mut V> {
+		self.get_mut(key)
 	}
 }
@@ -0,0 +1,297 @@
+/*! # Test Doubles for Taps
+
+This module provides helpers for testing code that accepts effect functions.
+Rather than writing a closure that pushes into a captured `Vec` and then
+inspecting that `Vec` by hand, a test creates a [`Spy`], passes the closure
+produced by [`Spy::observe`] wherever an effect function is expected, and then
+asserts on what the spy recorded.
+
+```rust
+use tap::{tap::Tap, testing::Spy};
+
+let spy = Spy::new();
+let total = vec![1, 2, 3]
+  .tap(spy.observe())
+  .into_iter()
+  .sum::<i32>();
+spy.assert_called_n_times(1);
+spy.assert_last_call(&vec![1, 2, 3]);
+```
+
+A spy’s log is shared between the spy, its clones, and every closure it has
+produced, so effect functions that run on other threads are recorded too.
+
+Where a spy records first and checks afterwards, a [`MockTap`] is configured
+with its expectations up front, and checks them itself when it is dropped.
+
+[`MockTap`]: struct.MockTap.html
+[`Spy`]: struct.Spy.html
+[`Spy::observe`]: struct.Spy.html#method.observe
+!*/
+
+use std::{
+	boxed::Box,
+	fmt::{self, Write},
+	format,
+	string::String,
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	thread,
+	vec::Vec,
+};
+
+/// Records every value passed to the effect functions it produces.
+///
+/// Cloning a `Spy` produces a second handle to the same log.
+pub struct Spy<T> {
+	log: Arc<Mutex<Vec<T>>>,
+}
+
+impl<T> Spy<T>
+where
+	T: Clone,
+{
+	/// Creates a spy with an empty log.
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			log: Arc::new(Mutex::new(Vec::new())),
+		}
+	}
+
+	/// Produces an effect function that records a clone of each value it
+	/// receives.
+	///
+	/// Every closure produced by the same spy appends to the same log, in the
+	/// order the calls happen.
+	#[inline]
+	pub fn observe(&self) -> impl Fn(&T) {
+		let log = Arc::clone(&self.log);
+		move |val| {
+			log.lock()
+				.unwrap_or_else(PoisonError::into_inner)
+				.push(val.clone())
+		}
+	}
+
+	/// Returns a copy of every recorded value, oldest first.
+	#[inline]
+	pub fn calls(&self) -> Vec<T> {
+		self.lock().clone()
+	}
+
+	/// Counts the recorded calls.
+	#[inline]
+	pub fn call_count(&self) -> usize {
+		self.lock().len()
+	}
+
+	/// Returns a copy of the most recently recorded value.
+	#[inline]
+	pub fn last_call(&self) -> Option<T> {
+		self.lock().last().cloned()
+	}
+
+	/// Asserts that exactly `n` calls have been recorded.
+	#[track_caller]
+	pub fn assert_called_n_times(&self, n: usize) {
+		let count = self.call_count();
+		assert!(
+			count == n,
+			"expected the spy to be called {} times, but it was called {} times",
+			n,
+			count,
+		);
+	}
+
+	/// Asserts that the most recently recorded value equals `expected`.
+	///
+	/// This panics if no calls have been recorded.
+	#[track_caller]
+	pub fn assert_last_call(&self, expected: &T)
+	where
+		T: PartialEq + fmt::Debug,
+	{
+		match self.last_call() {
+			Some(last) => assert_eq!(&last, expected, "unexpected last call"),
+			None => panic!(
+				"expected a last call of {:?}, but the spy was never called",
+				expected,
+			),
+		}
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Vec<T>> {
+		self.log.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+impl<T> Clone for Spy<T> {
+	#[inline]
+	fn clone(&self) -> Self {
+		Self {
+			log: Arc::clone(&self.log),
+		}
+	}
+}
+
+impl<T> Default for Spy<T>
+where
+	T: Clone,
+{
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> fmt::Debug for Spy<T>
+where
+	T: fmt::Debug,
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		let log = self.log.lock().unwrap_or_else(PoisonError::into_inner);
+		fmt.debug_struct("Spy").field("calls", &*log).finish()
+	}
+}
+
+/// Builds an effect function that checks its own calls.
+///
+/// Each `expect_*` method adds an expectation. [`build`] turns the builder into
+/// an effect function, which checks each argument as it is called and checks
+/// the call count when it is dropped. If any expectation was not met, the drop
+/// panics with a message listing every failure.
+///
+/// The built function must be called by mutable reference, as in
+/// `.tap(&mut mock)`: passing it by value would drop, and so check, it after
+/// the first call.
+///
+/// # Examples
+///
+/// ```rust
+/// use tap::{tap::Tap, testing::MockTap};
+///
+/// let mut mock = MockTap::new()
+///   .expect_calls(3)
+///   .expect_all(|n: &i32| *n > 0)
+///   .build();
+/// let total = (1 ..= 3).map(|n| n.tap(&mut mock)).sum::<i32>();
+/// drop(mock);
+/// ```
+///
+/// ```rust,should_panic
+/// use tap::{tap::Tap, testing::MockTap};
+///
+/// // MockTap expectations failed:
+/// // - expected 2 calls, but there were 1
+/// let mut mock = MockTap::new().expect_calls(2).build();
+/// let _ = 5.tap(&mut mock);
+/// ```
+///
+/// [`build`]: #method.build
+pub struct MockTap<T> {
+	calls: Option<usize>,
+	checks: Vec<Check<T>>,
+}
+
+/// An expectation that every argument must satisfy.
+type Check<T> = Box<dyn Fn(&T) -> bool>;
+
+impl<T> MockTap<T> {
+	/// Creates a builder with no expectations.
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			calls: None,
+			checks: Vec::new(),
+		}
+	}
+
+	/// Expects the effect function to be called exactly `n` times.
+	#[inline]
+	pub fn expect_calls(mut self, n: usize) -> Self {
+		self.calls = Some(n);
+		self
+	}
+
+	/// Expects every argument passed to the effect function to satisfy
+	/// `check`.
+	#[inline]
+	pub fn expect_all(mut self, check: impl Fn(&T) -> bool + 'static) -> Self {
+		self.checks.push(Box::new(check));
+		self
+	}
+
+	/// Produces the effect function.
+	pub fn build(self) -> impl FnMut(&T) {
+		let mut state = MockState {
+			calls: 0,
+			expected_calls: self.calls,
+			checks: self.checks,
+			failures: Vec::new(),
+		};
+		move |val| state.call(val)
+	}
+}
+
+impl<T> Default for MockTap<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> fmt::Debug for MockTap<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("MockTap")
+			.field("calls", &self.calls)
+			.field("checks", &self.checks.len())
+			.finish()
+	}
+}
+
+/// The running state of a built [`MockTap`], which checks it on drop.
+struct MockState<T> {
+	calls: usize,
+	expected_calls: Option<usize>,
+	checks: Vec<Check<T>>,
+	failures: Vec<String>,
+}
+
+impl<T> MockState<T> {
+	fn call(&mut self, val: &T) {
+		for (idx, check) in self.checks.iter().enumerate() {
+			if !check(val) {
+				self.failures.push(format!(
+					"call {} did not satisfy `expect_all` #{}",
+					self.calls, idx,
+				));
+			}
+		}
+		self.calls += 1;
+	}
+}
+
+impl<T> Drop for MockState<T> {
+	fn drop(&mut self) {
+		//  a second panic during unwinding would abort, and hide the first
+		if thread::panicking() {
+			return;
+		}
+		let mut failures = String::new();
+		if let Some(expected) = self.expected_calls {
+			if expected != self.calls {
+				let _ = writeln!(
+					failures,
+					"- expected {} calls, but there were {}",
+					expected, self.calls,
+				);
+			}
+		}
+		for failure in &self.failures {
+			let _ = writeln!(failures, "- {}", failure);
+		}
+		if !failures.is_empty() {
+			panic!("MockTap expectations failed:\n{}", failures);
+		}
+	}
+}
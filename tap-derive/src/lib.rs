@@ -0,0 +1,196 @@
+/*! # `tap-derive` – Per-Field Taps
+
+This crate provides `#[derive(Tap)]`, which generates a pair of inherent tap
+methods for each named field of a struct:
+
+- `tap_<field>(self, func: impl FnOnce(&FieldTy)) -> Self`
+- `tap_<field>_mut(self, func: impl FnOnce(&mut FieldTy)) -> Self`
+
+These are shorthand for `.tap_at(|s| &s.field, func)` and
+`.tap_at_mut(|s| &mut s.field, func)`. Each generated method has the same
+visibility as the field it reaches, so deriving `Tap` never exposes a field that
+was not already reachable.
+
+Fields accept a `#[tap(...)]` attribute:
+
+- `#[tap(skip)]` generates no methods for the field.
+- `#[tap(rename = "name")]` generates `tap_name` and `tap_name_mut` instead of
+  using the field’s own name.
+
+The generated methods are inherent, and so take priority over the methods of
+the `Tap` trait. A field whose name matches the suffix of a `Tap` method hides
+that method on the struct, without any warning: a field named `count` hides
+`Tap::tap_count`, and a field named `r#ref` hides `Tap::tap_ref` and
+`Tap::tap_ref_mut`. Rename or skip such fields to keep the trait method
+reachable by dot-call, or call it as `Tap::tap_count(value, …)`.
+
+Only structs with named fields are supported. This crate is not meant to be
+used directly; enable the `derive` feature of `tap` and use `tap::Tap`.
+!*/
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+	ext::IdentExt, parse_macro_input, Data, DeriveInput, Error, Fields, LitStr,
+};
+
+/// Generates `tap_<field>` and `tap_<field>_mut` methods for each named field.
+///
+/// These inherent methods hide any `Tap` trait method with the same name, such
+/// as `Tap::tap_count` for a field named `count`. Use `#[tap(rename = "...")]`
+/// or `#[tap(skip)]` on such fields to keep the trait method reachable.
+#[proc_macro_derive(Tap, attributes(tap))]
+pub fn derive_tap(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand(input).unwrap_or_else(compile_errors).into()
+}
+
+/// Renders errors as `compile_error!` invocations.
+///
+/// `syn::Error::into_compile_error` names the macro through `::core`, which
+/// does not resolve in the 2015 edition that `tap` itself uses.
+fn compile_errors(errors: Error) -> proc_macro2::TokenStream {
+	errors
+		.into_iter()
+		.map(|err| {
+			let msg = err.to_string();
+			quote_spanned!(err.span()=> compile_error!(#msg);)
+		})
+		.collect()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+	let fields = match input.data {
+		Data::Struct(data) => match data.fields {
+			Fields::Named(fields) => fields.named,
+			Fields::Unnamed(fields) => {
+				return Err(Error::new_spanned(
+					fields,
+					"`#[derive(Tap)]` does not support tuple structs",
+				));
+			}
+			Fields::Unit => {
+				return Err(Error::new(
+					Span::call_site(),
+					"`#[derive(Tap)]` does not support unit structs",
+				));
+			}
+		},
+		Data::Enum(_) | Data::Union(_) => {
+			return Err(Error::new(
+				Span::call_site(),
+				"`#[derive(Tap)]` only supports structs with named fields",
+			));
+		}
+	};
+
+	let mut methods = Vec::new();
+	let mut errors: Option<Error> = None;
+	for field in fields {
+		let ident = field.ident.expect("named fields have names");
+		let name = match method_name(&ident, &field.attrs) {
+			Ok(Some(name)) => name,
+			Ok(None) => continue,
+			Err(err) => {
+				match errors.as_mut() {
+					Some(errors) => errors.combine(err),
+					None => errors = Some(err),
+				}
+				continue;
+			}
+		};
+		let tap = format_ident!("tap_{}", name);
+		let tap_mut = format_ident!("tap_{}_mut", name);
+		let vis = &field.vis;
+		let ty = &field.ty;
+		let doc = format!("Immutable access to the `{}` field.", ident.unraw());
+		let doc_mut =
+			format!("Mutable access to the `{}` field.", ident.unraw());
+		methods.push(quote! {
+			#[doc = #doc]
+			#[inline(always)]
+			#[must_use = "taps return the tapped value; dropping it discards your data"]
+			#[track_caller]
+			#vis fn #tap(self, func: impl FnOnce(&#ty)) -> Self {
+				func(&self.#ident);
+				self
+			}
+
+			#[doc = #doc_mut]
+			#[inline(always)]
+			#[must_use = "taps return the tapped value; dropping it discards your data"]
+			#[track_caller]
+			#vis fn #tap_mut(mut self, func: impl FnOnce(&mut #ty)) -> Self {
+				func(&mut self.#ident);
+				self
+			}
+		});
+	}
+
+	if let Some(errors) = errors {
+		return Err(errors);
+	}
+
+	let name = &input.ident;
+	let (impl_generics, ty_generics, where_clause) =
+		input.generics.split_for_impl();
+	Ok(quote! {
+		#[automatically_derived]
+		impl #impl_generics #name #ty_generics #where_clause {
+			#(#methods)*
+		}
+	})
+}
+
+/// Chooses the name to use in a field’s methods, or `None` to skip it.
+fn method_name(
+	ident: &syn::Ident,
+	attrs: &[syn::Attribute],
+) -> syn::Result<Option<syn::Ident>> {
+	Ok(match FieldAttrs::parse(attrs)? {
+		FieldAttrs { skip: true, .. } => None,
+		FieldAttrs {
+			rename: Some(rename),
+			..
+		} => match rename.parse_with(syn::Ident::parse_any) {
+			Ok(name) => Some(name.unraw()),
+			Err(_) => {
+				return Err(Error::new_spanned(
+					rename,
+					"`rename` must be a valid identifier",
+				));
+			}
+		},
+		FieldAttrs { rename: None, .. } => Some(ident.unraw()),
+	})
+}
+
+/// The contents of the `#[tap(...)]` attributes on one field.
+#[derive(Default)]
+struct FieldAttrs {
+	skip: bool,
+	rename: Option<LitStr>,
+}
+
+impl FieldAttrs {
+	fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+		let mut out = Self::default();
+		for attr in attrs.iter().filter(|a| a.path().is_ident("tap")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("skip") {
+					out.skip = true;
+					Ok(())
+				} else if meta.path.is_ident("rename") {
+					out.rename = Some(meta.value()?.parse()?);
+					Ok(())
+				} else {
+					Err(meta.error("expected `skip` or `rename = \"...\"`"))
+				}
+			})?;
+		}
+		Ok(out)
+	}
+}
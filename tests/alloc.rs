@@ -0,0 +1,57 @@
+#![cfg(feature = "counting-alloc")]
+
+extern crate tap;
+
+use std::alloc::System;
+use tap::{alloc::CountingAllocator, prelude::*};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+
+#[test]
+fn counts_allocations() {
+	let mut seen = None;
+	let _ = 3.tap_alloc_count(
+		|n| {
+			let mut v = Vec::with_capacity(*n);
+			v.push(String::from("hello"));
+			v
+		},
+		|stats| seen = Some(stats),
+	);
+	let stats = seen.unwrap();
+	assert_eq!(stats.allocations, 2);
+	assert_eq!(stats.deallocations, 0);
+	assert_eq!(
+		stats.bytes_allocated,
+		(3 * std::mem::size_of::<String>() + 5) as u64,
+	);
+}
+
+#[test]
+fn counts_reallocations() {
+	let mut seen = None;
+	let _ = vec![0u8].tap_alloc_count(
+		|v| v.clone().tap_mut(|v| v.extend_from_slice(&[0; 64])),
+		|stats| seen = Some(stats),
+	);
+	let stats = seen.unwrap();
+	assert!(stats.allocated());
+	assert!(stats.reallocations >= 1);
+}
+
+#[test]
+fn zero_when_not_allocating() {
+	let mut seen = None;
+	let v = vec![1, 2, 3]
+		.tap_alloc_count(|v| v.iter().sum::<i32>(), |stats| seen = Some(stats))
+		.tap_assert_no_alloc(|v| v.len());
+	assert_eq!(seen, Some(tap::alloc::AllocStats::ZERO));
+	assert_eq!(v, [1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "effect function allocated")]
+fn assert_no_alloc_panics() {
+	let _ = 5.tap_assert_no_alloc(|n| n.to_string());
+}
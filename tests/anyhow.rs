@@ -0,0 +1,37 @@
+#![cfg(feature = "anyhow")]
+
+extern crate anyhow;
+extern crate tap;
+
+use anyhow::{anyhow, Context};
+use tap::prelude::*;
+
+fn parse(text: &str) -> anyhow::Result<u16> {
+	text.parse::<u16>().context("parsing a port")
+}
+
+#[test]
+fn error_chain() {
+	let mut calls = 0;
+	let mut roots = vec![];
+	let ok = parse("80").tap_anyhow_err(|_| calls += 1);
+	let err =
+		parse("x").tap_anyhow_err(|e| roots.push(e.root_cause().to_string()));
+	assert_eq!(ok.unwrap(), 80);
+	assert!(err.is_err());
+	assert_eq!(calls, 0);
+	assert_eq!(roots, ["invalid digit found in string"]);
+}
+
+#[test]
+fn context_then_tap() {
+	let mut depth = 0;
+	let res = Err::<(), _>(anyhow!("timeout"))
+		.tap_err_context("fetching", |e| depth = e.chain().count());
+	assert_eq!(depth, 2);
+	assert_eq!(format!("{:#}", res.unwrap_err()), "fetching: timeout");
+
+	let mut ran = false;
+	let ok = Ok::<_, anyhow::Error>(1).tap_err_context("unused", |_| ran = true);
+	assert_eq!((ok.unwrap(), ran), (1, false));
+}
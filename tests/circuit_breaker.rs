@@ -0,0 +1,77 @@
+#![cfg(all(feature = "std", target_has_atomic = "64"))]
+
+extern crate tap;
+
+use std::{panic, thread, time::Duration};
+use tap::{
+	circuit_breaker::{CircuitBreaker, CircuitState},
+	prelude::*,
+};
+
+#[test]
+fn opens_after_threshold() {
+	let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+	let mut calls = 0;
+	for n in 0..2 {
+		let _ = n.tap_circuit(&breaker, |_| {
+			calls += 1;
+			Err(())
+		});
+	}
+	let _ = 2.tap_circuit(&breaker, |_| Ok(()));
+	assert_eq!(breaker.state(), CircuitState::Closed);
+
+	for n in 0..10 {
+		let _ = n.tap_circuit(&breaker, |_| {
+			calls += 1;
+			Err(())
+		});
+	}
+	assert_eq!(calls, 5);
+	assert_eq!(breaker.state(), CircuitState::Open);
+	assert_eq!(breaker.call(|| Ok(())), None);
+}
+
+#[test]
+fn half_open_trial() {
+	let breaker = CircuitBreaker::new(1, Duration::ZERO);
+	assert_eq!(breaker.call(|| Err(())), Some(Err(())));
+	assert_eq!(breaker.state(), CircuitState::Open);
+
+	let nested = breaker.call(|| {
+		assert_eq!(breaker.state(), CircuitState::HalfOpen);
+		assert_eq!(breaker.call(|| Ok(())), None);
+		Err(())
+	});
+	assert_eq!(nested, Some(Err(())));
+	assert_eq!(breaker.state(), CircuitState::Open);
+
+	assert_eq!(breaker.call(|| Ok(())), Some(Ok(())));
+	assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn reset_timeout_is_honored() {
+	let breaker = CircuitBreaker::new(1, Duration::from_millis(250));
+	assert_eq!(breaker.call(|| Err(())), Some(Err(())));
+	assert_eq!(breaker.call(|| Ok(())), None);
+
+	thread::sleep(Duration::from_millis(300));
+	assert_eq!(breaker.call(|| Ok(())), Some(Ok(())));
+	assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn panicking_trial_reopens() {
+	let breaker = CircuitBreaker::new(1, Duration::ZERO);
+	assert_eq!(breaker.call(|| Err(())), Some(Err(())));
+
+	let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+		breaker.call(|| panic!("exporter crashed"))
+	}));
+	assert!(result.is_err());
+	assert_eq!(breaker.state(), CircuitState::Open);
+
+	assert_eq!(breaker.call(|| Ok(())), Some(Ok(())));
+	assert_eq!(breaker.state(), CircuitState::Closed);
+}
@@ -0,0 +1,59 @@
+/*! Code-size comparison between generic and trait-object taps.
+
+The `codegen_generic` and `codegen_dyn` examples each contain two hundred tap
+call sites. This test builds both to unoptimized LLVM IR and counts how many
+copies of the tap method each one instantiates.
+
+It is ignored by default because it invokes Cargo twice. Run it with
+`cargo test --test codegen -- --ignored`.
+!*/
+
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+fn emit_ir(example: &str) -> PathBuf {
+	let target = Path::new(env!("CARGO_TARGET_TMPDIR")).join("codegen");
+	let status = Command::new(env!("CARGO"))
+		.args(["rustc", "--quiet", "--example", example, "--manifest-path"])
+		.arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+		.args(["--", "--emit=llvm-ir", "-C", "no-prepopulate-passes"])
+		.env("CARGO_TARGET_DIR", &target)
+		.status()
+		.expect("failed to run cargo");
+	assert!(status.success(), "failed to build {}", example);
+
+	let prefix = format!("{}-", example);
+	fs::read_dir(target.join("debug").join("examples"))
+		.unwrap()
+		.map(|entry| entry.unwrap().path())
+		.filter(|path| path.extension().is_some_and(|ext| ext == "ll"))
+		.filter(|path| {
+			path.file_name()
+				.and_then(|name| name.to_str())
+				.is_some_and(|name| name.starts_with(&prefix))
+		})
+		.max_by_key(|path| fs::metadata(path).unwrap().modified().unwrap())
+		.expect("no LLVM IR was emitted")
+}
+
+/// Counts the function definitions in an IR file whose symbol mentions `Tap`.
+fn tap_instantiations(ir: &Path) -> usize {
+	fs::read_to_string(ir)
+		.unwrap()
+		.lines()
+		.filter(|line| line.starts_with("define") && line.contains("Tap"))
+		.count()
+}
+
+#[test]
+#[ignore = "invokes cargo; run with `--ignored`"]
+fn dyn_taps_share_one_instantiation() {
+	let generic = tap_instantiations(&emit_ir("codegen_generic"));
+	let dynamic = tap_instantiations(&emit_ir("codegen_dyn"));
+	println!("generic: {} copies, dyn: {} copies", generic, dynamic);
+	assert_eq!(generic, 200);
+	assert_eq!(dynamic, 1);
+}
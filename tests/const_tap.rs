@@ -0,0 +1,65 @@
+#![cfg(feature = "nightly-const")]
+#![feature(const_trait_impl)]
+
+extern crate tap;
+
+use tap::prelude::*;
+
+const fn crc_entry(idx: u32) -> u32 {
+	let mut crc = idx;
+	let mut bit = 0;
+	while bit < 8 {
+		crc = if crc & 1 == 1 {
+			(crc >> 1) ^ 0xEDB8_8320
+		} else {
+			crc >> 1
+		};
+		bit += 1;
+	}
+	crc
+}
+
+const fn fill(table: &mut [u32; 256]) {
+	let mut idx = 0;
+	while idx < 256 {
+		table[idx] = crc_entry(idx as u32);
+		idx += 1;
+	}
+}
+
+const fn checksum(table: &[u32; 256]) -> u32 {
+	table[1] ^ table[255]
+}
+
+const fn mark(val: &mut u8) {
+	*val = 1;
+}
+
+const fn check_zero(val: &u8) {
+	assert!(*val == 0);
+}
+
+const TABLE: [u32; 256] = [0; 256].tap_mut_const(fill);
+const CHECK: u32 = TABLE.pipe_ref_const(checksum);
+const MARKED: u8 = 0.tap_dbg_const(check_zero).tap_mut_dbg_const(mark);
+
+#[test]
+fn table_built_at_compile_time() {
+	assert_eq!(TABLE[0], 0);
+	assert_eq!(TABLE[1], 0x7707_3096);
+	assert_eq!(TABLE[255], 0x2D02_EF8D);
+	assert_eq!(CHECK, 0x7707_3096 ^ 0x2D02_EF8D);
+}
+
+#[test]
+fn runtime_closures() {
+	let mut seen = 0;
+	let out = 5.tap_const(|v| seen = *v).tap_mut_const(|v| *v += 1);
+	assert_eq!((seen, out), (5, 6));
+	assert_eq!(out.pipe_const(|v| v * 2), 12);
+}
+
+#[test]
+fn debug_only_at_compile_time() {
+	assert_eq!(MARKED, if cfg!(debug_assertions) { 1 } else { 0 });
+}
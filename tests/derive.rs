@@ -0,0 +1,45 @@
+#![cfg(feature = "derive")]
+
+extern crate tap;
+
+use tap::Tap;
+
+#[derive(Tap)]
+struct Request {
+	method: &'static str,
+	#[tap(rename = "uri")]
+	path: String,
+	#[tap(skip)]
+	#[allow(dead_code)]
+	token: Vec<u8>,
+	r#type: u8,
+}
+
+#[derive(Tap)]
+struct Wrapper<T: Clone> {
+	inner: T,
+}
+
+#[test]
+fn field_taps() {
+	let mut seen = (String::new(), 0);
+	let req = Request {
+		method: "GET",
+		path: "/".into(),
+		token: vec![],
+		r#type: 1,
+	}
+	.tap_method(|m| assert_eq!(*m, "GET"))
+	.tap_uri_mut(|p| p.push_str("index.html"))
+	.tap_uri(|p| seen.0 = p.clone())
+	.tap_type_mut(|t| *t += 1)
+	.tap_type(|t| seen.1 = *t);
+	assert_eq!(seen, ("/index.html".to_string(), 2));
+	assert_eq!(req.path, "/index.html");
+
+	//  the derive and the trait share a name without colliding
+	let wrapped = Wrapper { inner: vec![1, 2] }
+		.tap_inner_mut(|v| v.push(3))
+		.tap(|w| assert_eq!(w.inner.len(), 3));
+	assert_eq!(wrapped.inner, [1, 2, 3]);
+}
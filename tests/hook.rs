@@ -0,0 +1,31 @@
+#![cfg(all(feature = "global-hook", target_has_atomic = "ptr"))]
+
+extern crate tap;
+
+use std::sync::Mutex;
+use tap::{hook::TapEvent, prelude::*};
+
+static EVENTS: Mutex<Vec<(&str, &str, u32)>> = Mutex::new(Vec::new());
+
+fn record(event: &TapEvent) {
+	EVENTS.lock().unwrap().push((
+		event.method,
+		event.type_name,
+		event.location.line(),
+	));
+}
+
+fn ignore(_: &TapEvent) {}
+
+#[test]
+fn hook_sees_taps() {
+	assert!(tap::set_global_hook(record).is_ok());
+	assert!(tap::set_global_hook(ignore).is_err());
+
+	let line = line!() + 1;
+	let val = 5u8.tap(|_| {}).tap_mut(|v| *v += 1);
+	assert_eq!(val, 6);
+
+	let events = EVENTS.lock().unwrap();
+	assert_eq!(*events, [("tap", "u8", line), ("tap_mut", "u8", line)]);
+}
@@ -0,0 +1,921 @@
+#![cfg(feature = "iter")]
+
+extern crate tap;
+
+use std::iter::FusedIterator;
+use tap::{
+	iter::{IntStats, Stats},
+	prelude::*,
+};
+
+fn assert_fused<I: FusedIterator>(_: &I) {}
+
+#[test]
+fn each_both_ends() {
+	let mut seen = vec![];
+	let mut iter = [1, 2, 3, 4].iter().copied().tap_each(|n| seen.push(*n));
+	assert_eq!(iter.len(), 4);
+	assert_fused(&iter);
+	assert_eq!(iter.next(), Some(1));
+	assert_eq!(iter.next_back(), Some(4));
+	assert_eq!(iter.len(), 2);
+	assert_eq!(iter.rev().collect::<Vec<_>>(), [3, 2]);
+	assert_eq!(seen, [1, 4, 3, 2]);
+}
+
+#[test]
+fn enumerate_indices() {
+	let mut seen = vec![];
+	let out = "abcde"
+		.chars()
+		.collect::<Vec<_>>()
+		.into_iter()
+		.tap_each_enumerate(|idx, c| seen.push((idx, *c)))
+		.tap_mut(|it| {
+			it.next();
+			it.next_back();
+		})
+		.collect::<String>();
+	assert_eq!(out, "bcd");
+	assert_eq!(seen, [(0, 'a'), (4, 'e'), (1, 'b'), (2, 'c'), (3, 'd')]);
+}
+
+#[test]
+fn each_mut() {
+	let doubled = (1..=3).tap_each_mut(|n| *n *= 2).rev().collect::<Vec<_>>();
+	assert_eq!(doubled, [6, 4, 2]);
+}
+
+#[test]
+fn each_dbg() {
+	let mut count = 0;
+	let iter = (0..5).filter(|n| n % 2 == 0).tap_each_dbg(|_| count += 1);
+	assert_eq!(iter.size_hint(), (0, Some(5)));
+	assert_eq!(iter.count(), 3);
+	assert_eq!(count, if cfg!(debug_assertions) { 3 } else { 0 });
+
+	let mut count = 0;
+	let iter = (0..4)
+		.tap_each_enumerate_dbg(|_, _| count += 1)
+		.tap_each_mut_dbg(|n| *n *= 2);
+	assert_eq!(iter.size_hint(), (4, Some(4)));
+	assert_eq!(iter.len(), 4);
+	let out = iter.collect::<Vec<_>>();
+	if cfg!(debug_assertions) {
+		assert_eq!(count, 4);
+		assert_eq!(out, [0, 2, 4, 6]);
+	} else {
+		assert_eq!(count, 0);
+		assert_eq!(out, [0, 1, 2, 3]);
+	}
+}
+
+//  release builds hand back the very iterator that was tapped
+#[test]
+#[cfg(not(debug_assertions))]
+fn each_dbg_erased() {
+	let iter: std::ops::Range<i32> = (0..3)
+		.tap_each_dbg(|_| unreachable!())
+		.tap_each_enumerate_dbg(|_, _| unreachable!())
+		.tap_each_mut_dbg(|_| unreachable!());
+	assert_eq!(iter, 0..3);
+}
+
+#[test]
+fn skip_while_inspect() {
+	let mut rejected = vec![];
+	let rest = [1, 2, 5, 1, 7]
+		.iter()
+		.copied()
+		.tap_skip_while_inspect(|n| *n < 3, |n| rejected.push(*n))
+		.collect::<Vec<_>>();
+	assert_eq!(rest, [5, 1, 7]);
+	assert_eq!(rejected, [5]);
+
+	let mut calls = 0;
+	let rest = [9, 1, 2]
+		.iter()
+		.tap_skip_while_inspect(|n| **n < 3, |_| calls += 1)
+		.count();
+	assert_eq!(rest, 3);
+	assert_eq!(calls, 1);
+
+	let mut calls = 0;
+	let rest = [1, 2]
+		.iter()
+		.tap_skip_while_inspect(|n| **n < 3, |_| calls += 1)
+		.count();
+	assert_eq!(rest, 0);
+	assert_eq!(calls, 0);
+}
+
+#[test]
+fn take_while_inspect() {
+	let mut stopped = vec![];
+	let mut iter = [2, 4, 5, 6]
+		.iter()
+		.copied()
+		.tap_take_while_inspect(|n| n % 2 == 0, |n| stopped.push(*n));
+	assert_eq!(iter.by_ref().collect::<Vec<_>>(), [2, 4]);
+	assert_eq!(iter.next(), None);
+	drop(iter);
+	assert_eq!(stopped, [5]);
+
+	let mut calls = 0;
+	let taken = (0..3)
+		.tap_take_while_inspect(|_| true, |_| calls += 1)
+		.count();
+	assert_eq!((taken, calls), (3, 0));
+}
+
+#[test]
+fn fold_inspect() {
+	let mut steps = vec![];
+	let out = "abc".chars().tap_fold_inspect(
+		String::new(),
+		|mut acc, c| {
+			acc.insert(0, c);
+			acc
+		},
+		|acc| steps.push(acc.clone()),
+	);
+	assert_eq!(out, "cba");
+	assert_eq!(steps, ["a", "ba", "cba"]);
+
+	let mut calls = 0;
+	let empty = (0..0).tap_fold_inspect(7, |a, b| a + b, |_| calls += 1);
+	assert_eq!((empty, calls), (7, 0));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn partition() {
+	use std::cell::RefCell;
+
+	let log = RefCell::new(vec![]);
+	let (long, short) = ["a", "bbb", "cc", "dddd"].iter().tap_partition_collect(
+		|w| w.len() > 2,
+		|w| log.borrow_mut().push(format!("+{}", w)),
+		|w| log.borrow_mut().push(format!("-{}", w)),
+	);
+	assert_eq!(long, [&"bbb", &"dddd"]);
+	assert_eq!(short, [&"a", &"cc"]);
+	assert_eq!(log.into_inner(), ["-a", "+bbb", "-cc", "+dddd"]);
+
+	let (mut yes, mut no) = (1, 0);
+	let (pos, neg) =
+		[-1, 2, -3]
+			.iter()
+			.tap_partition_count(|n| **n > 0, &mut yes, &mut no);
+	assert_eq!((pos.len(), neg.len()), (1, 2));
+	assert_eq!((yes, no), (2, 2));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn collect_inspect() {
+	let mut seen = vec![];
+	let v = (1..=3).tap_collect_vec_inspect(|s| seen.extend_from_slice(s));
+	assert_eq!(v, seen);
+
+	let mut keys = vec![];
+	let map = ["a", "b"]
+		.iter()
+		.map(|k| (*k, k.len()))
+		.tap_collect_hashmap_inspect(|m| {
+			keys = m.keys().copied().collect::<Vec<_>>();
+			keys.sort_unstable();
+		});
+	assert_eq!(keys, ["a", "b"]);
+	assert_eq!(map["b"], 1);
+
+	let mut len = 0;
+	let set = "hello"
+		.chars()
+		.tap_collect_hashset_inspect(|s| len = s.len());
+	assert_eq!(len, 4);
+	assert!(set.contains(&'l'));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn container_each() {
+	use std::collections::HashMap;
+
+	let mut seen = vec![];
+	let v = vec![3, 1, 2]
+		.tap_each(|n| seen.push(*n))
+		.tap_each_mut(|n| *n *= 10)
+		.tap_each_dbg(|n| assert!(*n >= 10));
+	assert_eq!(seen, [3, 1, 2]);
+	assert_eq!(v, [30, 10, 20]);
+
+	let arr = ["x", "y"].tap_each_mut(|s| *s = "z");
+	assert_eq!(arr, ["z", "z"]);
+
+	let mut total = 0;
+	let map = [("a", 1), ("b", 2)]
+		.iter()
+		.copied()
+		.collect::<HashMap<_, _>>()
+		.tap_each_mut(|(_, v)| *v += 1)
+		.tap_each(|(_, v)| total += *v);
+	assert_eq!(total, 5);
+	assert_eq!(map["b"], 3);
+
+	let mut lazy = 0;
+	let count = (0..4).tap_each(|_| lazy += 1).count();
+	assert_eq!((count, lazy), (4, 4));
+}
+
+#[test]
+fn for_each_helpers() {
+	let mut count = 2;
+	let mut out = vec![];
+	"xyz"
+		.chars()
+		.tap_for_each_count(|c| out.push(c), &mut count);
+	assert_eq!(out, ['x', 'y', 'z']);
+	assert_eq!(count, 5);
+
+	let mut pairs = vec![];
+	(10..13)
+		.rev()
+		.tap_for_each_with_index(|idx, n| pairs.push((idx, n)));
+	assert_eq!(pairs, [(0, 12), (1, 11), (2, 10)]);
+}
+
+#[test]
+fn nth_sampling() {
+	#[cfg(target_has_atomic = "ptr")]
+	{
+		use std::sync::atomic::AtomicUsize;
+
+		let counter = AtomicUsize::new(0);
+		let mut hits = vec![];
+		for n in 0..7 {
+			let _ = n.tap_each_nth(3, &counter, |n| hits.push(*n));
+		}
+		assert_eq!(hits, [2, 5]);
+	}
+
+	let mut seen = vec![];
+	let out = (0..7)
+		.tap_iter_nth(3, |idx, n| seen.push((idx, *n)))
+		.rev()
+		.collect::<Vec<_>>();
+	assert_eq!(out, [6, 5, 4, 3, 2, 1, 0]);
+	assert_eq!(seen, [(6, 6), (3, 3), (0, 0)]);
+
+	assert!(
+		std::panic::catch_unwind(|| (0..1).tap_iter_nth(0, |_, _| {})).is_err()
+	);
+}
+
+#[test]
+fn interleave_inspect() {
+	let mut sources = vec![];
+	let merged = (0..2)
+		.tap_interleave_inspect(10..15, |left, n| sources.push((left, *n)))
+		.collect::<Vec<_>>();
+	assert_eq!(merged, [0, 10, 1, 11]);
+	assert_eq!(sources, [(true, 0), (false, 10), (true, 1), (false, 11)]);
+
+	let iter = (0..3).tap_interleave_inspect(10..12, |_, _| {});
+	assert_eq!(iter.size_hint(), (5, Some(5)));
+	assert_eq!(iter.count(), 5);
+
+	let mut iter = (0..5).tap_interleave_inspect(std::iter::empty(), |_, _| {});
+	assert_eq!(iter.size_hint(), (1, Some(1)));
+	assert_eq!(iter.next(), Some(0));
+	assert_eq!(iter.next(), None);
+	assert_eq!(iter.next(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn batches() {
+	let mut batches = vec![];
+	let mut iter = (0..7).tap_each_batch(3, |b| batches.push(b.to_vec()));
+	assert_eq!(iter.len(), 7);
+	assert_eq!(iter.next(), Some(0));
+	assert_eq!(iter.len(), 6);
+	assert_eq!(iter.by_ref().collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6]);
+	assert_eq!(iter.next(), None);
+	assert_eq!(batches, [vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+
+	let mut sizes = vec![];
+	let words = vec!["a".to_string(), "b".to_string()]
+		.into_iter()
+		.tap_each_batch_dbg(5, |b| sizes.push(b.len()))
+		.collect::<Vec<_>>();
+	assert_eq!(words, ["a", "b"]);
+	if cfg!(debug_assertions) {
+		assert_eq!(sizes, [2]);
+	}
+
+	let mut shown = 0;
+	assert_eq!((0..0).tap_each_batch(2, |_| shown += 1).count(), 0);
+	assert_eq!(shown, 0);
+	assert!(
+		std::panic::catch_unwind(|| (0..1).tap_each_batch(0, |_| {})).is_err()
+	);
+}
+
+#[test]
+fn on_empty() {
+	let mut calls = 0;
+	assert_eq!((0..0).tap_on_empty(|| calls += 1).count(), 0);
+	assert_eq!(calls, 1);
+
+	let mut calls = 0;
+	let mut iter = (0..0).tap_on_empty(|| calls += 1);
+	assert_eq!(iter.next(), None);
+	assert_eq!(iter.next(), None);
+	drop(iter);
+	assert_eq!(calls, 1);
+
+	let mut calls = 0;
+	assert_eq!((0..3).tap_on_empty(|| calls += 1).count(), 3);
+	assert_eq!(calls, 0);
+
+	//  partial consumption, and never consuming at all, both stay silent
+	let mut calls = 0;
+	let mut iter = (0..3).tap_on_empty(|| calls += 1);
+	assert_eq!(iter.size_hint(), (3, Some(3)));
+	assert_eq!(iter.next(), Some(0));
+	assert_eq!(iter.len(), 2);
+	drop(iter);
+	let _ = (0..0).tap_on_empty(|| calls += 1);
+	assert_eq!(calls, 0);
+
+	let mut calls = 0;
+	let back = (0..0).tap_on_empty(|| calls += 1).rev().collect::<Vec<_>>();
+	assert!(back.is_empty());
+	assert_eq!(calls, 1);
+
+	let mut calls = 0;
+	let out = (1..4).tap_on_empty(|| calls += 1).rev().collect::<Vec<_>>();
+	assert_eq!(out, [3, 2, 1]);
+	assert_eq!(calls, 0);
+}
+
+#[test]
+fn first_and_last() {
+	let mut calls = 0;
+	assert_eq!((0..0).tap_first(|_| calls += 1).count(), 0);
+	assert_eq!((0..0).tap_last(|_| calls += 1).count(), 0);
+	assert_eq!(calls, 0);
+
+	let (mut first, mut last) = (vec![], vec![]);
+	let out = std::iter::once(7)
+		.tap_first(|n| first.push(*n))
+		.tap_last(|n| last.push(*n))
+		.collect::<Vec<_>>();
+	assert_eq!(out, [7]);
+	assert_eq!((first, last), (vec![7], vec![7]));
+
+	let mut first = None;
+	let back = (0..5).tap_first(|n| first = Some(*n)).rev().take(2).count();
+	assert_eq!(back, 2);
+	assert_eq!(first, Some(4));
+
+	//  reading ahead of the consumer does not count as yielding
+	let mut last = None;
+	let mut iter = (0..2).tap_last(|n| last = Some(*n));
+	assert_eq!(iter.size_hint(), (2, Some(2)));
+	assert_eq!(iter.next(), Some(0));
+	assert_eq!(iter.len(), 1);
+	drop(iter);
+	assert_eq!(last, None);
+
+	let mut iter = (0..2).tap_last(|n| last = Some(*n));
+	assert_eq!(iter.by_ref().count(), 2);
+	assert_eq!(iter.next(), None);
+	assert_eq!(iter.len(), 0);
+	assert_fused(&iter);
+	drop(iter);
+	assert_eq!(last, Some(1));
+}
+
+#[test]
+fn single_nth() {
+	let mut seen = vec![];
+	let out = (10..15).tap_nth(0, |n| seen.push(*n)).collect::<Vec<_>>();
+	assert_eq!(out, [10, 11, 12, 13, 14]);
+	assert_eq!(seen, [10]);
+
+	let mut calls = 0;
+	let iter = (0..3).tap_nth(3, |_| calls += 1);
+	assert_eq!(iter.size_hint(), (3, Some(3)));
+	assert_eq!(iter.count(), 3);
+	assert_eq!(calls, 0);
+
+	//  `take` stops pulling before the index, or after it
+	let mut seen = vec![];
+	let _ = (0..10).tap_nth(5, |n| seen.push(*n)).take(5).count();
+	let _ = (0..10).tap_nth(5, |n| seen.push(*n)).take(6).count();
+	assert_eq!(seen, [5]);
+
+	//  items taken from the back do not count
+	let mut seen = vec![];
+	let mut iter = (0..5).tap_nth(1, |n| seen.push(*n));
+	assert_eq!(iter.next_back(), Some(4));
+	assert_eq!(iter.next_back(), Some(3));
+	assert_eq!(iter.len(), 3);
+	assert_eq!(iter.collect::<Vec<_>>(), [0, 1, 2]);
+	assert_eq!(seen, [1]);
+}
+
+#[test]
+fn every_nth() {
+	let three = std::num::NonZeroUsize::new(3).unwrap();
+	let mut seen = vec![];
+	let iter = (0..10).tap_every_nth(three, |idx, n| seen.push((idx, *n)));
+	assert_eq!(iter.size_hint(), (10, Some(10)));
+	assert_eq!(iter.count(), 10);
+	assert_eq!(seen, [(0, 0), (3, 3), (6, 6), (9, 9)]);
+
+	let one = std::num::NonZeroUsize::new(1).unwrap();
+	let mut sampled = vec![];
+	let mut inspected = vec![];
+	let out = (0..10)
+		.tap_every_nth(one, |_, n| sampled.push(*n))
+		.inspect(|n| inspected.push(*n))
+		.collect::<Vec<_>>();
+	assert_eq!(sampled, inspected);
+	assert_eq!(out, inspected);
+
+	let mut dbg = vec![];
+	let _ = (0..10)
+		.tap_every_nth_dbg(three, |idx, _| dbg.push(idx))
+		.count();
+	let expected: &[usize] = if cfg!(debug_assertions) {
+		&[0, 3, 6, 9]
+	} else {
+		&[]
+	};
+	assert_eq!(dbg, expected);
+}
+
+#[test]
+fn result_taps() {
+	let items: Vec<Result<i32, &str>> =
+		vec![Ok(1), Err("a"), Ok(2), Ok(3), Err("b")];
+	let stream = || items.clone().into_iter();
+
+	let mut oks = vec![];
+	let kept = stream().tap_oks(|n| oks.push(*n)).collect::<Vec<_>>();
+	assert_eq!(kept, stream().collect::<Vec<_>>());
+	assert_eq!(oks, [1, 2, 3]);
+
+	let mut errs = vec![];
+	let mut iter = stream().tap_errs(|e| errs.push(*e));
+	assert_eq!(iter.len(), 5);
+	assert_fused(&iter);
+	assert_eq!(iter.next_back(), Some(Err("b")));
+	assert_eq!(iter.filter_map(Result::ok).sum::<i32>(), 6);
+	assert_eq!(errs, ["b", "a"]);
+
+	let (mut ok_calls, mut err_calls) = (0, 0);
+	let count = stream()
+		.tap_results(|_| ok_calls += 1, |_| err_calls += 1)
+		.count();
+	assert_eq!((count, ok_calls, err_calls), (5, 3, 2));
+}
+
+#[test]
+fn option_taps() {
+	let items = vec![Some(1), None, Some(2), None, None, Some(3)];
+	let stream = || items.clone().into_iter();
+
+	let mut somes = vec![];
+	let mut nones = 0;
+	let out = stream()
+		.tap_somes(|n| somes.push(*n))
+		.tap_nones(|| nones += 1)
+		.collect::<Vec<_>>();
+	assert_eq!(out, items);
+	assert_eq!((somes, nones), (vec![1, 2, 3], 3));
+
+	let (mut hits, mut gaps) = (0, 0);
+	let mut iter = stream().tap_options(|_| hits += 1, || gaps += 1);
+	assert_eq!(iter.len(), 6);
+	assert_fused(&iter);
+	assert_eq!(iter.next_back(), Some(Some(3)));
+	assert_eq!(iter.flatten().sum::<i32>(), 3);
+	assert_eq!((hits, gaps), (3, 3));
+
+	let (mut dbg_somes, mut dbg_nones) = (0, 0);
+	let (mut dbg_hits, mut dbg_gaps) = (0, 0);
+	let _ = stream()
+		.tap_somes_dbg(|_| dbg_somes += 1)
+		.tap_nones_dbg(|| dbg_nones += 1)
+		.tap_options_dbg(|_| dbg_hits += 1, || dbg_gaps += 1)
+		.count();
+	let expected = if cfg!(debug_assertions) { 3 } else { 0 };
+	assert_eq!([dbg_somes, dbg_nones, dbg_hits, dbg_gaps], [expected; 4]);
+}
+
+#[test]
+fn count_on_drop() {
+	use std::{cell::Cell, panic};
+
+	let count = Cell::new(None);
+	let total = (0..5)
+		.tap_count_on_drop(|n| count.set(Some(n)))
+		.sum::<i32>();
+	assert_eq!((total, count.take()), (10, Some(5)));
+
+	let mut iter = (0..5).tap_count_on_drop(|n| count.set(Some(n)));
+	assert_eq!(iter.next(), Some(0));
+	assert_eq!(iter.next_back(), Some(4));
+	assert_eq!(iter.len(), 3);
+	assert_fused(&iter);
+	assert_eq!(count.get(), None);
+	drop(iter);
+	assert_eq!(count.take(), Some(2));
+
+	drop((0..5).tap_count_on_drop(|n| count.set(Some(n))));
+	assert_eq!(count.take(), Some(0));
+
+	let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+		(0..5)
+			.tap_count_on_drop(|n| count.set(Some(n)))
+			.for_each(|n| assert!(n < 3))
+	}));
+	assert!(result.is_err());
+	assert_eq!(count.take(), Some(4));
+}
+
+#[test]
+fn sorted_checks() {
+	let sorted = vec![1, 2, 2, 5];
+	let out = sorted
+		.clone()
+		.into_iter()
+		.tap_sorted_check()
+		.collect::<Vec<_>>();
+	assert_eq!(out, sorted);
+	assert_eq!(Some(7).into_iter().tap_strictly_sorted_check().count(), 1);
+	assert_eq!(
+		vec![9, 4, 1]
+			.into_iter()
+			.tap_sorted_by_check(|a: &i32, b| b.cmp(a))
+			.count(),
+		3,
+	);
+
+	let result = std::panic::catch_unwind(|| {
+		vec![1, 2, 2]
+			.into_iter()
+			.tap_strictly_sorted_check()
+			.count()
+	});
+	assert_eq!(result.is_err(), cfg!(debug_assertions));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "item 2 (3) is out of order after item 1 (8)")]
+fn unsorted_check_panics() {
+	let _ = vec![1, 8, 3].into_iter().tap_sorted_check().count();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "item 1 (5) is out of order after item 0 (9)")]
+fn unsorted_check_panics_on_first_pair() {
+	let _ = vec![9, 5].into_iter().tap_sorted_check().count();
+}
+
+//  Runs under `cargo test --release`, where the checks are erased and every
+//  item passes through unexamined.
+#[cfg(not(debug_assertions))]
+#[test]
+fn sorted_checks_erased() {
+	let unsorted = vec![9, 5, 5, 1];
+	let out = unsorted
+		.clone()
+		.into_iter()
+		.tap_sorted_check()
+		.tap_strictly_sorted_check()
+		.tap_sorted_by_check(|a: &i32, b| a.cmp(b))
+		.collect::<Vec<_>>();
+	assert_eq!(out, unsorted);
+}
+
+/// Yields `len` items while reporting a fixed remaining-items hint.
+struct Hinted {
+	len: usize,
+	hint: (usize, Option<usize>),
+}
+
+impl Iterator for Hinted {
+	type Item = usize;
+
+	fn next(&mut self) -> Option<usize> {
+		if self.len == 0 {
+			return None;
+		}
+		self.len -= 1;
+		self.hint = (
+			self.hint.0.saturating_sub(1),
+			self.hint.1.map(|hi| hi.saturating_sub(1)),
+		);
+		Some(self.len)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.hint
+	}
+}
+
+#[test]
+fn size_hint_checks() {
+	let honest = Hinted {
+		len: 4,
+		hint: (2, Some(6)),
+	};
+	assert_eq!(honest.tap_size_hint_check().count(), 4);
+
+	let unbounded = Hinted {
+		len: 3,
+		hint: (0, None),
+	};
+	assert_eq!(unbounded.tap_size_hint_check().count(), 3);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "yielded 3 items, but promised at most 2")]
+fn size_hint_lying_low() {
+	let iter = Hinted {
+		len: 3,
+		hint: (0, Some(2)),
+	};
+	let _ = iter.tap_size_hint_check().count();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "ended after 2 items, but promised at least 5")]
+fn size_hint_lying_high() {
+	let iter = Hinted {
+		len: 2,
+		hint: (5, None),
+	};
+	let _ = iter.tap_size_hint_check().count();
+}
+
+/// Returns `None` on every third call, and resumes afterwards.
+struct Flicker(usize);
+
+impl Iterator for Flicker {
+	type Item = usize;
+
+	fn next(&mut self) -> Option<usize> {
+		self.0 += 1;
+		if self.0.is_multiple_of(3) {
+			None
+		} else {
+			Some(self.0)
+		}
+	}
+}
+
+#[test]
+fn fused_checks() {
+	let mut iter = vec![1, 2].into_iter().tap_fused_check();
+	assert_fused(&iter);
+	assert_eq!(iter.next_back(), Some(2));
+	assert_eq!(iter.by_ref().count(), 1);
+	assert_eq!((iter.next(), iter.next_back()), (None, None));
+
+	let mut resumed = vec![];
+	let mut iter =
+		Flicker(0).tap_fused_check_with(|idx, n| resumed.push((idx, *n)));
+	let out = [iter.next(), iter.next(), iter.next(), iter.next()];
+	assert_eq!(out, [Some(1), Some(2), None, Some(4)]);
+	let expected: &[(usize, usize)] = if cfg!(debug_assertions) {
+		&[(2, 4)]
+	} else {
+		&[]
+	};
+	assert_eq!(resumed, expected);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "item 2 was yielded after it returned None")]
+fn unfused_check_panics() {
+	let mut iter = Flicker(0).tap_fused_check();
+	while iter.next().is_some() {}
+	let _ = iter.next();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn duplicate_taps() {
+	let mut repeats = vec![];
+	let out = ["a", "b", "a", "c", "b", "a"]
+		.iter()
+		.copied()
+		.tap_duplicates(|s| repeats.push(*s))
+		.collect::<Vec<_>>();
+	assert_eq!(out, ["a", "b", "a", "c", "b", "a"]);
+	assert_eq!(repeats, ["a", "b", "a"]);
+
+	let mut repeats = vec![];
+	let back = [1, 2, 1]
+		.iter()
+		.copied()
+		.tap_duplicates(|n| repeats.push(*n));
+	assert_eq!(back.rev().collect::<Vec<_>>(), [1, 2, 1]);
+	assert_eq!(repeats, [1]);
+
+	let mut repeats = vec![];
+	(0..10)
+		.tap_duplicates_by_key(|n| n % 4, |n| repeats.push(*n))
+		.for_each(drop);
+	assert_eq!(repeats, [4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn duplicate_capacity_limit() {
+	let mut repeats = vec![];
+	[1, 2, 3, 4, 1, 2, 3, 4, 1]
+		.iter()
+		.copied()
+		.tap_duplicates(|n| repeats.push(*n))
+		.with_capacity_limit(2)
+		.for_each(drop);
+	assert_eq!(repeats, [1, 2, 1]);
+
+	let mut repeats = 0;
+	[7, 7, 7]
+		.iter()
+		.copied()
+		.tap_duplicates(|_| repeats += 1)
+		.with_capacity_limit(0)
+		.for_each(drop);
+	assert_eq!(repeats, 0);
+}
+
+#[test]
+fn float_stats() {
+	fn naive(values: &[f64]) -> Stats {
+		let mut out = Stats::default();
+		let finite = values.iter().copied().filter(|v| !v.is_nan());
+		out.count = finite.clone().count();
+		out.nan_count = values.len() - out.count;
+		out.sum = finite.clone().sum();
+		if out.count > 0 {
+			out.mean = Some(out.sum / out.count as f64);
+			out.min = finite.clone().reduce(f64::min);
+			out.max = finite.reduce(f64::max);
+		}
+		out
+	}
+
+	let ramp = (0..1000).map(f64::from).collect::<Vec<_>>();
+	let spread = (0..200)
+		.map(|n| {
+			if n % 2 == 0 {
+				1e6 + n as f64
+			} else {
+				-0.5 * n as f64
+			}
+		})
+		.collect::<Vec<_>>();
+	let cases: [&[f64]; 5] = [
+		&[],
+		&[3.25; 7],
+		&ramp,
+		&spread,
+		&[2.0, f64::NAN, -1.0, f64::NAN],
+	];
+	for values in cases.iter() {
+		let mut seen = None;
+		values
+			.iter()
+			.copied()
+			.tap_stats(|s| seen = Some(s))
+			.for_each(drop);
+		let (seen, expected) = (seen.unwrap(), naive(values));
+		assert_eq!(
+			(seen.count, seen.nan_count, seen.min, seen.max),
+			(
+				expected.count,
+				expected.nan_count,
+				expected.min,
+				expected.max
+			),
+		);
+		assert!((seen.sum - expected.sum).abs() <= 1e-9 * expected.sum.abs());
+		match (seen.mean, expected.mean) {
+			(Some(a), Some(b)) => {
+				assert!((a - b).abs() <= 1e-9 * b.abs().max(1.0))
+			}
+			(a, b) => assert_eq!(a, b),
+		}
+	}
+}
+
+#[test]
+fn stats_fire_once() {
+	let mut reports = vec![];
+	let mut iter = [1u8, 2, 3, 4]
+		.iter()
+		.copied()
+		.tap_stats(|s| reports.push(s));
+	assert_eq!(iter.next(), Some(1));
+	assert_eq!(iter.next_back(), Some(4));
+	assert_eq!(iter.len(), 2);
+	drop(iter);
+	assert_eq!(reports.len(), 1);
+	assert_eq!((reports[0].count, reports[0].sum), (2, 5.0));
+
+	let mut reports = vec![];
+	let mut iter = (1..=2).tap_int_stats(|s| reports.push(s));
+	assert_eq!(iter.by_ref().count(), 2);
+	assert_eq!(iter.next(), None);
+	drop(iter);
+	assert_eq!(reports.len(), 1);
+	assert_eq!(reports[0].mean(), Some(1.5));
+}
+
+#[test]
+fn int_stats() {
+	let mut seen = None;
+	[i64::MIN, i64::MAX, i64::MAX, -3]
+		.iter()
+		.copied()
+		.tap_int_stats(|s| seen = Some(s))
+		.for_each(drop);
+	assert_eq!(
+		seen,
+		Some(IntStats {
+			count: 4,
+			sum: Some(i64::MAX as i128 - 4),
+			min: Some(i64::MIN as i128),
+			max: Some(i64::MAX as i128),
+		}),
+	);
+
+	let mut seen = None;
+	std::iter::empty::<u32>()
+		.tap_int_stats(|s| seen = Some(s))
+		.for_each(drop);
+	assert_eq!(seen, Some(IntStats::default()));
+	assert_eq!(seen.unwrap().mean(), None);
+}
+
+#[test]
+fn int_stats_overflow() {
+	let mut seen = None;
+	[i128::MAX, 1, i128::MIN]
+		.iter()
+		.copied()
+		.tap_int_stats(|s| seen = Some(s))
+		.for_each(drop);
+	let seen = seen.unwrap();
+	assert_eq!(seen.sum, None);
+	assert_eq!(seen.mean(), None);
+	assert_eq!(
+		(seen.count, seen.min, seen.max),
+		(3, Some(i128::MIN), Some(i128::MAX)),
+	);
+}
+
+//  The `_dbg` adapters return different types in debug and release builds.
+//  This pipeline uses only what both profiles provide, and is built by both
+//  `cargo test` and `cargo test --release`.
+#[test]
+#[cfg(feature = "alloc")]
+fn dbg_adapters_in_both_profiles() {
+	use std::{cell::Cell, num::NonZeroUsize};
+
+	let seen = Cell::new(0);
+	let bump = || seen.set(seen.get() + 1);
+	let out = vec![Some(1), None, Some(3)]
+		.into_iter()
+		.tap_each_dbg(|_| bump())
+		.tap_each_enumerate_dbg(|_, _| bump())
+		.tap_each_mut_dbg(|_| bump())
+		.tap_every_nth_dbg(NonZeroUsize::new(2).unwrap(), |_, _| bump())
+		.tap_somes_dbg(|_| bump())
+		.tap_nones_dbg(&bump)
+		.tap_options_dbg(|_| bump(), &bump)
+		.tap_size_hint_check()
+		.tap_fused_check()
+		.flatten()
+		.tap_sorted_check()
+		.tap_strictly_sorted_check()
+		.tap_sorted_by_check(|a: &i32, b| a.cmp(b))
+		.tap_each_batch_dbg(2, |_| bump())
+		.collect::<Vec<_>>();
+	assert_eq!(out, [1, 3]);
+	assert_eq!(seen.get(), if cfg!(debug_assertions) { 18 } else { 0 });
+}
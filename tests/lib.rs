@@ -32,3 +32,797 @@ fn basic() {
 	let _: Option<i32> = None.tap_break(|None| val = 10);
 	assert_eq!(val, 10);
 }
+
+#[test]
+fn batch() {
+	let none: [fn(&mut i32); 0] = [];
+	assert_eq!(5.tap_batch(none), 5);
+
+	let steps: [fn(&mut i32); 3] = [|v| *v += 1, |v| *v *= 10, |v| *v -= 3];
+	assert_eq!(1.tap_batch(steps), 17);
+}
+
+#[test]
+fn pipe_tap_order() {
+	let log = std::cell::RefCell::new(Vec::new());
+	let out = 5.pipe_tap(
+		|v| {
+			log.borrow_mut().push("transform");
+			v * 2
+		},
+		|v| {
+			assert_eq!(*v, 10);
+			log.borrow_mut().push("inspect");
+		},
+	);
+	assert_eq!(out, 10);
+	assert_eq!(log.into_inner(), ["transform", "inspect"]);
+}
+
+#[test]
+fn pipe_with_context() {
+	struct Db(Vec<&'static str>);
+	fn handle(id: usize, db: &Db) -> &'static str {
+		db.0[id]
+	}
+	fn count(span: &(usize, usize), db: &mut Db) -> usize {
+		db.0.push("seen");
+		span.1 - span.0 + db.0.len()
+	}
+
+	let mut db = Db(vec!["zero", "one"]);
+	assert_eq!(1.pipe_with(&db, handle), "one");
+	let span = (2, 5);
+	assert_eq!(span.pipe_ref_with(&mut db, count), 6);
+	assert_eq!(2.pipe_with(&db, handle), "seen");
+	assert_eq!(3.pipe_with(4, |a, b| a * b), 12);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn map_contents() {
+	use std::collections::{BTreeMap, HashMap};
+
+	let mut keys = Vec::new();
+	let mut total = 0;
+	let map = (1..=3)
+		.map(|n| (n, n * 10))
+		.collect::<BTreeMap<_, _>>()
+		.tap_keys(|k| keys.push(*k))
+		.tap_values(|v| total += v)
+		.tap_entries(|k, v| assert_eq!(k * 10, *v));
+	assert_eq!(map.len(), 3);
+	assert_eq!(keys, [1, 2, 3]);
+	assert_eq!(total, 60);
+
+	let mut count = 0;
+	let map = map
+		.into_iter()
+		.collect::<HashMap<_, _>>()
+		.tap_entries(|_, _| count += 1)
+		.tap_values_mut(|v| *v += 1);
+	assert_eq!(count, map.len());
+	let mut values = map.values().copied().collect::<Vec<_>>();
+	values.sort_unstable();
+	assert_eq!(values, [11, 21, 31]);
+
+	let empty = BTreeMap::<u8, u8>::new()
+		.tap_keys(|_| unreachable!())
+		.tap_values_mut(|_| unreachable!());
+	let _ = HashMap::<u8, u8>::new().tap_entries(|_, _| unreachable!());
+	assert!(empty.is_empty());
+}
+
+#[test]
+#[cfg(feature = "debugger")]
+fn breakpoint_not_taken() {
+	let mut checked = 0;
+	let out = 5.tap_breakpoint_when(|v| {
+		checked += 1;
+		*v > 10
+	});
+	assert_eq!(out, 5);
+	if cfg!(debug_assertions) {
+		assert_eq!(checked, 1);
+	}
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn err_rendering() {
+	let mut seen = Vec::new();
+	let ok: Result<i32, &str> = Ok(1);
+	let _ = ok
+		.tap_err_display(|e| seen.push(e.to_string()))
+		.tap_err_debug(|e| seen.push(e.to_string()));
+	assert!(seen.is_empty());
+
+	let err: Result<i32, &str> = Err("bad");
+	let _ = err
+		.tap_err_display(|e| seen.push(e.to_string()))
+		.tap_err_debug(|e| seen.push(e.to_string()));
+	assert_eq!(seen, ["bad", "\"bad\""]);
+}
+
+#[test]
+fn slice_buffers() {
+	let mut backing = [0u8; 4];
+	let buf = (&mut backing[..]).tap_copy_from_slice(&[1, 2, 3, 4]);
+	assert_eq!(buf, [1, 2, 3, 4]);
+
+	let mut strings = [String::new(), String::new()];
+	(&mut strings[..]).tap_clone_from_slice(&["a".into(), "b".into()]);
+	assert_eq!(strings, ["a", "b"]);
+
+	let res = std::panic::catch_unwind(|| {
+		let mut short = [0u8; 2];
+		(&mut short[..]).tap_copy_from_slice(&[1, 2, 3]);
+	});
+	assert!(res.is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn mem_report() {
+	use std::mem::size_of;
+
+	let mut report = None;
+	let _ = Vec::<u64>::with_capacity(10).tap_mem(|r| report = Some(r));
+	let report = report.unwrap();
+	assert_eq!(report.stack, size_of::<Vec<u64>>());
+	assert_eq!(report.heap_estimate, Some(80));
+
+	let mut words = Vec::with_capacity(4);
+	words.push(String::with_capacity(16));
+	words.push(String::with_capacity(5));
+	let mut heap = None;
+	let _ = words.tap_mem(|r| heap = r.heap_estimate);
+	assert_eq!(heap, Some(4 * size_of::<String>() + 16 + 5));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn str_case() {
+	let text = String::from("MiXeD")
+		.tap_str(|s| assert_eq!(s.len(), 5))
+		.tap_str_mut(str::make_ascii_lowercase);
+	assert_eq!(text, "mixed");
+
+	let boxed = Box::<str>::from("LOUD").tap_str_mut(str::make_ascii_lowercase);
+	assert_eq!(&*boxed, "loud");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn drop_guard() {
+	use std::{cell::Cell, mem, rc::Rc};
+	use tap::tap::TapGuard;
+
+	let runs = Rc::new(Cell::new(0));
+	let counter = |runs: &Rc<Cell<i32>>| {
+		let runs = runs.clone();
+		move |_: &Vec<i32>| runs.set(runs.get() + 1)
+	};
+
+	let mut guard = vec![1].tap_on_drop(counter(&runs));
+	guard.push(2);
+	assert_eq!(*guard, [1, 2]);
+	assert_eq!(runs.get(), 0);
+	drop(guard);
+	assert_eq!(runs.get(), 1);
+
+	mem::forget(vec![3].tap_on_drop(counter(&runs)));
+	assert_eq!(runs.get(), 1);
+
+	let guard = vec![4].tap_on_drop(counter(&runs));
+	assert_eq!(TapGuard::into_inner(guard), [4]);
+	assert_eq!(runs.get(), 1);
+}
+
+#[test]
+fn format_views() {
+	use std::fmt::{Debug, Display};
+
+	fn render(d: &dyn Display, g: &dyn Debug) -> String {
+		format!("{} / {:?}", d, g)
+	}
+
+	let mut shown = String::new();
+	let mut debugged = None;
+	let name = "tap"
+		.tap_display(|d| shown = format!("<{}>", d))
+		.tap_debug(|g| debugged = Some(render(&"dbg", g)));
+	assert_eq!(name, "tap");
+	assert_eq!(shown, "<tap>");
+	assert_eq!(debugged.as_deref(), Some(r#"dbg / "tap""#));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn index_views() {
+	use std::collections::HashMap;
+
+	let rows = vec![String::from("id"), String::from("row")]
+		.tap_index(1, |r| assert_eq!(r, "row"))
+		.tap_index_mut(0, |hdr| hdr.make_ascii_uppercase());
+	assert_eq!(rows, ["ID", "row"]);
+
+	let ports = [("http", 80), ("https", 443)]
+		.iter()
+		.copied()
+		.collect::<HashMap<_, _>>()
+		.tap_index(&"https", |p| assert_eq!(*p, 443));
+	assert_eq!(ports.len(), 2);
+
+	let buf = [0u8; 6].tap_index_mut(2..4, |mid: &mut [u8]| mid.fill(7));
+	assert_eq!(buf, [0, 0, 7, 7, 0, 0]);
+
+	let res = std::panic::catch_unwind(|| vec![1].tap_index(5, |_| {}));
+	assert!(res.is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn checked_lookups() {
+	use std::collections::HashMap;
+
+	let mut hits = vec![];
+	let v = vec![1, 2, 3]
+		.tap_get(0, |n| hits.push(*n))
+		.tap_get(3, |n| hits.push(*n))
+		.tap_get_mut(2, |n| *n *= 10)
+		.tap_get_mut(5, |n| *n *= 10);
+	assert_eq!(hits, [1]);
+	assert_eq!(v, [1, 2, 30]);
+
+	let mut buf = [0u8; 3];
+	let _ = (&mut buf[..])
+		.tap_get_mut(1, |b| *b = 9)
+		.tap_get(4, |_| panic!());
+	assert_eq!(buf, [0, 9, 0]);
+
+	let mut seen = None;
+	let map = [(String::from("a"), 1)]
+		.iter()
+		.cloned()
+		.collect::<HashMap<_, _>>()
+		.tap_get("a", |v| seen = Some(*v))
+		.tap_get("z", |_| panic!())
+		.tap_get_mut("a", |v| *v += 1)
+		.tap_get_mut("z", |_| panic!());
+	assert_eq!(seen, Some(1));
+	assert_eq!(map["a"], 2);
+
+	let mut found = vec![];
+	let pair = (Some(5), None::<i32>)
+		.tap_lookup(|p| p.0.as_ref(), |n| found.push(*n))
+		.tap_lookup(|p| p.1.as_ref(), |n| found.push(*n))
+		.tap_lookup_mut(|p| p.0.as_mut(), |n| *n += 1);
+	assert_eq!(found, [5]);
+	assert_eq!(pair, (Some(6), None));
+}
+
+#[test]
+fn test_hooks_gate() {
+	let mut ran = false;
+	let _ = 5.tap_test(|_| ran = true);
+	assert_eq!(ran, cfg!(feature = "test-hooks"));
+}
+
+struct Endpoint {
+	host: String,
+	ports: Vec<u16>,
+}
+
+struct Service {
+	name: &'static str,
+	endpoint: Endpoint,
+}
+
+#[test]
+fn projections() {
+	let mut host = None;
+	let mut port = None;
+	let svc = Service {
+		name: "api",
+		endpoint: Endpoint {
+			host: "localhost".into(),
+			ports: vec![80, 443],
+		},
+	}
+	.tap_at(|s| s.endpoint.host.as_str(), |h| host = Some(h.to_owned()))
+	.tap_at(|s| &s.endpoint.ports[1], |p| port = Some(*p));
+	assert_eq!(host.as_deref(), Some("localhost"));
+	assert_eq!(port, Some(443));
+	assert_eq!(svc.name, "api");
+}
+
+enum Shape {
+	Circle { radius: f64 },
+	Rect { sides: [f64; 2] },
+}
+
+#[test]
+fn mutable_projections() {
+	let svc = Service {
+		name: "api",
+		endpoint: Endpoint {
+			host: "localhost".into(),
+			ports: vec![80],
+		},
+	}
+	.tap_at_mut(|s| &mut s.endpoint.ports, |p| p.push(443))
+	.tap_at_mut(|s| s.endpoint.host.as_mut_str(), str::make_ascii_uppercase);
+	assert_eq!(svc.endpoint.ports, [80, 443]);
+	assert_eq!(svc.endpoint.host, "LOCALHOST");
+
+	let grow = |shape: Shape| {
+		shape.tap_at_mut(
+			|s| match s {
+				Shape::Circle { radius } => radius,
+				Shape::Rect { sides } => &mut sides[0],
+			},
+			|len| *len *= 2.0,
+		)
+	};
+	match grow(Shape::Circle { radius: 1.5 }) {
+		Shape::Circle { radius } => assert_eq!(radius, 3.0),
+		Shape::Rect { .. } => unreachable!(),
+	}
+	match grow(Shape::Rect { sides: [1.0, 4.0] }) {
+		Shape::Rect { sides } => assert_eq!(sides, [2.0, 4.0]),
+		Shape::Circle { .. } => unreachable!(),
+	}
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn nested_containers() {
+	use std::{cell::RefCell, rc::Rc};
+
+	let mut seen = vec![];
+	let list = Some(Box::new(vec![1]))
+		.tap_opt_box_mut(|v| v.push(2))
+		.tap_opt_box(|v| seen.extend_from_slice(v));
+	assert_eq!(seen, [1, 2]);
+	assert_eq!(list.as_deref(), Some(&vec![1, 2]));
+
+	let _ = None::<Box<str>>.tap_flatten(|_| unreachable!());
+
+	let boxed = Box::new(Some(3)).tap_flatten_mut(|n| *n *= 3);
+	assert_eq!(*boxed, Some(9));
+	let _ = Box::new(None::<i32>).tap_flatten(|_| unreachable!());
+
+	let cell = Rc::new(RefCell::new(String::from("a")));
+	let _ = cell.clone().tap_flatten_mut(|s| s.push('b'));
+	let mut len = 0;
+	let _ = cell.tap_flatten(|s| len = s.len());
+	assert_eq!(len, 2);
+}
+
+#[test]
+fn range_views() {
+	let buf = [1, 2, 3, 4, 5]
+		.tap_range(..2, |head| assert_eq!(head, [1, 2]))
+		.tap_range(4, |last| assert_eq!(*last, 5))
+		.tap_range_mut(3.., |tail| tail.reverse());
+	assert_eq!(buf, [1, 2, 3, 5, 4]);
+
+	let res = std::panic::catch_unwind(|| [0u8; 4].tap_range(2..9, |_| {}));
+	assert!(res.is_err());
+
+	let (mut hits, lo, hi) = (0, 3, 2);
+	let buf = vec![0u8; 4]
+		.tap_range_checked(2..9, |_| hits += 1)
+		.tap_range_checked(lo..hi, |_| hits += 1)
+		.tap_range_mut_checked(9, |b| *b = 1)
+		.tap_range_mut_checked(1..=2, |s| s.fill(7));
+	assert_eq!(hits, 0);
+	assert_eq!(buf, [0, 7, 7, 0]);
+
+	let (mut whole, mut viewed) = (vec![], vec![]);
+	let buf = buf
+		.tap_range(.., |s: &[u8]| whole = s.to_vec())
+		.tap_ref(|s: &[u8]| viewed = s.to_vec());
+	assert_eq!(whole, viewed);
+	assert_eq!(whole, buf);
+}
+
+#[test]
+fn tee_pairs() {
+	let (text, upper) = String::from("tap").tee(|s| s.to_uppercase());
+	assert_eq!(text, "tap");
+	assert_eq!(upper, "TAP");
+}
+
+#[test]
+fn reference_taps() {
+	fn total(nums: &[i32]) -> i32 {
+		nums.iter().sum()
+	}
+
+	let data = [1, 2, 3];
+	let mut sum = 0;
+	let view: &[i32] = (&data[..])
+		.tap_ref_self(|s| sum = total(s))
+		.tap(|s| assert_eq!(s.len(), 3));
+	assert_eq!(sum, 6);
+	assert_eq!(view, [1, 2, 3]);
+
+	let mut text = String::from("tap");
+	let mut len = 0;
+	let borrowed: &mut str = text.as_mut_str().tap_ref_self(|s| len = s.len());
+	borrowed.make_ascii_uppercase();
+	assert_eq!(len, 3);
+	assert_eq!(text, "TAP");
+
+	//  owned receivers auto-borrow, and get the borrow back
+	let owned = vec![4, 5];
+	let back: &Vec<i32> = owned.tap_ref_self(|v| assert_eq!(v.len(), 2));
+	assert_eq!(back, &owned);
+}
+
+#[test]
+fn double_deref() {
+	use std::sync::Arc;
+
+	let mut seen = vec![];
+	let bytes = Box::new(vec![3u8, 1, 2])
+		.tap_deref2(|s: &[u8]| seen.extend_from_slice(s))
+		.tap_deref2_mut(<[u8]>::sort)
+		.tap_deref2_mut_dbg(|s: &mut [u8]| s.reverse());
+	assert_eq!(seen, [3, 1, 2]);
+	if cfg!(debug_assertions) {
+		assert_eq!(*bytes, [3, 2, 1]);
+	}
+
+	let mut len = 0;
+	let shared = Arc::new(String::from("shared"))
+		.tap_deref2(|s: &str| len = s.len())
+		.tap_deref2_dbg(|s: &str| assert!(s.is_ascii()));
+	assert_eq!(len, 6);
+	assert_eq!(Arc::strong_count(&shared), 1);
+}
+
+#[test]
+fn sorted_asserts() {
+	use std::panic::catch_unwind;
+
+	let mut words = vec!["pear", "fig", "apple", "fig"];
+	words.sort_unstable();
+	let words = words
+		.tap_assert_sorted()
+		.tap_assert_sorted_dbg()
+		.tap_assert_sorted_by(|a, b| b.cmp(a).reverse());
+	assert!(catch_unwind(|| vec![2, 1].tap_assert_sorted()).is_err());
+	assert!(catch_unwind(|| {
+		words
+			.clone()
+			.tap_assert_sorted_by(|a, b| a.len().cmp(&b.len()))
+	})
+	.is_err());
+
+	let res = catch_unwind(|| vec![1, 3, 2].tap_assert_sorted());
+	let msg = *res.unwrap_err().downcast::<String>().unwrap();
+	assert_eq!(
+		msg,
+		"slice is not sorted: element 1 (3) is greater than element 2 (2)"
+	);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn unique_asserts() {
+	use std::panic::catch_unwind;
+
+	let _ = vec![(1, 'a'), (2, 'b')]
+		.tap_assert_unique()
+		.tap_assert_unique_by_key(|(_, c)| *c);
+	assert!(catch_unwind(|| vec!["fig", "fig"].tap_assert_unique()).is_err());
+
+	let res = catch_unwind(|| {
+		vec![(1, 'a'), (2, 'a')].tap_assert_unique_by_key(|(_, c)| *c)
+	});
+	let msg = *res.unwrap_err().downcast::<String>().unwrap();
+	assert_eq!(
+		msg,
+		"slice is not unique: element 1 ((2, 'a')) repeats the key 'a'"
+	);
+}
+
+#[test]
+fn copy_taps() {
+	let mut seen = vec![];
+	let flags = 0b1010u8
+		.tap_copy(|f| seen.push(f.count_ones()))
+		.tap_copy_dbg(|f| assert_eq!(f, 10));
+	assert_eq!(flags, 10);
+	assert_eq!(seen, [2]);
+}
+
+#[test]
+#[cfg(feature = "backtrace")]
+fn err_backtrace() {
+	let mut calls = 0;
+	let _ = Ok::<i32, &str>(5).tap_err_backtrace(|_, _| calls += 1);
+	assert_eq!(calls, 0);
+
+	let mut seen = None;
+	let _ = Err::<i32, &str>("boom").tap_err_backtrace(|err, trace| {
+		calls += 1;
+		seen = Some(format!("{}: {}", err, trace));
+	});
+	assert_eq!(calls, 1);
+	assert!(seen.unwrap().starts_with("boom: "));
+}
+
+#[test]
+fn approx_asserts() {
+	use std::panic::catch_unwind;
+
+	let _ = 0.5f32
+		.tap_assert_approx_eq(0.5, 0.0)
+		.tap_assert_approx_eq_dbg(0.49, 0.02);
+	let _ = 1000u16.tap_assert_approx_eq(1003.0, 5.0);
+
+	let res = catch_unwind(|| 1.5f64.tap_assert_approx_eq(1.0, 0.25));
+	let msg = *res.unwrap_err().downcast::<String>().unwrap();
+	assert_eq!(msg, "expected 1 ± 0.25, got 1.5");
+	assert!(catch_unwind(|| f64::NAN.tap_assert_approx_eq(0.0, 1e9)).is_err());
+}
+
+#[test]
+#[cfg(feature = "approx")]
+fn abs_diff_eq() {
+	let _ = (0.1f64 + 0.2).tap_assert_abs_diff_eq(0.3, f64::EPSILON);
+	let res =
+		std::panic::catch_unwind(|| 1.0f32.tap_assert_abs_diff_eq(1.5, 0.1));
+	let msg = *res.unwrap_err().downcast::<String>().unwrap();
+	assert_eq!(msg, "expected 1.5 ± 0.1, got 1.0");
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn bit_pattern_asserts() {
+	let _ = [0xCAu8, 0xFE]
+		.tap_assert_bit_pattern(&[0xCA, 0xFE])
+		.tap_assert_bit_pattern_dbg(b"\xca\xfe");
+
+	let res = std::panic::catch_unwind(|| {
+		0x0A0B0C0Du32
+			.to_be()
+			.tap_assert_bit_pattern(&[0x0A, 0xFF, 0x0C])
+	});
+	let msg = *res.unwrap_err().downcast::<String>().unwrap();
+	assert_eq!(
+		msg,
+		"bit pattern mismatch\n\
+		 expected: 0a ff 0c\n  \
+		 actual: 0a 0b 0c 0d\n          \
+		 \x20  ^^    ^^"
+	);
+}
+
+#[test]
+fn apply_transforms() {
+	let words = String::from("tap")
+		.tap_apply(|s| s + "-pipe")
+		.tap_mut(|s| s.make_ascii_uppercase())
+		.tap_apply(|s| s.replace('-', " "));
+	assert_eq!(words, "TAP PIPE");
+	assert_eq!(4.tap_apply(|n| n * n), 4.pipe(|n| n * n));
+}
+
+#[test]
+fn with_ref_taps() {
+	let baseline = String::from("ab");
+	let mut delta = 0;
+	let text = String::from("abcd")
+		.tap_with_ref(baseline.as_str(), |now, base| {
+			delta = now.len() - base.len()
+		})
+		.tap_with_ref_mut(&baseline, |now, base| now.push_str(base));
+	assert_eq!(delta, 2);
+	assert_eq!(text, "abcdab");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn push_trails() {
+	let mut trail = Vec::new();
+	let mut lens = Vec::new();
+	let out = ["a", "bcd"]
+		.iter()
+		.map(|s| {
+			s.to_string()
+				.tap_push(&mut trail)
+				.tap_push_with(&mut lens, String::len)
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(trail, out);
+	assert_eq!(lens, [1, 3]);
+
+	let mut dbg = Vec::new();
+	let mut dbg_lens = Vec::new();
+	let _ = 5
+		.tap_push_dbg(&mut dbg)
+		.tap_push_with_dbg(&mut dbg_lens, |n| n * 2);
+	if cfg!(debug_assertions) {
+		assert_eq!((dbg, dbg_lens), (vec![5], vec![10]));
+	} else {
+		assert!(dbg.is_empty() && dbg_lens.is_empty());
+	}
+}
+
+#[test]
+fn inspect_alias() {
+	use tap::inspect::Inspect;
+
+	let mut seen = None;
+	let out = vec![3, 1, 2]
+		.inspect(|v| seen = Some(v.len()))
+		.inspect_mut(|v| v.sort());
+	assert_eq!(seen, Some(3));
+	assert_eq!(out, [1, 2, 3]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn deref_lengths() {
+	use std::{collections::HashSet, rc::Rc, sync::Arc};
+
+	let mut lens = vec![];
+	let _ = Arc::new(vec![1, 2, 3]).tap_deref_len(|n| lens.push(n));
+	let _ = Box::<[u8]>::from(vec![0; 4]).tap_deref_len(|n| lens.push(n));
+	let _ = Rc::<str>::from("hello").tap_deref_len(|n| lens.push(n));
+	let _ = String::from("hi").tap_deref_len(|n| lens.push(n));
+	let _ = Box::new(HashSet::<i32>::new()).tap_deref_len(|n| lens.push(n));
+	assert_eq!(lens, [3, 4, 5, 2, 0]);
+}
+
+#[test]
+fn diff_tracking() {
+	let mut diff = TapDiff::new();
+	let mut seen = vec![];
+	let out = vec!["a", "a", "b"]
+		.into_iter()
+		.map(|s| {
+			diff.tap_diff(s.to_string(), |new, old| {
+				seen.push((new.clone(), old.cloned()))
+			})
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(out, ["a", "a", "b"]);
+	assert_eq!(
+		seen,
+		[
+			("a".to_string(), None),
+			("a".to_string(), Some("a".to_string())),
+			("b".to_string(), Some("a".to_string())),
+		]
+	);
+	assert_eq!(diff.last().map(String::as_str), Some("b"));
+
+	let mut changed = TapDiff::new();
+	let mut calls = 0;
+	for n in [1, 1, 2, 2, 2, 1] {
+		let _ = changed.tap_if_changed(n, |_, _| calls += 1);
+	}
+	assert_eq!(calls, 2);
+}
+
+#[test]
+fn change_detection() {
+	let mut prev = None;
+	let mut seen = vec![];
+	for n in [1, 1, 2, 2, 3, 1] {
+		let _ = n.tap_on_change(&mut prev, |new, old| seen.push((*old, *new)));
+	}
+	assert_eq!(seen, [(1, 2), (2, 3), (3, 1)]);
+	assert_eq!(prev, Some(1));
+
+	let mut dbg_prev = None;
+	let mut calls = 0;
+	for n in [1, 2, 3] {
+		let _ = n.tap_on_change_dbg(&mut dbg_prev, |_, _| calls += 1);
+	}
+	if cfg!(debug_assertions) {
+		assert_eq!((calls, dbg_prev), (2, Some(3)));
+	} else {
+		assert_eq!((calls, dbg_prev), (0, None));
+	}
+}
+
+#[test]
+fn mutation_change_detection() {
+	let (n, changed) = 5.tap_mut_if_changed(|n| *n = (*n).max(3));
+	assert_eq!((n, changed), (5, false));
+	let (n, changed) = n.tap_mut_if_changed(|n| *n *= 2);
+	assert_eq!((n, changed), (10, true));
+	let (v, changed) = [3, 1, 2].tap_mut_if_changed(|v| v.sort());
+	assert_eq!((v, changed), ([1, 2, 3], true));
+	let (_, changed) = v.tap_mut_if_changed(|v| v.sort());
+	assert!(!changed);
+}
+
+#[test]
+fn maybe_taps() {
+	let lookup = [Some("x"), None];
+	let mut hits = vec![];
+	let out = [0usize, 1, 5].map(|idx| {
+		idx.tap_maybe(|idx| {
+			hits.push((*lookup.get(*idx)?)?);
+			Some(())
+		})
+	});
+	assert_eq!(out, [0, 1, 5]);
+	assert_eq!(hits, ["x"]);
+}
+
+#[test]
+#[cfg(target_has_atomic = "8")]
+fn once_flags() {
+	use std::sync::atomic::{AtomicBool, Ordering};
+
+	let flag = AtomicBool::new(false);
+	let mut runs = 0;
+	let out = (0..4)
+		.map(|n| n.tap_once_flag(&flag, |_| runs += 1))
+		.sum::<i32>();
+	assert_eq!((out, runs), (6, 1));
+	assert!(flag.load(Ordering::SeqCst));
+
+	let mut seen = vec![];
+	for n in 0..3 {
+		let _ = tap::tap_once!(n + 1, |n| seen.push(*n));
+	}
+	assert_eq!(seen, [1]);
+}
+
+#[test]
+fn write_sinks() {
+	use std::fmt;
+
+	let mut out = String::new();
+	let val = (1..4)
+		.sum::<i32>()
+		.tap_writeln(&mut out)
+		.tap_write(&mut out, |w, v| write!(w, "doubled: {}", v * 2))
+		.tap_write(&mut out, |_, _| Err(fmt::Error));
+	assert_eq!(val, 6);
+	assert_eq!(out, "6\ndoubled: 12");
+}
+
+#[test]
+fn counted_sampling() {
+	use std::sync::atomic::{AtomicU64, Ordering};
+
+	let counter = AtomicU64::new(0);
+	let mut sampled = vec![];
+	for n in 0..12 {
+		let _ = n.tap_sample_n_in_m(1, 4, &counter, |n| sampled.push(*n));
+	}
+	assert_eq!(sampled, [0, 4, 8]);
+	assert_eq!(counter.load(Ordering::Relaxed), 12);
+
+	let all = AtomicU64::new(0);
+	let mut every = 0;
+	for n in 0..5 {
+		let _ = n.tap_sample_n_in_m(3, 2, &all, |_| every += 1);
+	}
+	assert_eq!(every, 5);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_sampling() {
+	let (mut never, mut always, mut half) = (0, 0, 0);
+	for n in 0..1000 {
+		let _ = n
+			.tap_sample(0.0, |_| never += 1)
+			.tap_sample(1.0, |_| always += 1)
+			.tap_sample(0.5, |_| half += 1);
+	}
+	assert_eq!((never, always), (0, 1000));
+	assert!(half > 300 && half < 700, "{} of 1000 sampled", half);
+}
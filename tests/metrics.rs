@@ -0,0 +1,51 @@
+#![cfg(feature = "metrics")]
+
+extern crate metrics;
+extern crate metrics_util;
+extern crate tap;
+
+use metrics_util::{
+	debugging::{DebugValue, DebuggingRecorder},
+	CompositeKey, MetricKind,
+};
+use tap::prelude::*;
+
+fn counter(
+	snapshot: &[(
+		CompositeKey,
+		Option<metrics::Unit>,
+		Option<metrics::SharedString>,
+		DebugValue,
+	)],
+	name: &str,
+) -> Option<u64> {
+	snapshot.iter().find_map(|(key, _, _, value)| {
+		match (key.kind(), key.key().name(), value) {
+			(MetricKind::Counter, n, DebugValue::Counter(c)) if n == name => {
+				Some(*c)
+			}
+			_ => None,
+		}
+	})
+}
+
+#[test]
+fn result_counters() {
+	let recorder = DebuggingRecorder::new();
+	let snapshotter = recorder.snapshotter();
+
+	metrics::with_local_recorder(&recorder, || {
+		for input in &["1", "x", "3", "y", "z"] {
+			let _ = input
+				.parse::<i32>()
+				.tap_ok_metric("parse_ok")
+				.tap_err_metric("parse_err");
+		}
+		let _ = Ok::<_, ()>(1).tap_err_metric("never");
+	});
+
+	let snapshot = snapshotter.snapshot().into_vec();
+	assert_eq!(counter(&snapshot, "parse_ok"), Some(2));
+	assert_eq!(counter(&snapshot, "parse_err"), Some(3));
+	assert_eq!(counter(&snapshot, "never"), None);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "opentelemetry")]
+
+extern crate opentelemetry;
+extern crate opentelemetry_sdk;
+extern crate tap;
+
+use opentelemetry::{
+	trace::{Tracer, TracerProvider},
+	KeyValue, Value,
+};
+use opentelemetry_sdk::trace::{InMemorySpanExporterBuilder, SdkTracerProvider};
+use tap::prelude::*;
+
+#[test]
+fn records_on_active_span() {
+	let exporter = InMemorySpanExporterBuilder::new().build();
+	let provider = SdkTracerProvider::builder()
+		.with_simple_exporter(exporter.clone())
+		.build();
+	let tracer = provider.tracer("tap");
+
+	let out = tracer.in_span("load", |_| {
+		vec![4, 5, 6]
+			.tap_otel_attribute("items", |v| Value::I64(v.len() as i64))
+			.tap_otel_attribute_dbg("debug", |_| Value::Bool(true))
+			.tap_otel_event("loaded", |v| {
+				vec![KeyValue::new("first", v[0] as i64)]
+			})
+			.tap_otel_event_dbg("loaded.dbg", |_| vec![])
+	});
+	assert_eq!(out, [4, 5, 6]);
+
+	let spans = exporter.get_finished_spans().unwrap();
+	assert_eq!(spans.len(), 1);
+	let span = &spans[0];
+	assert!(span.attributes.contains(&KeyValue::new("items", 3i64)));
+	assert_eq!(
+		span.attributes.contains(&KeyValue::new("debug", true)),
+		cfg!(debug_assertions),
+	);
+	let events = &span.events.events;
+	assert_eq!(events[0].name, "loaded");
+	assert_eq!(events[0].attributes, [KeyValue::new("first", 4i64)]);
+	assert_eq!(events.len(), if cfg!(debug_assertions) { 2 } else { 1 });
+}
+
+#[test]
+fn skipped_without_span() {
+	let mut calls = 0;
+	let out = 5
+		.tap_otel_attribute("n", |n| {
+			calls += 1;
+			Value::I64(*n)
+		})
+		.tap_otel_event("seen", |_| {
+			calls += 1;
+			vec![]
+		});
+	assert_eq!((out, calls), (5, 0));
+}
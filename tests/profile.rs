@@ -0,0 +1,60 @@
+#![cfg(feature = "std")]
+
+extern crate tap;
+
+use std::thread;
+use tap::{prelude::*, profile};
+
+//  The registry is global, so all assertions live in one test to keep the test
+//  harness’s threads from resetting each other’s measurements.
+#[test]
+fn accumulate_across_threads() {
+	let handles = (0..4)
+		.map(|_| {
+			thread::spawn(|| {
+				for n in 0..25 {
+					let _ = n
+						.tap_profile("even", |_| {})
+						.tap_profile("odd", |_| thread::yield_now());
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+
+	let report = profile::report();
+	assert_eq!(report.len(), 2);
+	assert_eq!(report[0].label, "even");
+	assert_eq!(report[1].label, "odd");
+	for entry in &report {
+		assert_eq!(entry.count, 100);
+		assert!(entry.min <= entry.max);
+		assert!(entry.mean() <= entry.max);
+		assert!(entry.total >= entry.max);
+	}
+
+	profile::set_enabled(false);
+	let _ = 5.tap_profile("disabled", |_| {});
+	profile::set_enabled(true);
+	assert!(profile::report().iter().all(|e| e.label != "disabled"));
+
+	profile::reset();
+	assert!(profile::report().is_empty());
+}
+
+#[test]
+fn profiler_scopes() {
+	let mut seen = vec![];
+	let out = tap::tap_profile_scope!(vec![1, 2], "scoped", |v| {
+		seen.extend_from_slice(v)
+	});
+	assert_eq!(out, [1, 2]);
+	assert_eq!(seen, [1, 2]);
+
+	let mut calls = 0;
+	let out = tap::tap_profile_scope_dbg!(5, "scoped_dbg", |_| calls += 1);
+	assert_eq!(out, 5);
+	assert_eq!(calls, if cfg!(debug_assertions) { 1 } else { 0 });
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "std")]
+
+extern crate tap;
+
+use std::thread;
+use tap::{
+	prelude::*,
+	testing::{MockTap, Spy},
+};
+
+#[test]
+fn spy_records_calls() {
+	let spy = Spy::new();
+	assert_eq!(spy.call_count(), 0);
+	assert_eq!(spy.last_call(), None);
+
+	let out = (1..4).map(|n| n.tap(spy.observe())).collect::<Vec<_>>();
+	assert_eq!(out, [1, 2, 3]);
+	assert_eq!(spy.calls(), [1, 2, 3]);
+	assert_eq!(spy.last_call(), Some(3));
+	spy.assert_called_n_times(3);
+	spy.assert_last_call(&3);
+	assert_eq!(format!("{:?}", spy), "Spy { calls: [1, 2, 3] }");
+}
+
+#[test]
+fn spy_shared_across_threads() {
+	let spy = Spy::<String>::default();
+	let handles = (0..4)
+		.map(|n| {
+			let observe = spy.observe();
+			thread::spawn(move || {
+				let _ = n.to_string().tap(&observe);
+			})
+		})
+		.collect::<Vec<_>>();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+	let mut calls = spy.clone().calls();
+	calls.sort();
+	assert_eq!(calls, ["0", "1", "2", "3"]);
+}
+
+#[test]
+fn spy_assertions_fail() {
+	use std::panic::catch_unwind;
+
+	let spy = Spy::<u8>::new();
+	assert!(catch_unwind(|| spy.assert_last_call(&0)).is_err());
+	let _ = 5u8.tap(spy.observe());
+	assert!(catch_unwind(|| spy.assert_called_n_times(2)).is_err());
+	assert!(catch_unwind(|| spy.assert_last_call(&6)).is_err());
+}
+
+#[test]
+fn mock_expectations_met() {
+	let mut mock = MockTap::new()
+		.expect_calls(3)
+		.expect_all(|s: &&str| !s.is_empty())
+		.expect_all(|s: &&str| s.is_ascii())
+		.build();
+	let words = ["a", "bc", "def"]
+		.iter()
+		.copied()
+		.map(|w| w.tap(&mut mock))
+		.collect::<Vec<_>>();
+	drop(mock);
+	assert_eq!(words, ["a", "bc", "def"]);
+}
+
+#[test]
+fn mock_lists_failures() {
+	let res = std::panic::catch_unwind(|| {
+		let mut mock = MockTap::new()
+			.expect_calls(1)
+			.expect_all(|n: &i32| *n >= 0)
+			.build();
+		for n in [1, -2] {
+			let _ = n.tap(&mut mock);
+		}
+	});
+	let msg = *res.unwrap_err().downcast::<String>().unwrap();
+	assert_eq!(
+		msg,
+		"MockTap expectations failed:\n\
+		 - expected 1 calls, but there were 2\n\
+		 - call 1 did not satisfy `expect_all` #0\n"
+	);
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "std")]
+
+extern crate tap;
+
+use std::{
+	sync::{mpsc, Arc, Mutex},
+	thread,
+};
+use tap::prelude::*;
+
+#[test]
+fn thread_id_differs() {
+	let mut here = None;
+	let _ = 5.tap_thread_id(|_, id| here = Some(id));
+	let there = thread::spawn(|| {
+		let mut there = None;
+		let _ = 5.tap_thread_id(|_, id| there = Some(id));
+		there
+	})
+	.join()
+	.unwrap();
+	assert!(here.is_some());
+	assert_ne!(here, there);
+}
+
+#[test]
+fn thread_name() {
+	thread::Builder::new()
+		.name("tapped".to_string())
+		.spawn(|| {
+			let mut seen = None;
+			let _ = 5.tap_thread_name(|_, name| seen = name.map(str::to_string));
+			assert_eq!(seen.as_deref(), Some("tapped"));
+		})
+		.unwrap()
+		.join()
+		.unwrap();
+}
+
+#[test]
+fn channel_send() {
+	let (tx, rx) = mpsc::channel();
+	let worker = thread::spawn(move || rx.iter().collect::<Vec<String>>());
+
+	let words = ["alpha", "beta", "gamma"]
+		.iter()
+		.copied()
+		.map(|w| w.tap_channel_send(&tx, |w| w.to_uppercase()))
+		.collect::<Vec<_>>();
+	drop(tx);
+
+	assert_eq!(words, ["alpha", "beta", "gamma"]);
+	assert_eq!(worker.join().unwrap(), ["ALPHA", "BETA", "GAMMA"]);
+}
+
+#[test]
+fn channel_send_disconnected() {
+	let (tx, rx) = mpsc::channel::<i32>();
+	drop(rx);
+	assert_eq!(5.tap_channel_send(&tx, |v| *v), 5);
+	#[cfg(feature = "log")]
+	assert_eq!(5.tap_channel_send_or_log(&tx, |v| *v), 5);
+	let res =
+		std::panic::catch_unwind(|| 5.tap_channel_send_or_panic(&tx, |v| *v));
+	assert!(res.is_err());
+}
+
+#[test]
+#[cfg(all(target_has_atomic = "ptr", target_has_atomic = "64"))]
+fn counters_across_threads() {
+	use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+	static ALL: AtomicUsize = AtomicUsize::new(0);
+	static EVENS: AtomicUsize = AtomicUsize::new(0);
+	static OKS: AtomicUsize = AtomicUsize::new(0);
+	static ERRS: AtomicUsize = AtomicUsize::new(0);
+	static BYTES: AtomicU64 = AtomicU64::new(0);
+
+	let handles = (0..4)
+		.map(|_| {
+			thread::spawn(|| {
+				for n in 0..100u32 {
+					let res: Result<u32, u32> =
+						if n % 10 == 0 { Err(n) } else { Ok(n) };
+					let _ = n
+						.tap_count(&ALL)
+						.tap_count_if(&EVENS, |n| n % 2 == 0)
+						.tap_add(&BYTES, |_| 4);
+					let _ = res.tap_ok_count(&OKS).tap_err_count(&ERRS);
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+
+	assert_eq!(ALL.load(Ordering::Relaxed), 400);
+	assert_eq!(EVENS.load(Ordering::Relaxed), 200);
+	assert_eq!(OKS.load(Ordering::Relaxed), 360);
+	assert_eq!(ERRS.load(Ordering::Relaxed), 40);
+	assert_eq!(BYTES.load(Ordering::Relaxed), 1600);
+}
+
+#[test]
+fn lock_through_poison() {
+	let shared = Arc::new(Mutex::new(vec![1, 2]));
+	let _ = (&*shared).tap_lock_mut(|v| v.push(3));
+
+	let poisoner = Arc::clone(&shared);
+	let res = thread::spawn(move || {
+		let _guard = poisoner.lock().unwrap();
+		panic!("poison the lock");
+	})
+	.join();
+	assert!(res.is_err());
+	assert!(shared.is_poisoned());
+
+	let mut seen = vec![];
+	let _ = (&*shared)
+		.tap_lock(|v| seen = v.clone())
+		.tap_lock_mut(|v| v.push(4));
+	assert_eq!(seen, [1, 2, 3]);
+	assert!(shared.is_poisoned());
+
+	let owned = Arc::try_unwrap(shared)
+		.unwrap()
+		.tap_lock_mut(|v| v.retain(|n| n % 2 == 0))
+		.tap_lock(|v| assert_eq!(v, &[2, 4]));
+	assert!(owned.is_poisoned());
+}
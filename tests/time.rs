@@ -0,0 +1,107 @@
+#![cfg(feature = "std")]
+
+#[macro_use]
+extern crate tap;
+
+use std::{
+	thread,
+	time::{Duration, Instant},
+};
+use tap::prelude::*;
+
+#[test]
+fn elapsed_is_monotonic() {
+	let start = Instant::now();
+	let mut laps = Vec::new();
+	for n in 0..5 {
+		let _ = n.tap_elapsed(start, |_, d| laps.push(d));
+	}
+	assert!(laps.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn elapsed_since_last() {
+	let mut laps: Vec<Option<Duration>> = Vec::new();
+	for n in 0..5 {
+		let out = tap_elapsed_since_last!(n, |v, lap| {
+			assert_eq!(*v, n);
+			laps.push(lap);
+		});
+		assert_eq!(out, n);
+	}
+	assert_eq!(laps[0], None);
+	assert!(laps[1..].iter().all(Option::is_some));
+
+	// a separate call site keeps its own timestamp
+	tap_elapsed_since_last!((), |_, lap| assert!(lap.is_none()));
+}
+
+#[test]
+fn slow_warn_threshold() {
+	let pause = Duration::from_millis(20);
+
+	let mut slow = None;
+	let out = 5.tap_slow_warn(
+		Duration::from_millis(1),
+		|_| thread::sleep(pause),
+		|d| slow = Some(d),
+	);
+	assert_eq!(out, 5);
+	assert!(slow.unwrap() >= pause);
+
+	let mut slow = None;
+	let _ = 5.tap_slow_warn(Duration::from_secs(60), |_| {}, |d| slow = Some(d));
+	assert!(slow.is_none());
+
+	let mut slow = None;
+	let out =
+		5.pipe_slow_warn(Duration::from_secs(60), |x| x + 1, |d| slow = Some(d));
+	assert_eq!(out, 6);
+	assert!(slow.is_none());
+}
+
+#[test]
+#[cfg(target_has_atomic = "64")]
+fn rate_limited_taps() {
+	let bucket = TokenBucket::new(2, 50.0);
+	let (mut ran, mut throttled) = (0, 0);
+	for n in 0..5 {
+		let _ = n.tap_rate_limited_or(&bucket, || throttled += 1, |_| ran += 1);
+	}
+	assert_eq!((ran, throttled), (2, 3));
+
+	thread::sleep(Duration::from_millis(60));
+	let mut later = 0;
+	for n in 0..5 {
+		let _ = n.tap_rate_limited(&bucket, |_| later += 1);
+	}
+	assert!(
+		(1..=2).contains(&later),
+		"{} taps ran after refilling",
+		later
+	);
+}
+
+#[test]
+#[cfg(target_has_atomic = "64")]
+fn rate_limit_shared_across_threads() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static BUCKET: TokenBucket = TokenBucket::new(10, 0.0);
+	static RAN: AtomicUsize = AtomicUsize::new(0);
+	let workers = (0..4)
+		.map(|_| {
+			thread::spawn(|| {
+				for n in 0..100 {
+					let _ = n.tap_rate_limited(&BUCKET, |_| {
+						RAN.fetch_add(1, Ordering::Relaxed);
+					});
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+	for worker in workers {
+		worker.join().unwrap();
+	}
+	assert_eq!(RAN.load(Ordering::Relaxed), 10);
+}
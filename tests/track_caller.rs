@@ -0,0 +1,40 @@
+#![cfg(feature = "std")]
+
+extern crate tap;
+
+use std::{
+	panic::{self, Location},
+	sync::{mpsc, Mutex},
+};
+use tap::prelude::*;
+
+//  The panic hook is process-global, so both checks live in one test.
+#[test]
+fn panics_report_the_caller() {
+	static SEEN: Mutex<Vec<(String, u32)>> = Mutex::new(Vec::new());
+	let hook = panic::take_hook();
+	panic::set_hook(Box::new(|info| {
+		let loc = info.location().unwrap();
+		SEEN.lock()
+			.unwrap()
+			.push((loc.file().to_string(), loc.line()));
+	}));
+
+	let line = Location::caller().line() + 1;
+	let res = panic::catch_unwind(|| 5.tap(|_| panic!("inside effect")));
+	assert!(res.is_err());
+
+	let (tx, rx) = mpsc::channel::<i32>();
+	drop(rx);
+	let res = panic::catch_unwind(|| 5.tap_channel_send_or_panic(&tx, |v| *v));
+	assert!(res.is_err());
+
+	panic::set_hook(hook);
+	let seen = SEEN.lock().unwrap();
+	assert_eq!(seen.len(), 2);
+	for (file, _) in seen.iter() {
+		assert_eq!(file, file!());
+	}
+	assert_eq!(seen[0].1, line);
+	assert_eq!(seen[1].1, line + 5);
+}
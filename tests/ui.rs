@@ -0,0 +1,11 @@
+#[test]
+fn ui() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+	if cfg!(feature = "iter") {
+		t.compile_fail("tests/ui/iter/*.rs");
+	}
+	if cfg!(feature = "derive") {
+		t.compile_fail("tests/ui/derive/*.rs");
+	}
+}
@@ -0,0 +1,8 @@
+extern crate tap;
+
+use std::sync::Arc;
+use tap::Tap;
+
+fn main() {
+	let _ = Arc::new(vec![1, 2, 3]).tap_deref2_mut(|s: &mut [i32]| s.sort());
+}
@@ -0,0 +1,13 @@
+extern crate tap;
+
+use tap::Tap;
+
+#[derive(Tap)]
+struct Point {
+	#[tap(hide)]
+	x: i32,
+	#[tap(rename = "not an ident")]
+	y: i32,
+}
+
+fn main() {}
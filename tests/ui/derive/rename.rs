@@ -0,0 +1,16 @@
+extern crate tap;
+
+use tap::Tap;
+
+#[derive(Tap)]
+struct Endpoint {
+	#[tap(rename = "addr")]
+	address: String,
+}
+
+fn main() {
+	let ep = Endpoint {
+		address: "localhost".into(),
+	};
+	let _ = ep.tap_addr(|_| {}).tap_address(|_| {});
+}
@@ -0,0 +1,14 @@
+extern crate tap;
+
+use std::sync::atomic::AtomicUsize;
+use tap::Tap;
+
+#[derive(Tap)]
+struct Stats {
+	count: u32,
+}
+
+fn main() {
+	let seen = AtomicUsize::new(0);
+	let _ = Stats { count: 0 }.tap_count(&seen);
+}
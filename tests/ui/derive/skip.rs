@@ -0,0 +1,18 @@
+extern crate tap;
+
+use tap::Tap;
+
+#[derive(Tap)]
+struct Session {
+	user: String,
+	#[tap(skip)]
+	secret: String,
+}
+
+fn main() {
+	let session = Session {
+		user: "root".into(),
+		secret: "hunter2".into(),
+	};
+	let _ = session.tap_user(|_| {}).tap_secret(|_| {});
+}
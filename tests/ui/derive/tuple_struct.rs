@@ -0,0 +1,8 @@
+extern crate tap;
+
+use tap::Tap;
+
+#[derive(Tap)]
+struct Meters(f64);
+
+fn main() {}
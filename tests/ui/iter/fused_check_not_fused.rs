@@ -0,0 +1,21 @@
+extern crate tap;
+
+use std::iter::FusedIterator;
+use tap::iter::TapIter;
+
+struct Flicker(usize);
+
+impl Iterator for Flicker {
+	type Item = usize;
+
+	fn next(&mut self) -> Option<usize> {
+		self.0 += 1;
+		if self.0.is_multiple_of(3) { None } else { Some(self.0) }
+	}
+}
+
+fn require_fused<I: FusedIterator>(_: I) {}
+
+fn main() {
+	require_fused(Flicker(0).tap_fused_check());
+}
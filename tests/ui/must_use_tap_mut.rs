@@ -0,0 +1,11 @@
+#![deny(unused_must_use)]
+
+extern crate tap;
+
+use tap::Tap;
+
+fn main() {
+	let mut data = vec![3, 1, 2];
+	data.clone().tap_mut(|v| v.sort());
+	data.push(4);
+}